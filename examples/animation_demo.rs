@@ -51,7 +51,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     );
     
     // Create a scene and add both nodes to it
-    let main_scene = builder.add_scene(
+    let _main_scene = builder.add_scene(
         Some("AnimatedScene".to_string()),
         Some(vec![static_node, animated_sphere_node])
     );
@@ -106,7 +106,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         timestamps.clone(),
         translations,
         InterpolationType::Linear
-    );
+    )?;
     
     builder.create_rotation_animation(
         orbit_animation,
@@ -114,7 +114,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         timestamps.clone(),
         rotations,
         InterpolationType::Linear
-    );
+    )?;
     
     builder.create_scale_animation(
         orbit_animation,
@@ -122,11 +122,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         timestamps,
         scales,
         InterpolationType::Linear
-    );
+    )?;
     
     // Export the animated scene as a binary glTF file
     let path = Path::new("animation_demo.glb");
-    builder.export_glb(&path.to_str().unwrap())?;
+    builder.export_glb(path.to_str().unwrap())?;
     
     println!("Successfully exported GLB file with animations: {}", path.display());
     println!("This example demonstrates a simple animation with:");
@@ -1,6 +1,6 @@
 use mesh_tools::GltfBuilder;
 use mesh_tools::Triangle;
-use mesh_tools::compat::{Point3, Vector2, Vector3};
+use mesh_tools::compat::Vector3;
 use std::error::Error;
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -8,7 +8,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     let mut builder = GltfBuilder::new();
     
     // Create a basic material for our custom mesh
-    let red_material = builder.create_basic_material(
+    let _red_material = builder.create_basic_material(
         Some("Red Material".to_string()),
         [1.0, 0.0, 0.0, 1.0], // Red color
     );
@@ -284,12 +284,12 @@ fn main() -> Result<(), Box<dyn Error>> {
     builder.export_glb(output_path)?;
     
     println!("Successfully exported GLB file with custom meshes: {}", output_path);
-    println!("");
+    println!();
     println!("This example demonstrates:");
     println!("1. A triangle with simple custom UV mapping (using create_simple_mesh)");
     println!("2. A quad with multiple UV channels (using create_custom_mesh)");
     println!("3. A cube with unique UV mapping for each face (using create_simple_mesh)");
-    println!("");
+    println!();
     println!("Note: Most glTF viewers only display the first UV channel (TEXCOORD_0).");
     println!("The second UV channel in the quad (TEXCOORD_1) is included to demonstrate");
     println!("the API's capability but would need a special material to utilize it.");
@@ -8,7 +8,7 @@ fn create_radial_emissive_texture(width: u32, height: u32, color: [u8; 3]) -> Dy
     let mut img = ImageBuffer::new(width, height);
     let center_x = width as f32 / 2.0;
     let center_y = height as f32 / 2.0;
-    let max_dist = (center_x.min(center_y) * 0.9) as f32;
+    let max_dist = center_x.min(center_y) * 0.9;
     
     for y in 0..height {
         for x in 0..width {
@@ -0,0 +1,62 @@
+use mesh_tools::GltfBuilder;
+use std::error::Error;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    // Create a new glTF builder
+    let mut builder = GltfBuilder::new();
+    
+    // Create a checkerboard texture so the export has an image to write out
+    let checker_texture = builder.create_checkerboard_texture(
+        256,                     // width
+        256,                     // height
+        32,                      // cell size
+        [255, 255, 255],         // white
+        [200, 40, 40],           // red
+    )?;
+    
+    let checker_material = builder.create_textured_material(
+        Some("Checker Material".to_string()),
+        checker_texture
+    );
+    
+    // Create a textured box and a plain sphere
+    let box_mesh = builder.create_box_with_material(1.0, Some(checker_material));
+    let sphere_mesh = builder.create_sphere(0.5, 24, 12, None);
+    
+    let box_node = builder.add_node(
+        Some("BoxNode".to_string()),
+        Some(box_mesh),
+        Some([-1.0, 0.0, 0.0]),
+        None,
+        None,
+    );
+    
+    let sphere_node = builder.add_node(
+        Some("SphereNode".to_string()),
+        Some(sphere_mesh),
+        Some([1.0, 0.0, 0.0]),
+        None,
+        None,
+    );
+    
+    builder.add_scene(
+        Some("Scene".to_string()),
+        Some(vec![box_node, sphere_node]),
+    );
+    
+    // Export as JSON + external buffer, writing the texture as a standalone PNG
+    let output_path = "gltf_export_demo.gltf";
+    builder.export_gltf(output_path, true)?;
+    
+    println!("Successfully exported glTF file: {}", output_path);
+    println!("Buffer data written to: gltf_export_demo.bin");
+    println!("Texture written to: gltf_export_demo_image0.png");
+    
+    // Export the same scene as a single self-contained file with data URIs
+    let embedded_path = "gltf_export_demo_embedded.gltf";
+    builder.export_gltf_embedded(embedded_path, true)?;
+    
+    println!("Successfully exported self-contained glTF file: {}", embedded_path);
+    
+    Ok(())
+}
@@ -210,12 +210,12 @@ fn main() -> Result<(), Box<dyn Error>> {
     builder.export_glb(output_path)?;
     
     println!("Successfully exported a hierarchical scene: {}", output_path);
-    println!("");
+    println!();
     println!("This demonstrates 3 different hierarchy examples:");
     println!("1. A 'solar system' with sun, planets and a moon");
     println!("2. A vertical 'stack' of boxes with parent-child relationships");
     println!("3. A 'wheel' with a hub and spokes created using create_node_hierarchy");
-    println!("");
+    println!();
     println!("The object transformations are relative to their parents. Transforming");
     println!("a parent will automatically transform all of its children.");
     
@@ -174,7 +174,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     
     // Set the default scene to be the first scene
     if let Some(scenes) = &mut builder.gltf.scenes {
-        if scenes.len() > 0 {
+        if !scenes.is_empty() {
             builder.gltf.scene = Some(0);
         }
     }
@@ -184,7 +184,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     builder.export_glb(output_path)?;
     
     println!("Successfully exported instancing demo: {}", output_path);
-    println!("");
+    println!();
     println!("This example demonstrates mesh instancing with:");
     println!("1. A 5x5 grid of torus instances with:");
     println!("   - Different colors (blue, red, gold, green)");
@@ -193,7 +193,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     println!("2. A ring of 12 cylinder instances all using the same mesh:");
     println!("   - Arranged in a circle");
     println!("   - Rotated to point toward the center");
-    println!("");
+    println!();
     println!("This example shows two different approaches to instancing:");
     println!("1. Creating separate meshes with different materials for the torus grid");
     println!("2. True instancing by reusing the same cylinder mesh multiple times");
@@ -63,7 +63,7 @@ fn create_fingerprint_metal(width: u32, height: u32) -> DynamicImage {
             let with_fingerprints = metalness - (noise * 0.5); // Fingerprints reduce metalness
             
             // Scale to 0-255 range
-            let value = (with_fingerprints.max(0.0).min(1.0) * 255.0) as u8;
+            let value = (with_fingerprints.clamp(0.0, 1.0) * 255.0) as u8;
             
             img.put_pixel(x, y, Rgba([value, value, value, 255]));
         }
@@ -328,7 +328,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         Some(fingerprint_torus),
         Some([4.0, -2.0, 0.0]),  // Bottom right
         // Rotate the torus to show its surface better
-        Some([0.7071, 0.0, 0.0, 0.7071]), // 90 degrees around X axis
+        Some([std::f32::consts::FRAC_1_SQRT_2, 0.0, 0.0, std::f32::consts::FRAC_1_SQRT_2]), // 90 degrees around X axis
         None,
     );
     
@@ -8,7 +8,7 @@ fn create_bump_normal_map(width: u32, height: u32, bump_height: f32) -> DynamicI
     let mut img = ImageBuffer::new(width, height);
     let center_x = width as f32 / 2.0;
     let center_y = height as f32 / 2.0;
-    let max_dist = (center_x.min(center_y) * 0.8) as f32;
+    let max_dist = center_x.min(center_y) * 0.8;
     
     for y in 0..height {
         for x in 0..width {
@@ -103,6 +103,9 @@ fn main() -> Result<(), Box<dyn Error>> {
     // Create a new glTF builder
     let mut builder = GltfBuilder::new();
     
+    // Export tangents so that every viewer orients the normal maps the same way
+    builder.set_generate_tangents(true);
+    
     // Create textures for normal maps
     
     // 1. Create a bump normal map (simulates a dome on a flat surface)
@@ -194,7 +197,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         Some("FlatPlaneNode".to_string()),
         Some(flat_plane_mesh),
         Some([-2.0, 0.0, 0.0]),  // Left position
-        Some([0.7071, 0.0, 0.0, 0.7071]),  // Rotate 90 degrees around X to face camera
+        Some([std::f32::consts::FRAC_1_SQRT_2, 0.0, 0.0, std::f32::consts::FRAC_1_SQRT_2]),  // Rotate 90 degrees around X to face camera
         None,
     );
     
@@ -203,7 +206,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         Some("BumpPlaneNode".to_string()),
         Some(bump_plane_mesh),
         Some([0.0, 0.0, 0.0]),   // Center position
-        Some([0.7071, 0.0, 0.0, 0.7071]),  // Rotate 90 degrees around X to face camera
+        Some([std::f32::consts::FRAC_1_SQRT_2, 0.0, 0.0, std::f32::consts::FRAC_1_SQRT_2]),  // Rotate 90 degrees around X to face camera
         None,
     );
     
@@ -212,7 +215,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         Some("WavePlaneNode".to_string()),
         Some(wave_plane_mesh),
         Some([2.0, 0.0, 0.0]),   // Right position
-        Some([0.7071, 0.0, 0.0, 0.7071]),  // Rotate 90 degrees around X to face camera
+        Some([std::f32::consts::FRAC_1_SQRT_2, 0.0, 0.0, std::f32::consts::FRAC_1_SQRT_2]),  // Rotate 90 degrees around X to face camera
         None,
     );
     
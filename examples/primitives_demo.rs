@@ -18,7 +18,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     
     // Set the green material to be double-sided
     if let Some(materials) = &mut builder.gltf.materials {
-        if let Some(material) = materials.get_mut(green_material) {
+        if let Some(material) = materials.get_mut(green_material.index()) {
             material.double_sided = Some(true);
         }
     }
@@ -170,7 +170,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     builder.export_glb(output_path)?;
     
     println!("Successfully exported GLB file with primitive shapes: {}", output_path);
-    println!("");
+    println!();
     println!("This example demonstrates these primitive shapes:");
     println!("1. Plane (green)");
     println!("2. Sphere (blue)");
@@ -40,7 +40,7 @@ fn create_roughness_pattern(width: u32, height: u32) -> DynamicImage {
             let dist = ((dx * dx + dy * dy) * 20.0).sqrt();
             
             // Use sin wave to create rings pattern
-            let roughness = (dist.sin() * 0.5 + 0.5).max(0.0).min(1.0);
+            let roughness = (dist.sin() * 0.5 + 0.5).clamp(0.0, 1.0);
             
             // Scale to 0-255 range
             let value = (roughness * 255.0) as u8;
@@ -83,7 +83,7 @@ fn create_weathered_texture(width: u32, height: u32) -> DynamicImage {
             let roughness = 0.7 - (edge_factor * 0.4) + (noise1 * 0.3) - (noise2 * 0.2);
             
             // Ensure it's in 0-1 range then scale to 0-255
-            let value = (roughness.max(0.0).min(1.0) * 255.0) as u8;
+            let value = (roughness.clamp(0.0, 1.0) * 255.0) as u8;
             
             img.put_pixel(x, y, Rgba([value, value, value, 255]));
         }
@@ -122,11 +122,11 @@ fn create_scratched_texture(width: u32, height: u32) -> DynamicImage {
             let y = start_y + (i as f32 * angle.sin()) as i32;
             
             // Make sure we're within bounds
-            if x >= 0 && x < width as i32 && y >= 0 && y < height as i32 {
+            if x >= 0 && x < width && y >= 0 && y < height as i32 {
                 // Draw width of scratch
                 for w in -width/2..=width/2 {
                     let sx = x + w;
-                    if sx >= 0 && sx < width as i32 {
+                    if sx >= 0 && sx < width {
                         img.put_pixel(sx as u32, y as u32, Rgba([scratch_roughness, scratch_roughness, scratch_roughness, 255]));
                     }
                 }
@@ -337,7 +337,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         Some(gradient_plane),
         Some([1.5, 2.0, 0.0]),   // Top middle-right
         // Rotate the plane to be vertical
-        Some([0.7071, 0.0, 0.0, 0.7071]), // 90 degrees around X axis
+        Some([std::f32::consts::FRAC_1_SQRT_2, 0.0, 0.0, std::f32::consts::FRAC_1_SQRT_2]), // 90 degrees around X axis
         None,
     );
     
@@ -362,7 +362,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         Some(scratched_torus),
         Some([2.5, -2.0, 0.0]),   // Bottom right
         // Rotate the torus to show its surface better
-        Some([0.7071, 0.0, 0.0, 0.7071]), // 90 degrees around X axis
+        Some([std::f32::consts::FRAC_1_SQRT_2, 0.0, 0.0, std::f32::consts::FRAC_1_SQRT_2]), // 90 degrees around X axis
         None,
     );
     
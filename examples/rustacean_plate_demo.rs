@@ -1,6 +1,5 @@
 use mesh_tools::GltfBuilder;
 use std::error::Error;
-use std::fs::File;
 use std::path::Path;
 use image::io::Reader as ImageReader;
 
@@ -43,7 +42,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         Some("RustaceanPlateNode".to_string()),
         Some(plate_mesh),
         Some([0.0, 0.0, 0.0]),  // Center position
-        Some([0.7071, 0.0, 0.0, 0.7071]),  // Rotate 90 degrees around X to face camera
+        Some([std::f32::consts::FRAC_1_SQRT_2, 0.0, 0.0, std::f32::consts::FRAC_1_SQRT_2]),  // Rotate 90 degrees around X to face camera
         None,
     );
     
@@ -1,16 +1,7 @@
-use mesh_tools::{GltfBuilder, Triangle};
-use mesh_tools::compat::{Point3, Vector2, Vector3};
+use mesh_tools::{GltfBuilder, MaterialId, NodeId, Triangle};
+use mesh_tools::geometry::{generate_normals, NormalMode};
 use std::error::Error;
 
-// Helper function to calculate cross product for mint Vector3
-fn cross(a: Vector3<f32>, b: Vector3<f32>) -> Vector3<f32> {
-    mesh_tools::compat::vector3::new(
-        a.y * b.z - a.z * b.y,
-        a.z * b.x - a.x * b.z,
-        a.x * b.y - a.y * b.x
-    )
-}
-
 fn main() -> Result<(), Box<dyn Error>> {
     // Create a new glTF builder
     let mut builder = GltfBuilder::new();
@@ -46,23 +37,35 @@ fn main() -> Result<(), Box<dyn Error>> {
     );
     
     // Generate procedural terrain mesh
-    generate_terrain_mesh(&mut builder, 64, 64, 10.0, 2.0,
-                         grass_material, mountain_material, snow_material, 
-                         water_material, sand_material)?;
+    let terrain = generate_terrain_mesh(&mut builder, 64, 64, 10.0, 2.0,
+                                        grass_material, mountain_material, snow_material, 
+                                        water_material, sand_material)?;
     
     // Add a camera viewpoint
+    let camera = builder.add_perspective_camera(
+        Some("Viewpoint Camera".to_string()),
+        std::f32::consts::FRAC_PI_4, // 45 degree vertical field of view
+        None, // Use the viewport's aspect ratio
+        0.1,
+        Some(100.0),
+    );
+    
     let viewpoint = builder.add_node(
         Some("Viewpoint".to_string()),
         None, // No mesh for viewpoint
-        Some([0.0, 15.0, 40.0]), // Position for a good view
-        Some([-0.4, 0.0, 0.0, 0.92]), // Rotation quaternion looking down at scene
+        Some([0.0, 8.0, 12.0]), // Position for a good view
+        None,
         None,
     );
+    builder.attach_camera(viewpoint, camera)?;
+    
+    // Look down at the center of the terrain
+    builder.look_at(viewpoint, [0.0, 0.0, 0.0], None)?;
     
-    // Set the default scene to include our viewpoint
+    // Create the scene with the terrain and the viewpoint
     builder.add_scene(
         Some("Terrain Scene".to_string()),
-        Some(vec![viewpoint]),
+        Some(vec![terrain, viewpoint]),
     );
     
     // Export the GLB file
@@ -70,7 +73,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     builder.export_glb(output_path)?;
     
     println!("Successfully exported procedural terrain: {}", output_path);
-    println!("");
+    println!();
     println!("This example demonstrates procedural terrain generation with:");
     println!("1. Height map based on mathematical noise functions");
     println!("2. Material variation based on elevation and slope");
@@ -80,19 +83,20 @@ fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-/// Generate a terrain mesh with materials based on height and slope
+/// Generate a terrain mesh with materials based on height and slope, returning its node
+#[allow(clippy::too_many_arguments)]
 fn generate_terrain_mesh(
     builder: &mut GltfBuilder,
     width_segments: usize,
     depth_segments: usize,
     width: f32,
     height_scale: f32,
-    grass_material: usize,
-    mountain_material: usize,
-    snow_material: usize,
-    water_material: usize,
-    sand_material: usize,
-) -> Result<(), Box<dyn Error>> {
+    grass_material: MaterialId,
+    mountain_material: MaterialId,
+    snow_material: MaterialId,
+    water_material: MaterialId,
+    sand_material: MaterialId,
+) -> Result<NodeId, Box<dyn Error>> {
     // Height map dimensions
     let grid_width = width_segments + 1;
     let grid_depth = depth_segments + 1;
@@ -102,7 +106,6 @@ fn generate_terrain_mesh(
     
     // Calculate vertices, normals, and indices using nalgebra types
     let mut positions = Vec::new();
-    let mut normals = Vec::new();
     let mut raw_indices = Vec::new(); // We'll convert these to Triangle structs later
     let mut texcoords = Vec::new();
     
@@ -159,10 +162,13 @@ fn generate_terrain_mesh(
         }
     }
     
-    // Calculate normals using the nalgebra types
-    normals = calculate_normals(&positions, &raw_indices);
+    // Calculate smooth normals from the triangles
+    let triangles: Vec<Triangle> = raw_indices.chunks_exact(3)
+        .map(|t| Triangle::new(t[0] as u32, t[1] as u32, t[2] as u32))
+        .collect();
+    let normals = generate_normals(&positions, &triangles, NormalMode::SmoothArea)?.normals;
     
-    // Create separate meshes based on height and slope for different materials
+    // Split the terrain into regions based on height and slope for different materials
     let water_threshold = 0.0;
     let sand_threshold = 0.2;
     let grass_threshold = 2.0;
@@ -176,7 +182,7 @@ fn generate_terrain_mesh(
     let mut snow_triangles = Vec::new();
     
     // Assign triangles to appropriate materials based on height
-    for (i, triangle) in raw_indices.chunks_exact(3).enumerate() {
+    for triangle in raw_indices.chunks_exact(3) {
         let idx1 = triangle[0] as usize;
         let idx2 = triangle[1] as usize;
         let idx3 = triangle[2] as usize;
@@ -214,161 +220,38 @@ fn generate_terrain_mesh(
         }
     }
     
-    // Create separate meshes for each terrain type using nalgebra types
-    let water_mesh = if !water_triangles.is_empty() {
-        // Convert the Vector2 texcoords to a Vec<Vec<Vector2>> for multiple UV sets
-        let texcoord_sets = Some(vec![texcoords.clone()]);
-        
-        Some(builder.create_custom_mesh(
-            Some("Water".to_string()),
-            &positions,
-            &water_triangles,
-            Some(normals.clone()),
-            texcoord_sets,
-            Some(water_material),
-        ))
-    } else {
-        None
-    };
-    
-    let sand_mesh = if !sand_triangles.is_empty() {
-        // Convert the Vector2 texcoords to a Vec<Vec<Vector2>> for multiple UV sets
-        let texcoord_sets = Some(vec![texcoords.clone()]);
-        
-        Some(builder.create_custom_mesh(
-            Some("Sand".to_string()),
-            &positions,
-            &sand_triangles,
-            Some(normals.clone()),
-            texcoord_sets,
-            Some(sand_material),
-        ))
-    } else {
-        None
-    };
-    
-    let grass_mesh = if !grass_triangles.is_empty() {
-        // Convert the Vector2 texcoords to a Vec<Vec<Vector2>> for multiple UV sets
-        let texcoord_sets = Some(vec![texcoords.clone()]);
-        
-        Some(builder.create_custom_mesh(
-            Some("Grass".to_string()),
-            &positions,
-            &grass_triangles,
-            Some(normals.clone()),
-            texcoord_sets,
-            Some(grass_material),
-        ))
-    } else {
-        None
-    };
-    
-    let mountain_mesh = if !mountain_triangles.is_empty() {
-        // Convert the Vector2 texcoords to a Vec<Vec<Vector2>> for multiple UV sets
-        let texcoord_sets = Some(vec![texcoords.clone()]);
-        
-        Some(builder.create_custom_mesh(
-            Some("Mountain".to_string()),
-            &positions,
-            &mountain_triangles,
-            Some(normals.clone()),
-            texcoord_sets,
-            Some(mountain_material),
-        ))
-    } else {
-        None
-    };
-    
-    let snow_mesh = if !snow_triangles.is_empty() {
-        // Convert the Vector2 texcoords to a Vec<Vec<Vector2>> for multiple UV sets
-        let texcoord_sets = Some(vec![texcoords.clone()]);
-        
-        Some(builder.create_custom_mesh(
-            Some("Snow".to_string()),
-            &positions,
-            &snow_triangles,
-            Some(normals.clone()),
-            texcoord_sets,
-            Some(snow_material),
-        ))
-    } else {
-        None
-    };
-    
-    // Create nodes for each terrain type
-    let mut terrain_nodes = Vec::new();
-    
-    if let Some(mesh) = water_mesh {
-        let node = builder.add_node(
-            Some("WaterNode".to_string()),
-            Some(mesh),
-            None,
-            None,
-            None,
-        );
-        terrain_nodes.push(node);
-    }
-    
-    if let Some(mesh) = sand_mesh {
-        let node = builder.add_node(
-            Some("SandNode".to_string()),
-            Some(mesh),
-            None,
-            None,
-            None,
-        );
-        terrain_nodes.push(node);
-    }
-    
-    if let Some(mesh) = grass_mesh {
-        let node = builder.add_node(
-            Some("GrassNode".to_string()),
-            Some(mesh),
-            None,
-            None,
-            None,
-        );
-        terrain_nodes.push(node);
-    }
-    
-    if let Some(mesh) = mountain_mesh {
-        let node = builder.add_node(
-            Some("MountainNode".to_string()),
-            Some(mesh),
-            None,
-            None,
-            None,
-        );
-        terrain_nodes.push(node);
-    }
-    
-    if let Some(mesh) = snow_mesh {
-        let node = builder.add_node(
-            Some("SnowNode".to_string()),
-            Some(mesh),
-            None,
-            None,
-            None,
-        );
-        terrain_nodes.push(node);
-    }
+    // Create a single terrain mesh with one primitive per terrain type, all sharing the vertices
+    let regions: Vec<_> = [
+        (water_triangles, water_material),
+        (sand_triangles, sand_material),
+        (grass_triangles, grass_material),
+        (mountain_triangles, mountain_material),
+        (snow_triangles, snow_material),
+    ]
+    .into_iter()
+    .filter(|(triangles, _)| !triangles.is_empty())
+    .map(|(triangles, material)| (triangles, Some(material)))
+    .collect();
+    
+    let mut terrain_mesh = builder.begin_mesh(Some("Terrain".to_string()));
+    terrain_mesh.add_primitives_with_shared_vertices(
+        &positions,
+        Some(normals),
+        Some(vec![texcoords]),
+        &regions,
+    );
+    let terrain_mesh = terrain_mesh.build()?;
     
-    // Create a parent node for all terrain parts
-    let terrain_parent = builder.create_node_hierarchy(
+    // A single node renders every terrain type
+    let terrain_node = builder.add_node(
         Some("Terrain".to_string()),
+        Some(terrain_mesh),
         None,
         None,
         None,
-        terrain_nodes,
     );
     
-    // Add the terrain to the scene
-    builder.add_scene(
-        Some("Main Scene".to_string()),
-        Some(vec![terrain_parent]),
-    );
-    
-    Ok(())
+    Ok(terrain_node)
 }
 
 /// Generate a height map using a combination of noise functions
@@ -432,56 +315,6 @@ fn simplex_noise_2d(x: f32, y: f32) -> f32 {
 
 /// Calculate smooth step function
 fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
-    let t = ((x - edge0) / (edge1 - edge0)).max(0.0).min(1.0);
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
     t * t * (3.0 - 2.0 * t)
 }
-
-/// Calculate vertex normals based on triangle faces using nalgebra types
-fn calculate_normals(positions: &[Point3<f32>], indices: &[u16]) -> Vec<Vector3<f32>> {
-    let vertex_count = positions.len();
-    let mut normals = vec![mesh_tools::compat::vector3::new(0.0, 0.0, 0.0); vertex_count];
-    
-    // For each triangle
-    for i in (0..indices.len()).step_by(3) {
-        let i1 = indices[i] as usize;
-        let i2 = indices[i + 1] as usize;
-        let i3 = indices[i + 2] as usize;
-        
-        // Get vertices of this triangle
-        let v1 = positions[i1];
-        let v2 = positions[i2];
-        let v3 = positions[i3];
-        
-        // Calculate vectors along two edges of the triangle
-        let edge1 = mesh_tools::compat::vector3::new(v2.x - v1.x, v2.y - v1.y, v2.z - v1.z);
-        let edge2 = mesh_tools::compat::vector3::new(v3.x - v1.x, v3.y - v1.y, v3.z - v1.z);
-        
-        // Calculate cross product to get face normal
-        let normal = cross(edge1, edge2);
-        
-        // Accumulate the normal on each vertex of the triangle
-        normals[i1].x += normal.x;
-        normals[i1].y += normal.y;
-        normals[i1].z += normal.z;
-        
-        normals[i2].x += normal.x;
-        normals[i2].y += normal.y;
-        normals[i2].z += normal.z;
-        
-        normals[i3].x += normal.x;
-        normals[i3].y += normal.y;
-        normals[i3].z += normal.z;
-    }
-    
-    // Normalize all the normals
-    for normal in &mut normals {
-        let length = (normal.x * normal.x + normal.y * normal.y + normal.z * normal.z).sqrt();
-        if length > 0.0 {
-            normal.x /= length;
-            normal.y /= length;
-            normal.z /= length;
-        }
-    }
-    
-    normals
-}
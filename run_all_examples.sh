@@ -26,6 +26,7 @@ EXAMPLES=(
   "roughness_demo"
   "rustacean_plate_demo"
   "specular_glossiness_demo"
+  "gltf_export_demo"
 )
 
 # Colors for output
@@ -0,0 +1,417 @@
+//! # Accessor Data Reading
+//!
+//! This module provides typed access to the vertex, index and animation data referenced by
+//! glTF accessors. An `AccessorReader` decodes the raw bytes of the binary buffer according to
+//! the accessor's component type, `normalized` flag, byte offset, the buffer view's byte stride
+//! and any sparse storage, and returns the data using the same types the builder accepts.
+//!
+//! This is useful for post-processing or validating generated meshes, and for inspecting
+//! documents loaded with `GltfBuilder::from_glb` or `GltfBuilder::from_gltf`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use mesh_tools::GltfBuilder;
+//!
+//! let mut builder = GltfBuilder::new();
+//! let box_mesh = builder.create_box(1.0);
+//!
+//! // Look up the accessors used by the box primitive
+//! let primitive = &builder.gltf.meshes.as_ref().unwrap()[box_mesh.index()].primitives[0];
+//! let position_accessor = primitive.attributes["POSITION"];
+//! let index_accessor = primitive.indices.unwrap();
+//!
+//! // Decode them back into typed data
+//! let reader = builder.accessor_reader();
+//! let positions = reader.read_positions(position_accessor).unwrap();
+//! let triangles = reader.read_triangles(index_accessor).unwrap();
+//!
+//! assert_eq!(positions.len(), 8);
+//! assert_eq!(triangles.len(), 12);
+//! ```
+
+use std::ops::Range;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::builder_primitives::Triangle;
+use crate::compat::{Point3, Vector2, Vector3};
+use crate::constants::{component_type, primitive_mode};
+use crate::error::{GltfError, Result};
+use crate::models::{Accessor, Gltf, Primitive};
+
+/// Reader for decoding accessor data from a glTF document and its binary buffer
+pub struct AccessorReader<'a> {
+    gltf: &'a Gltf,
+    buffer_data: &'a [u8],
+}
+
+impl<'a> AccessorReader<'a> {
+    /// Create a reader over a document whose buffer views all reference `buffer_data`
+    pub fn new(gltf: &'a Gltf, buffer_data: &'a [u8]) -> Self {
+        Self { gltf, buffer_data }
+    }
+    
+    /// Get the accessor with the given index
+    pub fn accessor(&self, accessor_index: usize) -> Result<&'a Accessor> {
+        self.gltf.accessors.as_ref()
+            .and_then(|accessors| accessors.get(accessor_index))
+            .ok_or(GltfError::InvalidIndex)
+    }
+    
+    /// Read an accessor as floats, returned as a flat list with the number of components per element
+    ///
+    /// Normalized integer components are mapped to the 0.0-1.0 (unsigned) or -1.0-1.0 (signed)
+    /// range, other integer components are converted as-is.
+    pub fn read_f32(&self, accessor_index: usize) -> Result<(Vec<f32>, usize)> {
+        let accessor = self.accessor(accessor_index)?;
+        let normalized = accessor.normalized.unwrap_or(false);
+        let (values, components) = self.read_raw(accessor)?;
+        
+        let values = values.into_iter()
+            .map(|value| if normalized {
+                normalize_component(value, accessor.component_type)
+            } else {
+                value as f32
+            })
+            .collect();
+        
+        Ok((values, components))
+    }
+    
+    /// Read an integer accessor (indices, joints) as a flat list of `u32` values
+    pub fn read_u32(&self, accessor_index: usize) -> Result<Vec<u32>> {
+        let accessor = self.accessor(accessor_index)?;
+        if accessor.component_type == component_type::FLOAT {
+            return Err(GltfError::InvalidData(format!(
+                "Accessor {} has float components, expected integers", accessor_index
+            )));
+        }
+        
+        let (values, _) = self.read_raw(accessor)?;
+        
+        Ok(values.into_iter().map(|value| value as u32).collect())
+    }
+    
+    /// Read a SCALAR accessor as floats (e.g. animation timestamps)
+    pub fn read_scalars(&self, accessor_index: usize) -> Result<Vec<f32>> {
+        let (values, components) = self.read_f32(accessor_index)?;
+        expect_components(accessor_index, components, 1)?;
+        
+        Ok(values)
+    }
+    
+    /// Read a VEC2 accessor (e.g. texture coordinates)
+    pub fn read_vec2(&self, accessor_index: usize) -> Result<Vec<Vector2<f32>>> {
+        let (values, components) = self.read_f32(accessor_index)?;
+        expect_components(accessor_index, components, 2)?;
+        
+        Ok(values.chunks_exact(2)
+            .map(|v| crate::compat::vector2::new(v[0], v[1]))
+            .collect())
+    }
+    
+    /// Read a VEC3 accessor (e.g. normals)
+    pub fn read_vec3(&self, accessor_index: usize) -> Result<Vec<Vector3<f32>>> {
+        let (values, components) = self.read_f32(accessor_index)?;
+        expect_components(accessor_index, components, 3)?;
+        
+        Ok(values.chunks_exact(3)
+            .map(|v| crate::compat::vector3::new(v[0], v[1], v[2]))
+            .collect())
+    }
+    
+    /// Read a VEC4 accessor (e.g. tangents, colors or rotations)
+    pub fn read_vec4(&self, accessor_index: usize) -> Result<Vec<[f32; 4]>> {
+        let (values, components) = self.read_f32(accessor_index)?;
+        expect_components(accessor_index, components, 4)?;
+        
+        Ok(values.chunks_exact(4)
+            .map(|v| [v[0], v[1], v[2], v[3]])
+            .collect())
+    }
+    
+    /// Read a VEC3 POSITION accessor as points
+    pub fn read_positions(&self, accessor_index: usize) -> Result<Vec<Point3<f32>>> {
+        Ok(self.read_vec3(accessor_index)?
+            .into_iter()
+            .map(|v| crate::compat::point3::new(v.x, v.y, v.z))
+            .collect())
+    }
+    
+    /// Read a SCALAR index accessor as triangles
+    pub fn read_triangles(&self, accessor_index: usize) -> Result<Vec<Triangle>> {
+        let indices = self.read_u32(accessor_index)?;
+        if indices.len() % 3 != 0 {
+            return Err(GltfError::InvalidData(format!(
+                "Accessor {} has {} indices, which is not a multiple of 3", accessor_index, indices.len()
+            )));
+        }
+        
+        Ok(indices.chunks_exact(3)
+            .map(|t| Triangle::new(t[0], t[1], t[2]))
+            .collect())
+    }
+    
+    /// Read the triangles drawn by a mesh primitive
+    ///
+    /// Non-indexed primitives use sequential vertex indices, and triangle strips and fans
+    /// are converted to triangle lists. Points and lines produce no triangles.
+    pub fn read_primitive_triangles(&self, primitive: &Primitive) -> Result<Vec<Triangle>> {
+        let indices = match primitive.indices {
+            Some(accessor_index) => self.read_u32(accessor_index)?,
+            None => {
+                let position = primitive.attributes.get("POSITION")
+                    .ok_or_else(|| GltfError::InvalidData("Primitive has no POSITION attribute".to_string()))?;
+                (0..self.accessor(*position)?.count as u32).collect()
+            }
+        };
+        
+        let triangles = match primitive.mode.unwrap_or(primitive_mode::TRIANGLES) {
+            primitive_mode::TRIANGLES => indices.chunks_exact(3)
+                .map(|t| Triangle::new(t[0], t[1], t[2]))
+                .collect(),
+            primitive_mode::TRIANGLE_STRIP => (2..indices.len())
+                .map(|i| if i % 2 == 0 {
+                    Triangle::new(indices[i - 2], indices[i - 1], indices[i])
+                } else {
+                    Triangle::new(indices[i - 1], indices[i - 2], indices[i])
+                })
+                .collect(),
+            primitive_mode::TRIANGLE_FAN => (2..indices.len())
+                .map(|i| Triangle::new(indices[i - 1], indices[i], indices[0]))
+                .collect(),
+            _ => Vec::new(),
+        };
+        
+        Ok(triangles)
+    }
+    
+    /// Decode the raw component values of an accessor, including sparse substitutions
+    fn read_raw(&self, accessor: &Accessor) -> Result<(Vec<f64>, usize)> {
+        let components = type_components(&accessor.type_)?;
+        let value_size = component_size(accessor.component_type)?;
+        let element_size = components * value_size;
+        
+        // Check the size against the data before allocating, as the count comes from the file
+        let too_large = || GltfError::InvalidData(format!(
+            "Accessor count {} is too large", accessor.count
+        ));
+        let value_count = accessor.count.checked_mul(components).ok_or_else(too_large)?;
+        let dense = match accessor.buffer_view {
+            Some(view_index) => {
+                let (view_data, stride) = self.buffer_view(view_index)?;
+                let stride = stride.unwrap_or(element_size);
+                let start = accessor.byte_offset.unwrap_or(0);
+                
+                let end = match accessor.count.checked_sub(1) {
+                    Some(last) => byte_range(start, last, stride, element_size).map(|range| range.end),
+                    None => Some(start),
+                };
+                if end.is_none_or(|end| end > view_data.len()) {
+                    return Err(GltfError::InvalidData(format!(
+                        "Accessor elements exceed buffer view {}", view_index
+                    )));
+                }
+                Some((view_data, stride, start))
+            }
+            None => None,
+        };
+        
+        let mut values = Vec::new();
+        values.try_reserve_exact(value_count).map_err(|_| too_large())?;
+        values.resize(value_count, 0.0);
+        
+        // Dense data; accessors without a buffer view are initialized with zeros
+        if let Some((view_data, stride, start)) = dense {
+            for element in 0..accessor.count {
+                let offset = start + element * stride;
+                let bytes = &view_data[offset..offset + element_size];
+                
+                for component in 0..components {
+                    values[element * components + component] = read_component(
+                        &bytes[component * value_size..],
+                        accessor.component_type,
+                    );
+                }
+            }
+        }
+        
+        // Sparse substitutions are tightly packed
+        if let Some(sparse) = &accessor.sparse {
+            let index_size = component_size(sparse.indices.component_type)?;
+            let (index_data, _) = self.buffer_view(sparse.indices.buffer_view)?;
+            let (value_data, _) = self.buffer_view(sparse.values.buffer_view)?;
+            
+            for i in 0..sparse.count {
+                let index_bytes = byte_range(sparse.indices.byte_offset, i, index_size, index_size)
+                    .and_then(|range| index_data.get(range))
+                    .ok_or_else(|| GltfError::InvalidData("Sparse index exceeds buffer view".to_string()))?;
+                let element = read_component(index_bytes, sparse.indices.component_type) as usize;
+                
+                if element >= accessor.count {
+                    return Err(GltfError::InvalidData(format!(
+                        "Sparse index {} exceeds accessor count {}", element, accessor.count
+                    )));
+                }
+                
+                let value_bytes = byte_range(sparse.values.byte_offset, i, element_size, element_size)
+                    .and_then(|range| value_data.get(range))
+                    .ok_or_else(|| GltfError::InvalidData("Sparse value exceeds buffer view".to_string()))?;
+                
+                for component in 0..components {
+                    values[element * components + component] = read_component(
+                        &value_bytes[component * value_size..],
+                        accessor.component_type,
+                    );
+                }
+            }
+        }
+        
+        Ok((values, components))
+    }
+    
+    /// Get the bytes and byte stride of a buffer view
+    fn buffer_view(&self, view_index: usize) -> Result<(&'a [u8], Option<usize>)> {
+        let view = self.gltf.buffer_views.as_ref()
+            .and_then(|views| views.get(view_index))
+            .ok_or(GltfError::InvalidIndex)?;
+        
+        if view.buffer != 0 {
+            return Err(GltfError::InvalidData(format!(
+                "Buffer view {} references buffer {}, only buffer 0 is available", view_index, view.buffer
+            )));
+        }
+        
+        let end = view.byte_offset.checked_add(view.byte_length)
+            .ok_or_else(|| GltfError::InvalidData(format!("Buffer view {} offset and length overflow", view_index)))?;
+        let data = self.buffer_data.get(view.byte_offset..end)
+            .ok_or_else(|| GltfError::InvalidData(format!(
+                "Buffer view {} exceeds buffer length {}", view_index, self.buffer_data.len()
+            )))?;
+        
+        Ok((data, view.byte_stride))
+    }
+}
+
+/// Number of components for an accessor type
+pub(crate) fn type_components(type_: &str) -> Result<usize> {
+    match type_ {
+        "SCALAR" => Ok(1),
+        "VEC2" => Ok(2),
+        "VEC3" => Ok(3),
+        "VEC4" | "MAT2" => Ok(4),
+        "MAT3" => Ok(9),
+        "MAT4" => Ok(16),
+        _ => Err(GltfError::InvalidData(format!("Unknown accessor type {}", type_))),
+    }
+}
+
+/// Size in bytes of a single component
+pub(crate) fn component_size(component: usize) -> Result<usize> {
+    match component {
+        component_type::BYTE | component_type::UNSIGNED_BYTE => Ok(1),
+        component_type::SHORT | component_type::UNSIGNED_SHORT => Ok(2),
+        component_type::UNSIGNED_INT | component_type::FLOAT => Ok(4),
+        _ => Err(GltfError::InvalidData(format!("Unknown component type {}", component))),
+    }
+}
+
+/// Byte range of element `index` of `size` bytes, placed every `stride` bytes from `base`
+///
+/// Returns `None` if the range does not fit in `usize`.
+fn byte_range(base: usize, index: usize, stride: usize, size: usize) -> Option<Range<usize>> {
+    let start = index.checked_mul(stride)?.checked_add(base)?;
+    Some(start..start.checked_add(size)?)
+}
+
+/// Read a single little-endian component as a raw value
+fn read_component(bytes: &[u8], component: usize) -> f64 {
+    match component {
+        component_type::BYTE => bytes[0] as i8 as f64,
+        component_type::UNSIGNED_BYTE => bytes[0] as f64,
+        component_type::SHORT => LittleEndian::read_i16(bytes) as f64,
+        component_type::UNSIGNED_SHORT => LittleEndian::read_u16(bytes) as f64,
+        component_type::UNSIGNED_INT => LittleEndian::read_u32(bytes) as f64,
+        _ => LittleEndian::read_f32(bytes) as f64,
+    }
+}
+
+/// Map a normalized integer component to its floating point value
+fn normalize_component(value: f64, component: usize) -> f32 {
+    let value = match component {
+        component_type::BYTE => (value / 127.0).max(-1.0),
+        component_type::UNSIGNED_BYTE => value / 255.0,
+        component_type::SHORT => (value / 32767.0).max(-1.0),
+        component_type::UNSIGNED_SHORT => value / 65535.0,
+        _ => value,
+    };
+    
+    value as f32
+}
+
+/// Check that an accessor has the expected number of components per element
+fn expect_components(accessor_index: usize, components: usize, expected: usize) -> Result<()> {
+    if components != expected {
+        return Err(GltfError::InvalidData(format!(
+            "Accessor {} has {} components per element, expected {}", accessor_index, components, expected
+        )));
+    }
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{GltfBuilder, GltfError};
+    
+    /// Builder with a box mesh, and the index of its POSITION accessor
+    fn box_positions() -> (GltfBuilder, usize) {
+        let mut builder = GltfBuilder::new();
+        let mesh = builder.create_box(1.0);
+        let accessor = builder.gltf.meshes.as_ref().unwrap()[mesh.index()].primitives[0].attributes["POSITION"];
+        (builder, accessor)
+    }
+    
+    #[test]
+    fn count_beyond_buffer_view_is_an_error() {
+        let (mut builder, accessor) = box_positions();
+        builder.gltf.accessors.as_mut().unwrap()[accessor].count = usize::MAX / 2;
+        
+        assert!(builder.accessor_reader().read_f32(accessor).is_err());
+    }
+    
+    #[test]
+    fn offset_overflow_is_an_error() {
+        let (mut builder, accessor) = box_positions();
+        builder.gltf.accessors.as_mut().unwrap()[accessor].byte_offset = Some(usize::MAX - 4);
+        
+        assert!(builder.accessor_reader().read_f32(accessor).is_err());
+    }
+    
+    #[test]
+    fn buffer_view_overflow_is_an_error() {
+        let (mut builder, accessor) = box_positions();
+        let view = builder.gltf.accessors.as_ref().unwrap()[accessor].buffer_view.unwrap();
+        builder.gltf.buffer_views.as_mut().unwrap()[view].byte_offset = usize::MAX - 2;
+        
+        assert!(matches!(builder.accessor_reader().read_f32(accessor), Err(GltfError::InvalidData(_))));
+    }
+    
+    #[test]
+    fn huge_count_without_buffer_view_is_an_error() {
+        let (mut builder, accessor) = box_positions();
+        let data = &mut builder.gltf.accessors.as_mut().unwrap()[accessor];
+        data.buffer_view = None;
+        data.count = usize::MAX / 3;
+        
+        assert!(builder.accessor_reader().read_f32(accessor).is_err());
+    }
+    
+    #[test]
+    fn reads_valid_accessor() {
+        let (builder, accessor) = box_positions();
+        
+        assert_eq!(builder.accessor_reader().read_positions(accessor).unwrap().len(), 8);
+    }
+}
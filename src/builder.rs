@@ -7,13 +7,25 @@
 //! The builder follows a fluent API pattern where methods can be chained together to
 //! build up the document structure incrementally.
 
-use std::fs::File;
-use std::io::{self, Write, Seek};
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
 use byteorder::{LittleEndian, WriteBytesExt};
 use serde_json;
 
+use crate::accessor::AccessorReader;
+use crate::builder_primitives::IndexWidth;
+use crate::constants::{buffer_view_target, component_type};
 use crate::error::{GltfError, Result};
+use crate::geometry::NormalMode;
+use crate::handle::{MeshId, NodeId, SceneId};
 use crate::models::*;
+use crate::validation::{self, ValidationReport};
+
+/// Index and encoded bytes of an image split out of the binary buffer
+type ExtractedImage = (usize, Vec<u8>);
 
 /// The main builder for creating and exporting glTF models
 ///
@@ -26,6 +38,43 @@ use crate::models::*;
 pub struct GltfBuilder {
     pub gltf: Gltf,
     pub buffer_data: Vec<u8>,
+    pub(crate) options: BuilderOptions,
+}
+
+/// Settings that change how the builder writes geometry
+#[derive(Debug, Clone)]
+pub(crate) struct BuilderOptions {
+    /// Add a `TANGENT` attribute to meshes with normals and texture coordinates
+    pub(crate) generate_tangents: bool,
+    /// Compute normals for meshes created without them
+    pub(crate) generate_normals: Option<NormalMode>,
+    /// Smallest index type for new primitives; the narrowest that fits when unset
+    pub(crate) index_width: Option<IndexWidth>,
+    /// Interleave the vertex attributes of each primitive in exported files
+    pub(crate) interleave_vertices: bool,
+    /// Refuse to export documents that fail validation
+    pub(crate) validate_on_export: bool,
+    /// Merge identical buffer views, accessors, samplers, images, textures and materials in exported files
+    pub(crate) deduplicate: bool,
+}
+
+impl Default for BuilderOptions {
+    fn default() -> Self {
+        Self {
+            generate_tangents: false,
+            generate_normals: None,
+            index_width: None,
+            interleave_vertices: false,
+            validate_on_export: false,
+            deduplicate: true,
+        }
+    }
+}
+
+impl Default for GltfBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl GltfBuilder {
@@ -64,14 +113,199 @@ impl GltfBuilder {
         GltfBuilder {
             gltf,
             buffer_data: Vec::new(),
+            options: BuilderOptions::default(),
         }
     }
 
+    /// Enable or disable tangent generation for new meshes
+    ///
+    /// When enabled, `create_custom_mesh`, the primitive shape methods and the importers add a
+    /// `TANGENT` attribute, computed with `geometry::generate_tangents` from the first set of
+    /// texture coordinates, to every triangle mesh with normals and texture coordinates.
+    /// Tangents make normal maps render consistently across viewers. Disabled by default.
+    ///
+    /// Geometry whose normals or texture coordinates do not have one entry per position, or
+    /// whose triangles refer to missing vertices, has no well-defined tangents and is created
+    /// without `TANGENT`. `generate_mesh_tangents` reports the reason as an error instead.
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::GltfBuilder;
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// builder.set_generate_tangents(true);
+    ///
+    /// let sphere = builder.create_sphere(1.0, 32, 16, None);
+    /// let primitive = &builder.gltf.meshes.as_ref().unwrap()[sphere.index()].primitives[0];
+    /// assert!(primitive.attributes.contains_key("TANGENT"));
+    /// ```
+    pub fn set_generate_tangents(&mut self, enabled: bool) {
+        self.options.generate_tangents = enabled;
+    }
+    
+    /// Choose how normals are generated for new meshes created without them
+    ///
+    /// When set, `create_custom_mesh`, `create_custom_mesh_with_colors`, `MeshBuilder` and the
+    /// importers compute normals with `geometry::generate_normals` for triangle meshes given no
+    /// normals. Flat and auto-smooth normals split vertices, so the created mesh can have more
+    /// vertices than were passed in; texture coordinates and colors are carried over. `None`,
+    /// the default, leaves such meshes without normals.
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::GltfBuilder;
+    /// use mesh_tools::geometry::NormalMode;
+    /// use mesh_tools::primitives::generate_torus;
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// builder.set_generate_normals(Some(NormalMode::Flat));
+    ///
+    /// // A faceted, low-poly torus
+    /// let (positions, indices, _, uvs) = generate_torus(1.0, 0.3, 12, 6);
+    /// let torus = builder.create_custom_mesh(None, &positions, &indices, None, Some(vec![uvs]), None);
+    ///
+    /// let primitive = &builder.gltf.meshes.as_ref().unwrap()[torus.index()].primitives[0];
+    /// assert!(primitive.attributes.contains_key("NORMAL"));
+    /// ```
+    pub fn set_generate_normals(&mut self, mode: Option<NormalMode>) {
+        self.options.generate_normals = mode;
+    }
+    
+    /// Choose the component type of the indices of new meshes
+    ///
+    /// By default, `None`, each primitive gets the narrowest index type that can address its
+    /// vertices: `UNSIGNED_BYTE` for up to 255 vertices (largest index below 255),
+    /// `UNSIGNED_SHORT` for up to 65,535 vertices (largest index below 65,535) and
+    /// `UNSIGNED_INT` for more. Some runtimes do not accept 8-bit indices, so a width can be
+    /// set instead; primitives with too many vertices for it still get a wider type.
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::{component_type, GltfBuilder, IndexWidth};
+    ///
+    /// let mut builder = GltfBuilder::new();
+    ///
+    /// // An 8-vertex box gets byte indices
+    /// let small = builder.create_box(1.0);
+    ///
+    /// builder.set_index_width(Some(IndexWidth::U16));
+    /// let wide = builder.create_box(1.0);
+    ///
+    /// let index_type = |mesh: mesh_tools::MeshId| {
+    ///     let indices = builder.gltf.meshes.as_ref().unwrap()[mesh.index()].primitives[0].indices.unwrap();
+    ///     builder.gltf.accessors.as_ref().unwrap()[indices].component_type
+    /// };
+    /// assert_eq!(index_type(small), component_type::UNSIGNED_BYTE);
+    /// assert_eq!(index_type(wide), component_type::UNSIGNED_SHORT);
+    /// ```
+    pub fn set_index_width(&mut self, width: Option<IndexWidth>) {
+        self.options.index_width = width;
+    }
+    
+    /// Enable or disable interleaved vertex buffers in exported glTF and GLB files
+    ///
+    /// The builder stores every vertex attribute in its own buffer view. When enabled, the
+    /// exporters combine the attributes of each primitive into a single buffer view with a
+    /// byte stride, which some runtimes can upload to the GPU as it is. The document held by
+    /// the builder is not changed. Disabled by default.
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::GltfBuilder;
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// builder.set_interleave_vertices(true);
+    ///
+    /// let sphere = builder.create_sphere(1.0, 16, 8, None);
+    /// let node = builder.add_node(None, Some(sphere), None, None, None);
+    /// builder.add_scene(None, Some(vec![node]));
+    ///
+    /// // Positions, normals and texture coordinates share one strided view
+    /// let exported = GltfBuilder::from_glb_bytes(&builder.to_glb_bytes().unwrap()).unwrap();
+    /// let accessors = exported.gltf.accessors.as_ref().unwrap();
+    /// let primitive = &exported.gltf.meshes.as_ref().unwrap()[0].primitives[0];
+    /// let position = &accessors[primitive.attributes["POSITION"]];
+    /// let normal = &accessors[primitive.attributes["NORMAL"]];
+    ///
+    /// assert_eq!(position.buffer_view, normal.buffer_view);
+    /// assert_eq!(normal.byte_offset, Some(12));
+    /// let view = &exported.gltf.buffer_views.as_ref().unwrap()[position.buffer_view.unwrap()];
+    /// assert_eq!(view.byte_stride, Some(32));
+    /// ```
+    pub fn set_interleave_vertices(&mut self, enabled: bool) {
+        self.options.interleave_vertices = enabled;
+    }
+    
+    /// Enable or disable validation before export
+    ///
+    /// When enabled, `export_glb`, `export_gltf`, `export_gltf_embedded` and the in-memory
+    /// variants run `validate` first and return `GltfError::Validation` with the report if the
+    /// document has errors. Warnings do not stop the export. Disabled by default.
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::{GltfBuilder, GltfError};
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// builder.set_validate_on_export(true);
+    ///
+    /// let cube = builder.create_box(1.0);
+    /// let node = builder.add_node(None, Some(cube), None, None, None);
+    /// builder.add_scene(None, Some(vec![node]));
+    /// assert!(builder.to_glb_bytes().is_ok());
+    ///
+    /// // A node that is its own child makes the hierarchy a cycle
+    /// builder.gltf.nodes.as_mut().unwrap()[node.index()].children = Some(vec![node.index()]);
+    /// assert!(matches!(builder.to_glb_bytes(), Err(GltfError::Validation(_))));
+    /// ```
+    pub fn set_validate_on_export(&mut self, enabled: bool) {
+        self.options.validate_on_export = enabled;
+    }
+    
+    /// Enable or disable merging of duplicate resources in exported glTF and GLB files
+    ///
+    /// Scenes often create the same geometry, textures and materials many times, for example by
+    /// calling `create_box_with_material` for every instance of a prop. When enabled, the
+    /// exporters store buffer data with the same bytes only once, and merge accessors, samplers,
+    /// images, textures and materials with the same content, redirecting every reference to the
+    /// copy that is kept. The document held by the builder is not changed, so handles stay valid
+    /// and objects can still be edited individually. Enabled by default.
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::GltfBuilder;
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// let material = builder.create_basic_material(Some("Red".to_string()), [1.0, 0.0, 0.0, 1.0]);
+    /// let first = builder.create_box_with_material(1.0, Some(material));
+    /// let second = builder.create_box_with_material(1.0, Some(material));
+    /// let nodes = vec![
+    ///     builder.add_node(None, Some(first), None, None, None),
+    ///     builder.add_node(None, Some(second), Some([2.0, 0.0, 0.0]), None, None),
+    /// ];
+    /// builder.add_scene(None, Some(nodes));
+    ///
+    /// // Both boxes share one set of accessors
+    /// let exported = GltfBuilder::from_glb_bytes(&builder.to_glb_bytes().unwrap()).unwrap();
+    /// let meshes = exported.gltf.meshes.as_ref().unwrap();
+    /// assert_eq!(meshes[0].primitives[0].attributes, meshes[1].primitives[0].attributes);
+    /// assert_eq!(meshes[0].primitives[0].indices, meshes[1].primitives[0].indices);
+    ///
+    /// // Without deduplication every box keeps its own copy
+    /// builder.set_deduplicate(false);
+    /// let exported = GltfBuilder::from_glb_bytes(&builder.to_glb_bytes().unwrap()).unwrap();
+    /// let meshes = exported.gltf.meshes.as_ref().unwrap();
+    /// assert_ne!(meshes[0].primitives[0].indices, meshes[1].primitives[0].indices);
+    /// ```
+    pub fn set_deduplicate(&mut self, enabled: bool) {
+        self.options.deduplicate = enabled;
+    }
+    
     /// Add a scene to the glTF document
-    pub fn add_scene(&mut self, name: Option<String>, nodes: Option<Vec<usize>>) -> usize {
+    pub fn add_scene(&mut self, name: Option<String>, nodes: Option<Vec<NodeId>>) -> SceneId {
         let scene = Scene {
             name,
-            nodes,
+            nodes: nodes.map(|nodes| nodes.into_iter().map(NodeId::index).collect()),
         };
         
         if let Some(scenes) = &mut self.gltf.scenes {
@@ -83,64 +317,69 @@ impl GltfBuilder {
                 self.gltf.scene = Some(0);
             }
             
-            index
+            SceneId::from_index(index)
         } else {
             self.gltf.scenes = Some(vec![scene]);
             self.gltf.scene = Some(0);
-            0
+            SceneId::from_index(0)
         }
     }
 
     /// Add a node to the glTF document
-    pub fn add_node(&mut self, name: Option<String>, mesh: Option<usize>, 
+    pub fn add_node(&mut self, name: Option<String>, mesh: Option<MeshId>, 
                    translation: Option<[f32; 3]>, rotation: Option<[f32; 4]>,
-                   scale: Option<[f32; 3]>) -> usize {
+                   scale: Option<[f32; 3]>) -> NodeId {
         let node = Node {
             name,
-            mesh,
+            mesh: mesh.map(MeshId::index),
+            camera: None,
+            skin: None,
             translation,
             rotation,
             scale,
             matrix: None,
             children: None,
+            extensions: None,
         };
         
-        if let Some(nodes) = &mut self.gltf.nodes {
-            let index = nodes.len();
-            nodes.push(node);
-            index
-        } else {
-            self.gltf.nodes = Some(vec![node]);
-            0
-        }
+        self.push_node(node)
     }
 
     /// Add a node with a list of children to the glTF document
-    pub fn add_node_with_children(&mut self, name: Option<String>, mesh: Option<usize>, 
+    pub fn add_node_with_children(&mut self, name: Option<String>, mesh: Option<MeshId>, 
                           translation: Option<[f32; 3]>, rotation: Option<[f32; 4]>,
-                          scale: Option<[f32; 3]>, children: Vec<usize>) -> usize {
+                          scale: Option<[f32; 3]>, children: Vec<NodeId>) -> NodeId {
         let node = Node {
             name,
-            mesh,
+            mesh: mesh.map(MeshId::index),
+            camera: None,
+            skin: None,
             translation,
             rotation,
             scale,
             matrix: None,
-            children: Some(children),
+            children: Some(children.into_iter().map(NodeId::index).collect()),
+            extensions: None,
         };
         
+        self.push_node(node)
+    }
+    
+    /// Add a node to the nodes array
+    fn push_node(&mut self, node: Node) -> NodeId {
         if let Some(nodes) = &mut self.gltf.nodes {
             let index = nodes.len();
             nodes.push(node);
-            index
+            NodeId::from_index(index)
         } else {
             self.gltf.nodes = Some(vec![node]);
-            0
+            NodeId::from_index(0)
         }
     }
 
     /// Add a child to an existing node
-    pub fn add_child_to_node(&mut self, parent_index: usize, child_index: usize) -> Result<()> {
+    pub fn add_child_to_node(&mut self, parent: NodeId, child: NodeId) -> Result<()> {
+        let (parent_index, child_index) = (parent.index(), child.index());
         if let Some(nodes) = &mut self.gltf.nodes {
             if parent_index < nodes.len() && child_index < nodes.len() {
                 let parent = &mut nodes[parent_index];
@@ -167,7 +406,7 @@ impl GltfBuilder {
                                parent_translation: Option<[f32; 3]>,
                                parent_rotation: Option<[f32; 4]>,
                                parent_scale: Option<[f32; 3]>,
-                               child_indices: Vec<usize>) -> usize {
+                               child_indices: Vec<NodeId>) -> NodeId {
         // Create the parent node with the children
         self.add_node_with_children(
             parent_name,
@@ -180,35 +419,39 @@ impl GltfBuilder {
     }
 
     /// Add a mesh to the glTF document
-    pub fn add_mesh(&mut self, name: Option<String>, primitives: Vec<Primitive>) -> usize {
+    pub fn add_mesh(&mut self, name: Option<String>, primitives: Vec<Primitive>) -> MeshId {
         let mesh = Mesh {
             name,
             primitives,
+            weights: None,
+            extras: None,
         };
         
         if let Some(meshes) = &mut self.gltf.meshes {
             let index = meshes.len();
             meshes.push(mesh);
-            index
+            MeshId::from_index(index)
         } else {
             self.gltf.meshes = Some(vec![mesh]);
-            0
+            MeshId::from_index(0)
         }
     }
     
     /// Add an accessor to the glTF document
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn add_accessor(&mut self, buffer_view: usize, component_type: usize, 
                        count: usize, type_: String, byte_offset: Option<usize>,
                        min: Option<Vec<f32>>, max: Option<Vec<f32>>) -> usize {
         let accessor = Accessor {
-            buffer_view: buffer_view,
-            component_type: component_type,
+            buffer_view: Some(buffer_view),
+            component_type,
             count,
             type_,
-            byte_offset: byte_offset,
+            byte_offset,
             min,
             max,
             normalized: None,
+            sparse: None,
         };
         
         if let Some(accessors) = &mut self.gltf.accessors {
@@ -226,8 +469,8 @@ impl GltfBuilder {
                           target: Option<usize>) -> usize {
         let buffer_view = BufferView {
             buffer: 0, // We only use a single buffer
-            byte_offset: byte_offset,
-            byte_length: byte_length,
+            byte_offset,
+            byte_length,
             byte_stride: None,
             target,
         };
@@ -264,52 +507,434 @@ impl GltfBuilder {
         (byte_offset, byte_length)
     }
 
+    /// Upload vertex attribute data and create an accessor for it
+    ///
+    /// `bytes` holds `count` elements of the given accessor and component type, `stride` bytes
+    /// apart if the elements are padded. Integer components can be `normalized` to 0..1.
+    pub(crate) fn add_attribute_accessor(&mut self, bytes: &[u8], component_type: usize, count: usize,
+                                         type_: &str, stride: Option<usize>, normalized: bool) -> usize {
+        let (offset, length) = self.add_buffer_data(bytes);
+        let buffer_view = self.add_buffer_view(offset, length, Some(buffer_view_target::ARRAY_BUFFER));
+        if let Some(buffer_views) = &mut self.gltf.buffer_views {
+            buffer_views[buffer_view].byte_stride = stride;
+        }
+        
+        let accessor = self.add_accessor(buffer_view, component_type, count, type_.to_string(), None, None, None);
+        if normalized {
+            if let Some(accessors) = &mut self.gltf.accessors {
+                accessors[accessor].normalized = Some(true);
+            }
+        }
+        
+        accessor
+    }
+    
+    /// Upload float vertex data and create an accessor for it
+    ///
+    /// With `with_bounds`, the accessor gets the per-component minimum and maximum, which glTF
+    /// requires for `POSITION` accessors.
+    pub(crate) fn add_float_attribute(&mut self, values: &[f32], type_: &str, with_bounds: bool) -> usize {
+        let components = crate::accessor::type_components(type_).unwrap_or(1);
+        let bytes: Vec<u8> = values.iter().flat_map(|value| value.to_le_bytes()).collect();
+        let accessor = self.add_attribute_accessor(&bytes, component_type::FLOAT, values.len() / components,
+                                                   type_, None, false);
+        
+        if with_bounds && !values.is_empty() {
+            let mut min = vec![f32::MAX; components];
+            let mut max = vec![f32::MIN; components];
+            for element in values.chunks(components) {
+                for (component, &value) in element.iter().enumerate() {
+                    min[component] = min[component].min(value);
+                    max[component] = max[component].max(value);
+                }
+            }
+            
+            if let Some(accessors) = &mut self.gltf.accessors {
+                accessors[accessor].min = Some(min);
+                accessors[accessor].max = Some(max);
+            }
+        }
+        
+        accessor
+    }
+    
+    /// Number of vertices of a primitive, from its POSITION accessor
+    pub(crate) fn primitive_vertex_count(&self, mesh: MeshId, primitive_index: usize) -> Result<usize> {
+        let position_accessor = self.gltf.meshes.as_ref()
+            .and_then(|meshes| meshes.get(mesh.index()))
+            .and_then(|mesh| mesh.primitives.get(primitive_index))
+            .ok_or(GltfError::InvalidIndex)?
+            .attributes.get("POSITION").copied()
+            .ok_or_else(|| GltfError::InvalidData("Primitive has no POSITION attribute".to_string()))?;
+        
+        Ok(self.accessor_reader().accessor(position_accessor)?.count)
+    }
+    
+    /// Create a reader for decoding the accessor data stored in this builder
+    pub fn accessor_reader(&self) -> AccessorReader<'_> {
+        AccessorReader::new(&self.gltf, &self.buffer_data)
+    }
+    
+    /// Get the bytes covered by a buffer view
+    pub(crate) fn buffer_view_bytes(&self, buffer_view: usize) -> Result<&[u8]> {
+        let view = self.gltf.buffer_views.as_ref()
+            .and_then(|views| views.get(buffer_view))
+            .ok_or(GltfError::InvalidIndex)?;
+        
+        let end = view.byte_offset.checked_add(view.byte_length)
+            .ok_or_else(|| GltfError::InvalidData(format!("Buffer view {} offset and length overflow", buffer_view)))?;
+        if end > self.buffer_data.len() {
+            return Err(GltfError::InvalidData(format!(
+                "Buffer view {} exceeds buffer length {}", buffer_view, self.buffer_data.len()
+            )));
+        }
+        
+        Ok(&self.buffer_data[view.byte_offset..end])
+    }
+    
+    /// Split the images stored in buffer views out of the binary buffer
+    ///
+    /// Returns a copy of the document whose images no longer reference buffer views,
+    /// the compacted buffer without the image data, and the encoded bytes of every
+    /// extracted image together with its index in the images array.
+    pub(crate) fn extract_images(&self) -> Result<(Gltf, Vec<u8>, Vec<ExtractedImage>)> {
+        let mut gltf = self.gltf.clone();
+        let mut extracted = Vec::new();
+        let mut image_views = HashSet::new();
+        
+        if let Some(images) = &mut gltf.images {
+            for (index, image) in images.iter_mut().enumerate() {
+                if let Some(view_index) = image.buffer_view.take() {
+                    extracted.push((index, self.buffer_view_bytes(view_index)?.to_vec()));
+                    image_views.insert(view_index);
+                }
+            }
+        }
+        
+        // Rebuild the buffer without the image data and remap the remaining views
+        let mut buffer_data = Vec::new();
+        let mut buffer_views = Vec::new();
+        let mut remap = Vec::new();
+        
+        for (index, mut view) in gltf.buffer_views.take().unwrap_or_default().into_iter().enumerate() {
+            if image_views.contains(&index) {
+                remap.push(None);
+                continue;
+            }
+            
+            while buffer_data.len() % 4 != 0 {
+                buffer_data.push(0);
+            }
+            
+            let bytes = self.buffer_view_bytes(index)?;
+            view.byte_offset = buffer_data.len();
+            buffer_data.extend_from_slice(bytes);
+            
+            remap.push(Some(buffer_views.len()));
+            buffer_views.push(view);
+        }
+        
+        let remap_view = |view_index: usize| remap.get(view_index)
+            .copied()
+            .flatten()
+            .ok_or_else(|| GltfError::InvalidData(format!(
+                "Accessor references image buffer view {}", view_index
+            )));
+        
+        if let Some(accessors) = &mut gltf.accessors {
+            for accessor in accessors.iter_mut() {
+                if let Some(view_index) = accessor.buffer_view {
+                    accessor.buffer_view = Some(remap_view(view_index)?);
+                }
+                
+                if let Some(sparse) = &mut accessor.sparse {
+                    sparse.indices.buffer_view = remap_view(sparse.indices.buffer_view)?;
+                    sparse.values.buffer_view = remap_view(sparse.values.buffer_view)?;
+                }
+            }
+        }
+        
+        gltf.buffer_views = Some(buffer_views);
+        if let Some(buffers) = &mut gltf.buffers {
+            if !buffers.is_empty() {
+                buffers[0].byte_length = buffer_data.len();
+            }
+        }
+        
+        Ok((gltf, buffer_data, extracted))
+    }
+    
+    /// Check the document against the glTF 2.0 specification
+    ///
+    /// Validates the document as it will be exported, including buffer view bounds and
+    /// animation keyframe times against the binary buffer. See the `validation` module for
+    /// the checks performed.
+    ///
+    /// # Returns
+    /// A report listing every error and warning found
+    pub fn validate(&self) -> ValidationReport {
+        validation::validate(&self.export_document(), Some(&self.buffer_data))
+    }
+    
+    /// The document as written by the exporters, without the buffer if there is no binary data
+    fn export_document(&self) -> Cow<'_, Gltf> {
+        if !self.buffer_data.is_empty() {
+            return Cow::Borrowed(&self.gltf);
+        }
+        
+        let mut gltf = self.gltf.clone();
+        gltf.buffers = None;
+        Cow::Owned(gltf)
+    }
+    
+    /// Copy the builder with the transformations enabled for export applied
+    ///
+    /// Returns `None` when the document is exported as it is.
+    fn export_copy(&self) -> Result<Option<GltfBuilder>> {
+        // Deduplicate after interleaving, so that identical interleaved views are merged too
+        match (self.options.interleave_vertices, self.options.deduplicate) {
+            (true, true) => Ok(Some(self.interleaved()?.deduplicated()?)),
+            (true, false) => Ok(Some(self.interleaved()?)),
+            (false, true) => Ok(Some(self.deduplicated()?)),
+            (false, false) => Ok(None),
+        }
+    }
+    
+    /// Validate the document before export, if enabled with `set_validate_on_export`
+    fn validate_before_export(&self) -> Result<()> {
+        if !self.options.validate_on_export {
+            return Ok(());
+        }
+        
+        let report = self.validate();
+        if report.is_valid() {
+            Ok(())
+        } else {
+            Err(GltfError::Validation(report))
+        }
+    }
+    
+    /// Export the glTF as a JSON `.gltf` file with an external `.bin` buffer
+    ///
+    /// The binary buffer is written next to `path` with the same file stem and a `.bin`
+    /// extension, and is referenced from the document through `Buffer.uri`. The JSON is
+    /// pretty-printed so it can be diffed and edited by hand.
+    ///
+    /// When `external_images` is `true`, every image stored in the buffer is written as a
+    /// standalone file (`<stem>_image<N>.png` or `.jpg`) referenced through `Image.uri`,
+    /// and its bytes are left out of the `.bin` file.
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::GltfBuilder;
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// let box_mesh = builder.create_box(1.0);
+    /// let node = builder.add_node(Some("Box".to_string()), Some(box_mesh), None, None, None);
+    /// builder.add_scene(None, Some(vec![node]));
+    ///
+    /// let path = std::env::temp_dir().join("export_gltf_box.gltf");
+    /// builder.export_gltf(path.to_str().unwrap(), true).unwrap();
+    /// ```
+    pub fn export_gltf(&self, path: &str, external_images: bool) -> Result<()> {
+        if let Some(copy) = self.export_copy()? {
+            return copy.export_gltf(path, external_images);
+        }
+        
+        self.validate_before_export()?;
+        
+        let path = Path::new(path);
+        let stem = path.file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| GltfError::InvalidData(format!("Invalid output path: {}", path.display())))?;
+        let directory = path.parent().unwrap_or_else(|| Path::new(""));
+        
+        let (mut gltf, buffer_data, images) = if external_images {
+            self.extract_images()?
+        } else {
+            (self.gltf.clone(), self.buffer_data.clone(), Vec::new())
+        };
+        
+        // Write each extracted image to its own file
+        for (index, data) in images {
+            if let Some(image) = gltf.images.as_mut().and_then(|images| images.get_mut(index)) {
+                let file_name = format!("{}_image{}.{}", stem, index, image_extension(image));
+                fs::write(directory.join(&file_name), &data)?;
+                image.uri = Some(file_name);
+            }
+        }
+        
+        // Write the binary buffer next to the JSON document
+        if buffer_data.is_empty() {
+            gltf.buffers = None;
+        } else if let Some(buffers) = &mut gltf.buffers {
+            let file_name = format!("{}.bin", stem);
+            fs::write(directory.join(&file_name), &buffer_data)?;
+            
+            if !buffers.is_empty() {
+                buffers[0].byte_length = buffer_data.len();
+                buffers[0].uri = Some(file_name);
+            }
+        }
+        
+        let json = serde_json::to_string_pretty(&gltf)?;
+        fs::write(path, json)?;
+        
+        Ok(())
+    }
+    
+    /// Export the glTF as a single self-contained `.gltf` JSON file
+    ///
+    /// The binary buffer is embedded in `Buffer.uri` as a base64
+    /// `data:application/octet-stream` URI. When `embed_images` is `true`, every image
+    /// stored in the buffer is moved into its own `data:image/...` URI on `Image.uri`;
+    /// otherwise images stay inside the embedded buffer.
+    pub fn export_gltf_embedded(&self, path: &str, embed_images: bool) -> Result<()> {
+        let json = self.to_gltf_embedded_json(embed_images)?;
+        fs::write(path, json)?;
+        
+        Ok(())
+    }
+    
+    /// Serialize the glTF as self-contained JSON with base64 data URIs
+    ///
+    /// See [`GltfBuilder::export_gltf_embedded`] for how the buffer and images are embedded.
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::GltfBuilder;
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// let box_mesh = builder.create_box(1.0);
+    /// let node = builder.add_node(None, Some(box_mesh), None, None, None);
+    /// builder.add_scene(None, Some(vec![node]));
+    ///
+    /// let json = builder.to_gltf_embedded_json(true).unwrap();
+    /// assert!(json.contains("data:application/octet-stream;base64,"));
+    /// ```
+    pub fn to_gltf_embedded_json(&self, embed_images: bool) -> Result<String> {
+        if let Some(copy) = self.export_copy()? {
+            return copy.to_gltf_embedded_json(embed_images);
+        }
+        
+        self.validate_before_export()?;
+        
+        let (mut gltf, buffer_data, images) = if embed_images {
+            self.extract_images()?
+        } else {
+            (self.gltf.clone(), self.buffer_data.clone(), Vec::new())
+        };
+        
+        for (index, data) in images {
+            if let Some(image) = gltf.images.as_mut().and_then(|images| images.get_mut(index)) {
+                let mime_type = image.mime_type.clone().unwrap_or_else(|| "image/png".to_string());
+                image.uri = Some(format!("data:{};base64,{}", mime_type, base64::encode(&data)));
+            }
+        }
+        
+        if buffer_data.is_empty() {
+            gltf.buffers = None;
+        } else if let Some(buffers) = &mut gltf.buffers {
+            if !buffers.is_empty() {
+                buffers[0].byte_length = buffer_data.len();
+                buffers[0].uri = Some(format!(
+                    "data:application/octet-stream;base64,{}", base64::encode(&buffer_data)
+                ));
+            }
+        }
+        
+        Ok(serde_json::to_string(&gltf)?)
+    }
+    
     /// Export the glTF as a GLB file
     pub fn export_glb(&self, path: &str) -> Result<()> {
-        let mut file = File::create(path)?;
+        let mut file = BufWriter::new(File::create(path)?);
+        self.write_glb(&mut file)?;
+        file.flush()?;
         
-        // Write GLB header (magic, version, length)
-        file.write_all(b"glTF")?;
-        file.write_u32::<LittleEndian>(2)?; // version
+        Ok(())
+    }
+    
+    /// Export the glTF as GLB data in memory
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::GltfBuilder;
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// let box_mesh = builder.create_box(1.0);
+    /// let node = builder.add_node(None, Some(box_mesh), None, None, None);
+    /// builder.add_scene(None, Some(vec![node]));
+    ///
+    /// let bytes = builder.to_glb_bytes().unwrap();
+    /// assert_eq!(&bytes[0..4], b"glTF");
+    /// ```
+    pub fn to_glb_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.write_glb(&mut bytes)?;
+        
+        Ok(bytes)
+    }
+    
+    /// Write the glTF as GLB data to any writer
+    ///
+    /// All chunk lengths are computed before writing, so the writer does not
+    /// need to support seeking.
+    pub fn write_glb<W: Write>(&self, writer: &mut W) -> Result<()> {
+        if let Some(copy) = self.export_copy()? {
+            return copy.write_glb(writer);
+        }
         
-        // We'll write the total length later, once we know it
-        let length_pos = file.stream_position()?;
-        file.write_u32::<LittleEndian>(0)?; // placeholder total length
+        self.validate_before_export()?;
         
         // JSON chunk
-        let json = serde_json::to_string(&self.gltf)?;
+        let json = serde_json::to_string(&self.export_document())?;
         let json_len = json.len();
         let json_pad = (4 - (json_len % 4)) % 4; // Padding to 4-byte boundary
         
-        file.write_u32::<LittleEndian>((json_len + json_pad) as u32)?; // chunk length
-        file.write_u32::<LittleEndian>(0x4E4F534A)?; // chunk type "JSON"
-        file.write_all(json.as_bytes())?;
+        // BIN chunk
+        let bin_len = self.buffer_data.len();
+        let bin_pad = (4 - (bin_len % 4)) % 4; // Padding to 4-byte boundary
+        
+        // Header (12 bytes) plus chunk headers (8 bytes each) and chunk data
+        let mut total_len = 12 + 8 + json_len + json_pad;
+        if !self.buffer_data.is_empty() {
+            total_len += 8 + bin_len + bin_pad;
+        }
+        
+        // Write GLB header (magic, version, length)
+        writer.write_all(b"glTF")?;
+        writer.write_u32::<LittleEndian>(2)?; // version
+        writer.write_u32::<LittleEndian>(total_len as u32)?;
+        
+        writer.write_u32::<LittleEndian>((json_len + json_pad) as u32)?; // chunk length
+        writer.write_u32::<LittleEndian>(0x4E4F534A)?; // chunk type "JSON"
+        writer.write_all(json.as_bytes())?;
         
         // Add padding
         for _ in 0..json_pad {
-            file.write_u8(0x20)?; // Space character for padding
+            writer.write_u8(0x20)?; // Space character for padding
         }
         
-        // BIN chunk
         if !self.buffer_data.is_empty() {
-            let bin_len = self.buffer_data.len();
-            let bin_pad = (4 - (bin_len % 4)) % 4; // Padding to 4-byte boundary
-            
-            file.write_u32::<LittleEndian>((bin_len + bin_pad) as u32)?; // chunk length
-            file.write_u32::<LittleEndian>(0x004E4942)?; // chunk type "BIN"
-            file.write_all(&self.buffer_data)?;
+            writer.write_u32::<LittleEndian>((bin_len + bin_pad) as u32)?; // chunk length
+            writer.write_u32::<LittleEndian>(0x004E4942)?; // chunk type "BIN"
+            writer.write_all(&self.buffer_data)?;
             
             // Add padding
             for _ in 0..bin_pad {
-                file.write_u8(0)?;
+                writer.write_u8(0)?;
             }
         }
         
-        // Go back and write the total length
-        let current_pos = file.stream_position()?;
-        file.seek(io::SeekFrom::Start(length_pos))?;
-        file.write_u32::<LittleEndian>(current_pos as u32)?;
-        
         Ok(())
     }
 }
+
+/// File extension to use when writing an image out as a standalone file
+pub(crate) fn image_extension(image: &Image) -> &'static str {
+    match image.mime_type.as_deref() {
+        Some("image/jpeg") => "jpg",
+        _ => "png",
+    }
+}
@@ -1,4 +1,8 @@
+use std::fmt;
+
 use crate::builder::GltfBuilder;
+use crate::error::{GltfError, Result};
+use crate::handle::{AccessorId, AnimationId, AnimationSamplerId, NodeId};
 use crate::models::{Animation, AnimationChannel, AnimationChannelTarget, AnimationSampler};
 
 /// Animation path type
@@ -9,15 +13,16 @@ pub enum AnimationPath {
     Weights,
 }
 
-impl AnimationPath {
-    /// Convert to string representation for glTF
-    pub fn to_string(&self) -> String {
-        match self {
-            AnimationPath::Translation => "translation".to_string(),
-            AnimationPath::Rotation => "rotation".to_string(),
-            AnimationPath::Scale => "scale".to_string(),
-            AnimationPath::Weights => "weights".to_string(),
-        }
+impl fmt::Display for AnimationPath {
+    /// Format using the string representation used by glTF
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            AnimationPath::Translation => "translation",
+            AnimationPath::Rotation => "rotation",
+            AnimationPath::Scale => "scale",
+            AnimationPath::Weights => "weights",
+        };
+        f.write_str(value)
     }
 }
 
@@ -28,14 +33,15 @@ pub enum InterpolationType {
     CubicSpline,
 }
 
-impl InterpolationType {
-    /// Convert to string representation for glTF
-    pub fn to_string(&self) -> String {
-        match self {
-            InterpolationType::Linear => "LINEAR".to_string(),
-            InterpolationType::Step => "STEP".to_string(),
-            InterpolationType::CubicSpline => "CUBICSPLINE".to_string(),
-        }
+impl fmt::Display for InterpolationType {
+    /// Format using the string representation used by glTF
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            InterpolationType::Linear => "LINEAR",
+            InterpolationType::Step => "STEP",
+            InterpolationType::CubicSpline => "CUBICSPLINE",
+        };
+        f.write_str(value)
     }
 }
 
@@ -49,7 +55,7 @@ impl GltfBuilder {
     /// # Returns
     /// 
     /// The index of the created animation
-    pub fn add_animation(&mut self, name: Option<String>) -> usize {
+    pub fn add_animation(&mut self, name: Option<String>) -> AnimationId {
         let animation = Animation {
             name,
             channels: Some(Vec::new()),
@@ -59,10 +65,10 @@ impl GltfBuilder {
         if let Some(animations) = &mut self.gltf.animations {
             let index = animations.len();
             animations.push(animation);
-            index
+            AnimationId::from_index(index)
         } else {
             self.gltf.animations = Some(vec![animation]);
-            0
+            AnimationId::from_index(0)
         }
     }
     
@@ -77,31 +83,35 @@ impl GltfBuilder {
     /// 
     /// # Returns
     /// 
-    /// The index of the created sampler within the animation
+    /// The index of the created sampler within the animation, or `GltfError::InvalidIndex`
+    /// if the animation or an accessor does not exist
     pub fn add_animation_sampler(
         &mut self, 
-        animation_index: usize, 
-        input_accessor: usize, 
-        output_accessor: usize,
+        animation_index: AnimationId, 
+        input_accessor: AccessorId, 
+        output_accessor: AccessorId,
         interpolation: InterpolationType
-    ) -> usize {
+    ) -> Result<AnimationSamplerId> {
+        let accessor_count = self.gltf.accessors.as_ref().map_or(0, |accessors| accessors.len());
+        if input_accessor.index() >= accessor_count || output_accessor.index() >= accessor_count {
+            return Err(GltfError::InvalidIndex);
+        }
+        
         let sampler = AnimationSampler {
-            input: input_accessor,
+            input: input_accessor.index(),
             interpolation: Some(interpolation.to_string()),
-            output: output_accessor,
+            output: output_accessor.index(),
         };
         
-        let animations = self.gltf.animations.as_mut().expect("Animations array not initialized");
-        if animation_index >= animations.len() {
-            panic!("Animation index out of bounds");
-        }
+        let animation = self.gltf.animations.as_mut()
+            .and_then(|animations| animations.get_mut(animation_index.index()))
+            .ok_or(GltfError::InvalidIndex)?;
         
-        let animation = &mut animations[animation_index];
-        let samplers = animation.samplers.get_or_insert_with(|| Vec::new());
+        let samplers = animation.samplers.get_or_insert_with(Vec::new);
         let sampler_index = samplers.len();
         samplers.push(sampler);
         
-        sampler_index
+        Ok(AnimationSamplerId::from_index(sampler_index))
     }
     
     /// Add a channel to an animation
@@ -115,33 +125,40 @@ impl GltfBuilder {
     /// 
     /// # Returns
     /// 
-    /// The index of the created channel within the animation
+    /// The index of the created channel within the animation, `GltfError::MissingNode` if the
+    /// target node does not exist, or `GltfError::InvalidIndex` if the animation or sampler
+    /// does not exist
     pub fn add_animation_channel(
         &mut self, 
-        animation_index: usize, 
-        sampler_index: usize, 
-        target_node: usize, 
+        animation_index: AnimationId, 
+        sampler_index: AnimationSamplerId, 
+        target_node: NodeId, 
         target_path: AnimationPath
-    ) -> usize {
+    ) -> Result<usize> {
+        self.check_target_node(target_node)?;
+        
         let channel = AnimationChannel {
-            sampler: sampler_index,
+            sampler: sampler_index.index(),
             target: AnimationChannelTarget {
-                node: target_node,
+                node: target_node.index(),
                 path: target_path.to_string(),
             }
         };
         
-        let animations = self.gltf.animations.as_mut().expect("Animations array not initialized");
-        if animation_index >= animations.len() {
-            panic!("Animation index out of bounds");
+        let animation = self.gltf.animations.as_mut()
+            .and_then(|animations| animations.get_mut(animation_index.index()))
+            .ok_or(GltfError::InvalidIndex)?;
+        
+        let sampler_count = animation.samplers.as_ref().map_or(0, |samplers| samplers.len());
+        if sampler_index.index() >= sampler_count {
+            return Err(GltfError::InvalidIndex);
         }
         
-        let animation = &mut animations[animation_index];
-        let channels = animation.channels.get_or_insert_with(|| Vec::new());
+        let channels = animation.channels.get_or_insert_with(Vec::new);
         let channel_index = channels.len();
         channels.push(channel);
         
-        channel_index
+        Ok(channel_index)
     }
     
     /// Create translation keyframes for an animation
@@ -156,18 +173,47 @@ impl GltfBuilder {
     /// 
     /// # Returns
     /// 
-    /// The indices of the created channel and sampler
+    /// The indices of the created channel and sampler, or an error if the animation or node
+    /// does not exist, the timestamps are not strictly increasing, or the number of values
+    /// does not match the timestamps. `CUBICSPLINE` animations take an in-tangent, a value
+    /// and an out-tangent per keyframe, in that order.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use mesh_tools::{GltfBuilder, GltfError, InterpolationType};
+    /// 
+    /// let mut builder = GltfBuilder::new();
+    /// let node = builder.add_node(None, None, None, None, None);
+    /// let animation = builder.add_animation(Some("Hop".to_string()));
+    /// 
+    /// builder.create_translation_animation(
+    ///     animation,
+    ///     node,
+    ///     vec![0.0, 0.5, 1.0],
+    ///     vec![[0.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 0.0]],
+    ///     InterpolationType::Linear,
+    /// ).unwrap();
+    /// 
+    /// // Keyframes out of order are rejected instead of producing a broken file
+    /// let result = builder.create_translation_animation(
+    ///     animation,
+    ///     node,
+    ///     vec![0.0, 1.0, 0.5],
+    ///     vec![[0.0, 0.0, 0.0]; 3],
+    ///     InterpolationType::Linear,
+    /// );
+    /// assert!(matches!(result, Err(GltfError::NonIncreasingTimestamps { index: 2 })));
+    /// ```
     pub fn create_translation_animation(
         &mut self,
-        animation_index: usize,
-        node_index: usize,
+        animation_index: AnimationId,
+        node_index: NodeId,
         timestamps: Vec<f32>,
         translations: Vec<[f32; 3]>,
         interpolation: InterpolationType,
-    ) -> (usize, usize) {
-        if timestamps.len() != translations.len() {
-            panic!("Timestamps and translations must have the same length");
-        }
+    ) -> Result<(usize, AnimationSamplerId)> {
+        self.check_keyframes(animation_index, node_index, &timestamps, translations.len(), &interpolation)?;
         
         // Create time input accessor
         let timestamps_data: Vec<u8> = timestamps.iter().flat_map(|&t| t.to_le_bytes()).collect();
@@ -180,15 +226,16 @@ impl GltfBuilder {
             None
         );
         
-        // Create input accessor (timestamps)
+        // Create input accessor (timestamps), whose bounds are required by the spec
+        let (time_min, time_max) = timestamp_bounds(&timestamps);
         let input_accessor = self.add_accessor(
             time_buffer_view,
             5126, // FLOAT component type
             timestamps.len(),
             "SCALAR".to_string(),
             None,
-            None,
-            None
+            time_min,
+            time_max
         );
         
         // Create translation output accessor
@@ -216,19 +263,19 @@ impl GltfBuilder {
         // Create sampler and channel
         let sampler_index = self.add_animation_sampler(
             animation_index,
-            input_accessor,
-            output_accessor,
+            AccessorId::from_index(input_accessor),
+            AccessorId::from_index(output_accessor),
             interpolation,
-        );
+        )?;
         
         let channel_index = self.add_animation_channel(
             animation_index,
             sampler_index,
             node_index,
             AnimationPath::Translation,
-        );
+        )?;
         
-        (channel_index, sampler_index)
+        Ok((channel_index, sampler_index))
     }
     
     /// Create rotation keyframes for an animation
@@ -243,18 +290,19 @@ impl GltfBuilder {
     /// 
     /// # Returns
     /// 
-    /// The indices of the created channel and sampler
+    /// The indices of the created channel and sampler, or an error if the animation or node
+    /// does not exist, the timestamps are not strictly increasing, or the number of values
+    /// does not match the timestamps. `CUBICSPLINE` animations take an in-tangent, a value
+    /// and an out-tangent per keyframe, in that order.
     pub fn create_rotation_animation(
         &mut self,
-        animation_index: usize,
-        node_index: usize,
+        animation_index: AnimationId,
+        node_index: NodeId,
         timestamps: Vec<f32>,
         rotations: Vec<[f32; 4]>,
         interpolation: InterpolationType,
-    ) -> (usize, usize) {
-        if timestamps.len() != rotations.len() {
-            panic!("Timestamps and rotations must have the same length");
-        }
+    ) -> Result<(usize, AnimationSamplerId)> {
+        self.check_keyframes(animation_index, node_index, &timestamps, rotations.len(), &interpolation)?;
         
         // Create time input accessor
         let timestamps_data: Vec<u8> = timestamps.iter().flat_map(|&t| t.to_le_bytes()).collect();
@@ -267,15 +315,16 @@ impl GltfBuilder {
             None
         );
         
-        // Create input accessor (timestamps)
+        // Create input accessor (timestamps), whose bounds are required by the spec
+        let (time_min, time_max) = timestamp_bounds(&timestamps);
         let input_accessor = self.add_accessor(
             time_buffer_view,
             5126, // FLOAT component type
             timestamps.len(),
             "SCALAR".to_string(),
             None,
-            None,
-            None
+            time_min,
+            time_max
         );
         
         // Create rotation output accessor
@@ -303,19 +352,19 @@ impl GltfBuilder {
         // Create sampler and channel
         let sampler_index = self.add_animation_sampler(
             animation_index,
-            input_accessor,
-            output_accessor,
+            AccessorId::from_index(input_accessor),
+            AccessorId::from_index(output_accessor),
             interpolation,
-        );
+        )?;
         
         let channel_index = self.add_animation_channel(
             animation_index,
             sampler_index,
             node_index,
             AnimationPath::Rotation,
-        );
+        )?;
         
-        (channel_index, sampler_index)
+        Ok((channel_index, sampler_index))
     }
     
     /// Create scale keyframes for an animation
@@ -330,18 +379,19 @@ impl GltfBuilder {
     /// 
     /// # Returns
     /// 
-    /// The indices of the created channel and sampler
+    /// The indices of the created channel and sampler, or an error if the animation or node
+    /// does not exist, the timestamps are not strictly increasing, or the number of values
+    /// does not match the timestamps. `CUBICSPLINE` animations take an in-tangent, a value
+    /// and an out-tangent per keyframe, in that order.
     pub fn create_scale_animation(
         &mut self,
-        animation_index: usize,
-        node_index: usize,
+        animation_index: AnimationId,
+        node_index: NodeId,
         timestamps: Vec<f32>,
         scales: Vec<[f32; 3]>,
         interpolation: InterpolationType,
-    ) -> (usize, usize) {
-        if timestamps.len() != scales.len() {
-            panic!("Timestamps and scales must have the same length");
-        }
+    ) -> Result<(usize, AnimationSamplerId)> {
+        self.check_keyframes(animation_index, node_index, &timestamps, scales.len(), &interpolation)?;
         
         // Create time input accessor
         let timestamps_data: Vec<u8> = timestamps.iter().flat_map(|&t| t.to_le_bytes()).collect();
@@ -354,15 +404,16 @@ impl GltfBuilder {
             None
         );
         
-        // Create input accessor (timestamps)
+        // Create input accessor (timestamps), whose bounds are required by the spec
+        let (time_min, time_max) = timestamp_bounds(&timestamps);
         let input_accessor = self.add_accessor(
             time_buffer_view,
             5126, // FLOAT component type
             timestamps.len(),
             "SCALAR".to_string(),
             None,
-            None,
-            None
+            time_min,
+            time_max
         );
         
         // Create scale output accessor
@@ -390,18 +441,235 @@ impl GltfBuilder {
         // Create sampler and channel
         let sampler_index = self.add_animation_sampler(
             animation_index,
-            input_accessor,
-            output_accessor,
+            AccessorId::from_index(input_accessor),
+            AccessorId::from_index(output_accessor),
             interpolation,
-        );
+        )?;
         
         let channel_index = self.add_animation_channel(
             animation_index,
             sampler_index,
             node_index,
             AnimationPath::Scale,
+        )?;
+        
+        Ok((channel_index, sampler_index))
+    }
+    
+    /// Create morph target weight keyframes for an animation
+    /// 
+    /// # Arguments
+    /// 
+    /// * `animation_index` - The index of the animation
+    /// * `node_index` - The index of the target node, whose mesh has morph targets
+    /// * `timestamps` - Vector of keyframe timestamps (in seconds)
+    /// * `weights` - Vector of weight sets (one weight per morph target for each keyframe)
+    /// * `interpolation` - The interpolation method
+    /// 
+    /// # Returns
+    /// 
+    /// The indices of the created channel and sampler, or an error if the animation or node
+    /// does not exist, the node has no mesh with morph targets, a keyframe does not have one
+    /// weight per morph target, the timestamps are not strictly increasing, or the number of
+    /// values does not match the timestamps. `CUBICSPLINE` animations take an in-tangent, a
+    /// value and an out-tangent per keyframe, in that order.
+    pub fn create_weights_animation(
+        &mut self,
+        animation_index: AnimationId,
+        node_index: NodeId,
+        timestamps: Vec<f32>,
+        weights: Vec<Vec<f32>>,
+        interpolation: InterpolationType,
+    ) -> Result<(usize, AnimationSamplerId)> {
+        self.check_keyframes(animation_index, node_index, &timestamps, weights.len(), &interpolation)?;
+        
+        // Every keyframe holds one weight per morph target of the node's mesh
+        let target_count = self.gltf.nodes.as_ref()
+            .and_then(|nodes| nodes[node_index.index()].mesh)
+            .and_then(|mesh| self.gltf.meshes.as_ref()?.get(mesh))
+            .and_then(|mesh| mesh.primitives.first())
+            .and_then(|primitive| primitive.targets.as_ref())
+            .map_or(0, Vec::len);
+        if target_count == 0 {
+            return Err(GltfError::InvalidData(format!(
+                "Node {} has no mesh with morph targets to animate", node_index.index()
+            )));
+        }
+        if let Some(keyframe) = weights.iter().position(|w| w.len() != target_count) {
+            return Err(GltfError::InvalidData(format!(
+                "Keyframe {} has {} weights, but the mesh has {} morph targets",
+                keyframe, weights[keyframe].len(), target_count
+            )));
+        }
+        
+        // Create time input accessor
+        let timestamps_data: Vec<u8> = timestamps.iter().flat_map(|&t| t.to_le_bytes()).collect();
+        
+        // Add buffer data and create buffer view
+        let (time_offset, time_length) = self.add_buffer_data(&timestamps_data);
+        let time_buffer_view = self.add_buffer_view(
+            time_offset,
+            time_length,
+            None
         );
         
-        (channel_index, sampler_index)
+        // Create input accessor (timestamps), whose bounds are required by the spec
+        let (time_min, time_max) = timestamp_bounds(&timestamps);
+        let input_accessor = self.add_accessor(
+            time_buffer_view,
+            5126, // FLOAT component type
+            timestamps.len(),
+            "SCALAR".to_string(),
+            None,
+            time_min,
+            time_max
+        );
+        
+        // Create weights output accessor, with the weights of all targets for each keyframe in turn
+        let weights_data: Vec<u8> = weights.iter().flat_map(|w| w.iter().flat_map(|&v| v.to_le_bytes())).collect();
+        
+        // Add buffer data and create buffer view
+        let (weights_offset, weights_length) = self.add_buffer_data(&weights_data);
+        let weights_buffer_view = self.add_buffer_view(
+            weights_offset,
+            weights_length,
+            None
+        );
+        
+        // Create output accessor (weights)
+        let output_accessor = self.add_accessor(
+            weights_buffer_view,
+            5126, // FLOAT component type
+            weights.iter().map(|w| w.len()).sum(),
+            "SCALAR".to_string(),
+            None,
+            None,
+            None
+        );
+        
+        // Create sampler and channel
+        let sampler_index = self.add_animation_sampler(
+            animation_index,
+            AccessorId::from_index(input_accessor),
+            AccessorId::from_index(output_accessor),
+            interpolation,
+        )?;
+        
+        let channel_index = self.add_animation_channel(
+            animation_index,
+            sampler_index,
+            node_index,
+            AnimationPath::Weights,
+        )?;
+        
+        Ok((channel_index, sampler_index))
+    }
+    
+    /// Check keyframes before any data is added, so a rejected animation leaves no trace
+    fn check_keyframes(&self, animation_index: AnimationId, node_index: NodeId, timestamps: &[f32],
+                       value_count: usize, interpolation: &InterpolationType) -> Result<()> {
+        let animation_count = self.gltf.animations.as_ref().map_or(0, |animations| animations.len());
+        if animation_index.index() >= animation_count {
+            return Err(GltfError::InvalidIndex);
+        }
+        self.check_target_node(node_index)?;
+        
+        if timestamps.is_empty() {
+            return Err(GltfError::InvalidData("An animation needs at least one keyframe".to_string()));
+        }
+        // Comparisons with NaN are false, so NaN timestamps are rejected as well
+        let in_order = |index: usize| match index {
+            0 => timestamps[0] >= 0.0,
+            _ => timestamps[index] > timestamps[index - 1],
+        };
+        if let Some(index) = (0..timestamps.len()).find(|&index| !in_order(index)) {
+            return Err(GltfError::NonIncreasingTimestamps { index });
+        }
+        
+        if let InterpolationType::CubicSpline = interpolation {
+            if timestamps.len() < 2 {
+                return Err(GltfError::InvalidData(
+                    "CUBICSPLINE animations need at least two keyframes".to_string()
+                ));
+            }
+            if value_count != timestamps.len() * 3 {
+                return Err(GltfError::MissingCubicSplineTangents {
+                    keyframes: timestamps.len(),
+                    values: value_count,
+                });
+            }
+        } else if value_count != timestamps.len() {
+            return Err(GltfError::KeyframeCountMismatch {
+                timestamps: timestamps.len(),
+                values: value_count,
+            });
+        }
+        
+        Ok(())
+    }
+    
+    /// Check that the node targeted by an animation channel exists
+    fn check_target_node(&self, node_index: NodeId) -> Result<()> {
+        let node_count = self.gltf.nodes.as_ref().map_or(0, |nodes| nodes.len());
+        if node_index.index() >= node_count {
+            return Err(GltfError::MissingNode(node_index.index()));
+        }
+        
+        Ok(())
+    }
+}
+
+/// Minimum and maximum keyframe time, for the bounds of an animation input accessor
+///
+/// The timestamps have been checked to be non-empty and increasing.
+fn timestamp_bounds(timestamps: &[f32]) -> (Option<Vec<f32>>, Option<Vec<f32>>) {
+    (Some(vec![timestamps[0]]), Some(vec![timestamps[timestamps.len() - 1]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MorphTarget;
+    use crate::compat::vector3;
+    
+    /// A box mesh with two morph targets on a node
+    fn morphed_node(builder: &mut GltfBuilder) -> NodeId {
+        let mesh = builder.create_box(1.0);
+        let vertex_count = builder.gltf.accessors.as_ref().unwrap()[0].count;
+        for _ in 0..2 {
+            let deltas = vec![vector3::new(0.0, 1.0, 0.0); vertex_count];
+            builder.add_morph_target(mesh, None, 0.0, vec![MorphTarget::new(deltas)]).unwrap();
+        }
+        builder.add_node(None, Some(mesh), None, None, None)
+    }
+    
+    #[test]
+    fn weights_animation_needs_a_mesh_with_targets() {
+        let mut builder = GltfBuilder::new();
+        let animation = builder.add_animation(None);
+        let empty = builder.add_node(None, None, None, None, None);
+        let plain_mesh = builder.create_box(1.0);
+        let plain = builder.add_node(None, Some(plain_mesh), None, None, None);
+        
+        for node in [empty, plain] {
+            let result = builder.create_weights_animation(animation, node, vec![0.0], vec![vec![1.0]],
+                                                          InterpolationType::Linear);
+            assert!(result.is_err());
+        }
+    }
+    
+    #[test]
+    fn weights_animation_needs_one_weight_per_target() {
+        let mut builder = GltfBuilder::new();
+        let node = morphed_node(&mut builder);
+        let animation = builder.add_animation(None);
+        
+        let result = builder.create_weights_animation(animation, node, vec![0.0, 1.0],
+                                                      vec![vec![1.0], vec![0.0]], InterpolationType::Linear);
+        assert!(result.is_err());
+        
+        let result = builder.create_weights_animation(animation, node, vec![0.0, 1.0],
+                                                      vec![vec![1.0, 0.0], vec![0.0, 1.0]], InterpolationType::Linear);
+        assert!(result.is_ok());
     }
 }
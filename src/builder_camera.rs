@@ -0,0 +1,118 @@
+//! # Camera Implementation
+//!
+//! This module implements adding cameras to the glTF document held by a `GltfBuilder`,
+//! attaching them to nodes and orienting those nodes. The camera models themselves are
+//! created by the functions in the `camera` module.
+
+use crate::builder::GltfBuilder;
+use crate::camera;
+use crate::error::{GltfError, Result};
+use crate::handle::{CameraId, NodeId};
+use crate::models::Camera;
+
+impl GltfBuilder {
+    /// Add a perspective camera to the glTF document
+    ///
+    /// # Parameters
+    /// * `name` - Optional name for the camera
+    /// * `yfov` - Vertical field of view in radians
+    /// * `aspect_ratio` - Optional aspect ratio (width / height); viewers use the viewport's when omitted
+    /// * `znear` - Distance to the near clipping plane
+    /// * `zfar` - Optional distance to the far clipping plane; omit for an infinite projection
+    ///
+    /// # Returns
+    /// The index of the created camera
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::GltfBuilder;
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// let box_mesh = builder.create_box(1.0);
+    /// let box_node = builder.add_node(None, Some(box_mesh), None, None, None);
+    ///
+    /// // Place a camera at (3, 2, 5) looking at the box
+    /// let camera = builder.add_perspective_camera(None, 0.8, None, 0.1, Some(100.0));
+    /// let camera_node = builder.add_node(Some("Camera".to_string()), None, Some([3.0, 2.0, 5.0]), None, None);
+    /// builder.attach_camera(camera_node, camera).unwrap();
+    /// builder.look_at(camera_node, [0.0, 0.0, 0.0], None).unwrap();
+    ///
+    /// builder.add_scene(None, Some(vec![box_node, camera_node]));
+    /// ```
+    pub fn add_perspective_camera(&mut self, name: Option<String>, yfov: f32,
+                                  aspect_ratio: Option<f32>, znear: f32,
+                                  zfar: Option<f32>) -> CameraId {
+        let camera = camera::create_perspective_camera(name, yfov, aspect_ratio, znear, zfar);
+        self.add_camera(camera)
+    }
+    
+    /// Add an orthographic camera to the glTF document
+    ///
+    /// # Parameters
+    /// * `name` - Optional name for the camera
+    /// * `xmag` - Half the width of the view volume
+    /// * `ymag` - Half the height of the view volume
+    /// * `znear` - Distance to the near clipping plane
+    /// * `zfar` - Distance to the far clipping plane
+    ///
+    /// # Returns
+    /// The index of the created camera
+    pub fn add_orthographic_camera(&mut self, name: Option<String>, xmag: f32, ymag: f32,
+                                   znear: f32, zfar: f32) -> CameraId {
+        let camera = camera::create_orthographic_camera(name, xmag, ymag, znear, zfar);
+        self.add_camera(camera)
+    }
+    
+    /// Add a camera to the glTF document
+    pub fn add_camera(&mut self, camera: Camera) -> CameraId {
+        if let Some(cameras) = &mut self.gltf.cameras {
+            let index = cameras.len();
+            cameras.push(camera);
+            CameraId::from_index(index)
+        } else {
+            self.gltf.cameras = Some(vec![camera]);
+            CameraId::from_index(0)
+        }
+    }
+    
+    /// Attach a camera to a node, which then defines the camera's position and orientation
+    pub fn attach_camera(&mut self, node: NodeId, camera: CameraId) -> Result<()> {
+        let camera_count = self.gltf.cameras.as_ref().map_or(0, |cameras| cameras.len());
+        if camera.index() >= camera_count {
+            return Err(GltfError::InvalidIndex);
+        }
+        
+        let node = self.gltf.nodes.as_mut()
+            .and_then(|nodes| nodes.get_mut(node.index()))
+            .ok_or(GltfError::InvalidIndex)?;
+        node.camera = Some(camera.index());
+        
+        Ok(())
+    }
+    
+    /// Rotate a node so that its -Z axis, the viewing direction of cameras, points at a target
+    ///
+    /// The node's translation is used as the eye position and its rotation is replaced.
+    /// Both are in the coordinate space of the node's parent.
+    ///
+    /// # Parameters
+    /// * `node` - The node to rotate
+    /// * `target` - Point to look at
+    /// * `up` - Optional up direction; defaults to +Y
+    pub fn look_at(&mut self, node: NodeId, target: [f32; 3], up: Option<[f32; 3]>) -> Result<()> {
+        let node = self.gltf.nodes.as_mut()
+            .and_then(|nodes| nodes.get_mut(node.index()))
+            .ok_or(GltfError::InvalidIndex)?;
+        
+        if node.matrix.is_some() {
+            return Err(GltfError::InvalidData(
+                "Cannot orient a node whose transform is given as a matrix".to_string()
+            ));
+        }
+        
+        let eye = node.translation.unwrap_or([0.0, 0.0, 0.0]);
+        node.rotation = Some(camera::look_at_rotation(eye, target, up.unwrap_or([0.0, 1.0, 0.0])));
+        
+        Ok(())
+    }
+}
@@ -0,0 +1,235 @@
+//! # Vertex Color Implementation
+//!
+//! This module implements `COLOR_0` vertex colors for meshes in the glTF document held by a
+//! `GltfBuilder`. Colors can be RGB or RGBA and stored as floats or as normalized unsigned
+//! bytes or shorts, which take a quarter or half of the space of floats.
+//!
+//! glTF vertex colors are in linear color space and multiply the material's base color.
+
+use crate::builder::GltfBuilder;
+use crate::builder_primitives::Triangle;
+use crate::compat::{Point3, Vector2, Vector3};
+use crate::constants::{accessor_type, component_type};
+use crate::error::{GltfError, Result};
+use crate::geometry::GeneratedNormals;
+use crate::handle::{MaterialId, MeshId};
+
+/// Vertex colors for a primitive, one entry per vertex
+///
+/// Integer colors are normalized, so `255` (or `65535`) means full intensity.
+#[derive(Debug, Clone)]
+pub enum VertexColors {
+    /// Float RGB colors
+    Rgb(Vec<[f32; 3]>),
+    /// Float RGBA colors
+    Rgba(Vec<[f32; 4]>),
+    /// Normalized unsigned byte RGB colors
+    RgbU8(Vec<[u8; 3]>),
+    /// Normalized unsigned byte RGBA colors
+    RgbaU8(Vec<[u8; 4]>),
+    /// Normalized unsigned short RGB colors
+    RgbU16(Vec<[u16; 3]>),
+    /// Normalized unsigned short RGBA colors
+    RgbaU16(Vec<[u16; 4]>),
+}
+
+impl VertexColors {
+    /// Number of vertices with a color
+    fn count(&self) -> usize {
+        match self {
+            VertexColors::Rgb(colors) => colors.len(),
+            VertexColors::Rgba(colors) => colors.len(),
+            VertexColors::RgbU8(colors) => colors.len(),
+            VertexColors::RgbaU8(colors) => colors.len(),
+            VertexColors::RgbU16(colors) => colors.len(),
+            VertexColors::RgbaU16(colors) => colors.len(),
+        }
+    }
+    
+    /// Carry the colors over to vertices generated from the original ones
+    fn remap(&self, generated: &GeneratedNormals) -> Self {
+        match self {
+            VertexColors::Rgb(colors) => VertexColors::Rgb(generated.remap(colors)),
+            VertexColors::Rgba(colors) => VertexColors::Rgba(generated.remap(colors)),
+            VertexColors::RgbU8(colors) => VertexColors::RgbU8(generated.remap(colors)),
+            VertexColors::RgbaU8(colors) => VertexColors::RgbaU8(generated.remap(colors)),
+            VertexColors::RgbU16(colors) => VertexColors::RgbU16(generated.remap(colors)),
+            VertexColors::RgbaU16(colors) => VertexColors::RgbaU16(generated.remap(colors)),
+        }
+    }
+}
+
+impl GltfBuilder {
+    /// Create a mesh with custom geometry and vertex colors
+    ///
+    /// Takes the same parameters as `create_custom_mesh`, plus the colors stored as `COLOR_0`.
+    ///
+    /// # Returns
+    /// The handle of the created mesh, or an error if the number of colors does not match the
+    /// number of vertices
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::{GltfBuilder, Triangle, VertexColors};
+    /// use mesh_tools::compat::point3;
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// let positions = vec![
+    ///     point3::new(0.0, 0.0, 0.0),
+    ///     point3::new(1.0, 0.0, 0.0),
+    ///     point3::new(0.0, 1.0, 0.0),
+    /// ];
+    /// let colors = VertexColors::RgbU8(vec![[255, 0, 0], [0, 255, 0], [0, 0, 255]]);
+    ///
+    /// let mesh = builder.create_custom_mesh_with_colors(
+    ///     Some("Gradient".to_string()), &positions, &[Triangle::new(0, 1, 2)], None, None, colors, None,
+    /// ).unwrap();
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_custom_mesh_with_colors(&mut self,
+                                          name: Option<String>,
+                                          positions: &[Point3<f32>],
+                                          indices: &[Triangle],
+                                          normals: Option<Vec<Vector3<f32>>>,
+                                          texcoords: Option<Vec<Vec<Vector2<f32>>>>,
+                                          colors: VertexColors,
+                                          material: Option<MaterialId>) -> Result<MeshId> {
+        check_color_count(&colors, positions.len())?;
+        
+        // Generate normals if requested, which may split vertices
+        if let Some(generated) = self.generate_missing_normals(positions, indices, &normals, &texcoords) {
+            let texcoords = texcoords.map(|sets| sets.iter().map(|set| generated.remap(set)).collect());
+            let colors = colors.remap(&generated);
+            return self.create_custom_mesh_with_colors(name, &generated.positions, &generated.indices,
+                                                       Some(generated.normals), texcoords, colors, material);
+        }
+        
+        let mut primitive = self.create_custom_primitive(positions, indices, normals, texcoords, material);
+        let color_accessor = self.add_color_accessor(&colors);
+        primitive.attributes.insert("COLOR_0".to_string(), color_accessor);
+        
+        Ok(self.add_mesh(name, vec![primitive]))
+    }
+    
+    /// Add vertex colors to a primitive of an existing mesh as `COLOR_0`
+    ///
+    /// # Parameters
+    /// * `mesh` - The mesh containing the primitive
+    /// * `primitive_index` - The primitive within the mesh
+    /// * `colors` - One color per vertex of the primitive
+    ///
+    /// # Returns
+    /// An error if the primitive does not exist or the number of colors does not match the
+    /// number of vertices
+    pub fn add_vertex_colors(&mut self, mesh: MeshId, primitive_index: usize,
+                             colors: VertexColors) -> Result<()> {
+        let vertex_count = self.primitive_vertex_count(mesh, primitive_index)?;
+        check_color_count(&colors, vertex_count)?;
+        
+        let color_accessor = self.add_color_accessor(&colors);
+        self.gltf.meshes.as_mut().unwrap()[mesh.index()].primitives[primitive_index]
+            .attributes.insert("COLOR_0".to_string(), color_accessor);
+        
+        Ok(())
+    }
+    
+    /// Color every vertex of a mesh with a callback
+    ///
+    /// The callback receives the position and, if the primitive has normals, the normal of each
+    /// vertex and returns its RGBA color. This works with any mesh, including the ones created
+    /// by the primitive shape methods.
+    ///
+    /// # Parameters
+    /// * `mesh` - The mesh to color
+    /// * `color` - Function computing the color of a vertex
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::GltfBuilder;
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// let terrain = builder.create_plane(10.0, 10.0, 32, 32, None);
+    ///
+    /// // Shade from blue in the west to red in the east
+    /// builder.color_vertices(terrain, |position, _normal| {
+    ///     let t = (position.x + 5.0) / 10.0;
+    ///     [t, 0.2, 1.0 - t, 1.0]
+    /// }).unwrap();
+    /// ```
+    pub fn color_vertices<F>(&mut self, mesh: MeshId, mut color: F) -> Result<()>
+    where
+        F: FnMut(Point3<f32>, Option<Vector3<f32>>) -> [f32; 4],
+    {
+        let primitive_count = self.gltf.meshes.as_ref()
+            .and_then(|meshes| meshes.get(mesh.index()))
+            .ok_or(GltfError::InvalidIndex)?
+            .primitives.len();
+        
+        for primitive_index in 0..primitive_count {
+            let attributes = &self.gltf.meshes.as_ref().unwrap()[mesh.index()].primitives[primitive_index].attributes;
+            let position_accessor = attributes.get("POSITION").copied();
+            let normal_accessor = attributes.get("NORMAL").copied();
+            
+            let reader = self.accessor_reader();
+            let positions = match position_accessor {
+                Some(accessor) => reader.read_positions(accessor)?,
+                None => continue,
+            };
+            let normals = normal_accessor.map(|accessor| reader.read_vec3(accessor)).transpose()?;
+            
+            let colors = positions.iter().enumerate()
+                .map(|(i, &position)| color(position, normals.as_ref().and_then(|normals| normals.get(i).copied())))
+                .collect();
+            
+            self.add_vertex_colors(mesh, primitive_index, VertexColors::Rgba(colors))?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Upload vertex colors and create a `COLOR_0` accessor for them
+    ///
+    /// Vertex attribute elements must start on 4-byte boundaries, so three-component integer
+    /// colors are padded and given a byte stride.
+    fn add_color_accessor(&mut self, colors: &VertexColors) -> usize {
+        let (bytes, component, type_, normalized, stride): (Vec<u8>, usize, &str, bool, Option<usize>) = match colors {
+            VertexColors::Rgb(colors) => (
+                colors.iter().flatten().flat_map(|c| c.to_le_bytes()).collect(),
+                component_type::FLOAT, accessor_type::VEC3, false, None,
+            ),
+            VertexColors::Rgba(colors) => (
+                colors.iter().flatten().flat_map(|c| c.to_le_bytes()).collect(),
+                component_type::FLOAT, accessor_type::VEC4, false, None,
+            ),
+            VertexColors::RgbU8(colors) => (
+                colors.iter().flat_map(|&[r, g, b]| [r, g, b, 0]).collect(),
+                component_type::UNSIGNED_BYTE, accessor_type::VEC3, true, Some(4),
+            ),
+            VertexColors::RgbaU8(colors) => (
+                colors.iter().flatten().copied().collect(),
+                component_type::UNSIGNED_BYTE, accessor_type::VEC4, true, None,
+            ),
+            VertexColors::RgbU16(colors) => (
+                colors.iter().flat_map(|&[r, g, b]| [r, g, b, 0]).flat_map(|c| c.to_le_bytes()).collect(),
+                component_type::UNSIGNED_SHORT, accessor_type::VEC3, true, Some(8),
+            ),
+            VertexColors::RgbaU16(colors) => (
+                colors.iter().flatten().flat_map(|c| c.to_le_bytes()).collect(),
+                component_type::UNSIGNED_SHORT, accessor_type::VEC4, true, None,
+            ),
+        };
+        
+        self.add_attribute_accessor(&bytes, component, colors.count(), type_, stride, normalized)
+    }
+}
+
+/// Check that there is one color per vertex
+fn check_color_count(colors: &VertexColors, vertex_count: usize) -> Result<()> {
+    if colors.count() != vertex_count {
+        return Err(GltfError::InvalidData(format!(
+            "Got {} vertex colors for {} vertices", colors.count(), vertex_count
+        )));
+    }
+    
+    Ok(())
+}
@@ -0,0 +1,255 @@
+//! # Resource Deduplication Implementation
+//!
+//! This module implements the deduplication applied on export unless disabled with
+//! `GltfBuilder::set_deduplicate`. Identical geometry, textures and materials are often
+//! created many times over, for example by calling `create_box_with_material` for every
+//! instance of a prop. On export, buffer views with the same bytes, and accessors, samplers,
+//! images, textures and materials with the same content, are merged into one, and every
+//! reference to a duplicate is redirected to the copy that is kept.
+//!
+//! Objects are compared by their serialized content after their own references have been
+//! redirected, so two materials using duplicate textures are merged as well. The document
+//! held by the builder is not changed, so objects can still be edited through their handles.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::builder::GltfBuilder;
+use crate::error::{GltfError, Result};
+use crate::models::Material;
+
+impl GltfBuilder {
+    /// Copy the builder with duplicate resources merged
+    ///
+    /// The copy has deduplication disabled and otherwise the same settings.
+    pub(crate) fn deduplicated(&self) -> Result<GltfBuilder> {
+        let mut gltf = self.gltf.clone();
+        
+        // Buffer views with the same bytes and layout share one copy of the data
+        let mut buffer_data = Vec::new();
+        let mut view_map = Vec::new();
+        let mut unique_views = Vec::new();
+        let mut seen_views = HashMap::new();
+        for (index, mut view) in gltf.buffer_views.take().unwrap_or_default().into_iter().enumerate() {
+            let bytes = self.buffer_view_bytes(index)?;
+            let key = (bytes, view.byte_stride, view.target);
+            if let Some(&existing) = seen_views.get(&key) {
+                view_map.push(existing);
+                continue;
+            }
+            
+            while buffer_data.len() % 4 != 0 {
+                buffer_data.push(0);
+            }
+            view.byte_offset = buffer_data.len();
+            buffer_data.extend_from_slice(bytes);
+            
+            seen_views.insert(key, unique_views.len());
+            view_map.push(unique_views.len());
+            unique_views.push(view);
+        }
+        gltf.buffer_views = Some(unique_views);
+        if let Some(buffers) = &mut gltf.buffers {
+            if !buffers.is_empty() {
+                buffers[0].byte_length = buffer_data.len();
+            }
+        }
+        
+        if let Some(accessors) = &mut gltf.accessors {
+            for accessor in accessors.iter_mut() {
+                remap_option(&mut accessor.buffer_view, &view_map)?;
+                if let Some(sparse) = &mut accessor.sparse {
+                    sparse.indices.buffer_view = remap(sparse.indices.buffer_view, &view_map)?;
+                    sparse.values.buffer_view = remap(sparse.values.buffer_view, &view_map)?;
+                }
+            }
+        }
+        let accessor_map = merge_duplicates(&mut gltf.accessors)?;
+        
+        for primitive in gltf.meshes.iter_mut().flatten().flat_map(|mesh| mesh.primitives.iter_mut()) {
+            for accessor in primitive.attributes.values_mut() {
+                *accessor = remap(*accessor, &accessor_map)?;
+            }
+            remap_option(&mut primitive.indices, &accessor_map)?;
+            for target in primitive.targets.iter_mut().flatten() {
+                for accessor in target.values_mut() {
+                    *accessor = remap(*accessor, &accessor_map)?;
+                }
+            }
+        }
+        for sampler in gltf.animations.iter_mut().flatten().flat_map(|animation| animation.samplers.iter_mut().flatten()) {
+            sampler.input = remap(sampler.input, &accessor_map)?;
+            sampler.output = remap(sampler.output, &accessor_map)?;
+        }
+        for skin in gltf.skins.iter_mut().flatten() {
+            remap_option(&mut skin.inverse_bind_matrices, &accessor_map)?;
+        }
+        
+        // Textures reference samplers and images, and materials reference textures
+        let sampler_map = merge_duplicates(&mut gltf.samplers)?;
+        for image in gltf.images.iter_mut().flatten() {
+            remap_option(&mut image.buffer_view, &view_map)?;
+        }
+        let image_map = merge_duplicates(&mut gltf.images)?;
+        
+        for texture in gltf.textures.iter_mut().flatten() {
+            texture.source = remap(texture.source, &image_map)?;
+            remap_option(&mut texture.sampler, &sampler_map)?;
+        }
+        let texture_map = merge_duplicates(&mut gltf.textures)?;
+        
+        for material in gltf.materials.iter_mut().flatten() {
+            for texture in material_textures(material) {
+                *texture = remap(*texture, &texture_map)?;
+            }
+        }
+        let material_map = merge_duplicates(&mut gltf.materials)?;
+        
+        for primitive in gltf.meshes.iter_mut().flatten().flat_map(|mesh| mesh.primitives.iter_mut()) {
+            remap_option(&mut primitive.material, &material_map)?;
+        }
+        
+        let mut options = self.options.clone();
+        options.deduplicate = false;
+        
+        Ok(GltfBuilder {
+            gltf,
+            buffer_data,
+            options,
+        })
+    }
+}
+
+/// Get the texture indices referenced by a material
+pub(crate) fn material_textures(material: &mut Material) -> Vec<&mut usize> {
+    let mut textures = Vec::new();
+    
+    if let Some(pbr) = &mut material.pbr_metallic_roughness {
+        textures.extend(pbr.base_color_texture.as_mut().map(|info| &mut info.index));
+        textures.extend(pbr.metallic_roughness_texture.as_mut().map(|info| &mut info.index));
+    }
+    textures.extend(material.normal_texture.as_mut().map(|info| &mut info.index));
+    textures.extend(material.occlusion_texture.as_mut().map(|info| &mut info.index));
+    textures.extend(material.emissive_texture.as_mut().map(|info| &mut info.index));
+    if let Some(specular) = material.extensions.as_mut().and_then(|extensions| extensions.pbr_specular_glossiness.as_mut()) {
+        textures.extend(specular.diffuse_texture.as_mut().map(|info| &mut info.index));
+        textures.extend(specular.specular_glossiness_texture.as_mut().map(|info| &mut info.index));
+    }
+    
+    textures
+}
+
+/// Remove objects whose serialized content equals an earlier one
+///
+/// Returns the new index of every original object.
+fn merge_duplicates<T: Serialize>(items: &mut Option<Vec<T>>) -> Result<Vec<usize>> {
+    let Some(list) = items.take() else {
+        return Ok(Vec::new());
+    };
+    
+    let mut map = Vec::with_capacity(list.len());
+    let mut unique = Vec::new();
+    let mut seen = HashMap::new();
+    for item in list {
+        let key = serde_json::to_string(&item)?;
+        if let Some(&existing) = seen.get(&key) {
+            map.push(existing);
+            continue;
+        }
+        
+        seen.insert(key, unique.len());
+        map.push(unique.len());
+        unique.push(item);
+    }
+    
+    *items = Some(unique);
+    Ok(map)
+}
+
+/// Redirect an index through a map from old to new indices
+fn remap(index: usize, map: &[usize]) -> Result<usize> {
+    map.get(index).copied().ok_or(GltfError::InvalidIndex)
+}
+
+/// Redirect an optional index through a map from old to new indices
+fn remap_option(index: &mut Option<usize>, map: &[usize]) -> Result<()> {
+    if let Some(index) = index {
+        *index = remap(*index, map)?;
+    }
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    
+    use crate::GltfBuilder;
+    use crate::constants::component_type;
+    use crate::handle::MeshId;
+    use crate::models::{Accessor, AccessorSparse, AccessorSparseIndices, AccessorSparseValues};
+    
+    /// Add a morph target to the first primitive of a mesh that moves vertex 2 up, stored as
+    /// a sparse accessor without a buffer view of its own
+    fn add_sparse_target(builder: &mut GltfBuilder, mesh: MeshId) {
+        let vertex_count = builder.primitive_vertex_count(mesh, 0).unwrap();
+        let (offset, length) = builder.add_buffer_data(&2u16.to_le_bytes());
+        let indices_view = builder.add_buffer_view(offset, length, None);
+        let values: Vec<u8> = [0.0f32, 1.0, 0.0].iter().flat_map(|value| value.to_le_bytes()).collect();
+        let (offset, length) = builder.add_buffer_data(&values);
+        let values_view = builder.add_buffer_view(offset, length, None);
+        
+        let accessors = builder.gltf.accessors.as_mut().unwrap();
+        accessors.push(Accessor {
+            buffer_view: None,
+            component_type: component_type::FLOAT,
+            count: vertex_count,
+            type_: "VEC3".to_string(),
+            byte_offset: None,
+            min: Some(vec![0.0, 0.0, 0.0]),
+            max: Some(vec![0.0, 1.0, 0.0]),
+            normalized: None,
+            sparse: Some(AccessorSparse {
+                count: 1,
+                indices: AccessorSparseIndices { buffer_view: indices_view, byte_offset: 0, component_type: component_type::UNSIGNED_SHORT },
+                values: AccessorSparseValues { buffer_view: values_view, byte_offset: 0 },
+            }),
+        });
+        let target = HashMap::from([("POSITION".to_string(), accessors.len() - 1)]);
+        builder.gltf.meshes.as_mut().unwrap()[mesh.index()].primitives[0].targets = Some(vec![target]);
+    }
+    
+    #[test]
+    fn shared_textures_materials_and_sparse_accessors_are_merged() {
+        let mut builder = GltfBuilder::new();
+        let mut nodes = Vec::new();
+        for _ in 0..2 {
+            let texture = builder.create_checkerboard_texture(8, 8, 2, [255, 0, 0], [0, 0, 0]).unwrap();
+            let material = builder.create_textured_material(None, texture);
+            let mesh = builder.create_box_with_material(1.0, Some(material));
+            add_sparse_target(&mut builder, mesh);
+            nodes.push(builder.add_node(None, Some(mesh), None, None, None));
+        }
+        builder.add_scene(None, Some(nodes));
+        
+        let deduplicated = builder.deduplicated().unwrap();
+        let gltf = &deduplicated.gltf;
+        
+        assert_eq!(gltf.images.as_ref().unwrap().len(), 1);
+        assert_eq!(gltf.textures.as_ref().unwrap().len(), 1);
+        assert_eq!(gltf.materials.as_ref().unwrap().len(), 1);
+        assert_eq!(gltf.accessors.as_ref().unwrap().len(), builder.gltf.accessors.as_ref().unwrap().len() / 2);
+        
+        let primitives: Vec<_> = gltf.meshes.as_ref().unwrap().iter().map(|mesh| &mesh.primitives[0]).collect();
+        assert_eq!(primitives[0].material, primitives[1].material);
+        assert_eq!(primitives[0].attributes, primitives[1].attributes);
+        assert_eq!(primitives[0].targets, primitives[1].targets);
+        
+        let target = primitives[0].targets.as_ref().unwrap()[0]["POSITION"];
+        let deltas = deduplicated.accessor_reader().read_vec3(target).unwrap();
+        assert_eq!((deltas[2].x, deltas[2].y, deltas[2].z), (0.0, 1.0, 0.0));
+        assert!(deltas.iter().enumerate().all(|(i, delta)| i == 2 || delta.y == 0.0));
+        assert!(deduplicated.validate().is_valid(), "{}", deduplicated.validate());
+    }
+}
@@ -0,0 +1,116 @@
+//! # Generated Vertex Attribute Implementation
+//!
+//! This module implements adding vertex attributes derived from mesh geometry, such as normals
+//! and tangents, to meshes in the glTF document held by a `GltfBuilder`. The attributes are
+//! computed by the functions in the `geometry` module.
+
+use crate::builder::GltfBuilder;
+use crate::builder_primitives::Triangle;
+use crate::compat::{Point3, Vector2, Vector3};
+use crate::constants::{accessor_type, primitive_mode};
+use crate::error::{GltfError, Result};
+use crate::geometry::{self, GeneratedNormals};
+use crate::handle::MeshId;
+
+impl GltfBuilder {
+    /// Generate tangents for every primitive of an existing mesh
+    ///
+    /// Tangents are computed with `geometry::generate_tangents` from the positions, normals and
+    /// `TEXCOORD_0` of each triangle primitive, and stored as its `TANGENT` attribute. This is
+    /// useful for imported meshes; new meshes get tangents automatically after
+    /// `set_generate_tangents(true)`.
+    ///
+    /// # Parameters
+    /// * `mesh` - The mesh to generate tangents for
+    ///
+    /// # Returns
+    /// An error if the mesh does not exist, or a triangle primitive lacks normals or texture
+    /// coordinates. Point and line primitives are skipped.
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::GltfBuilder;
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// let plane = builder.create_plane(2.0, 2.0, 4, 4, None);
+    /// builder.generate_mesh_tangents(plane).unwrap();
+    ///
+    /// let primitive = &builder.gltf.meshes.as_ref().unwrap()[plane.index()].primitives[0];
+    /// assert!(primitive.attributes.contains_key("TANGENT"));
+    /// ```
+    pub fn generate_mesh_tangents(&mut self, mesh: MeshId) -> Result<()> {
+        let mesh_index = mesh.index();
+        let primitive_count = self.gltf.meshes.as_ref()
+            .and_then(|meshes| meshes.get(mesh_index))
+            .ok_or(GltfError::InvalidIndex)?
+            .primitives.len();
+        
+        for primitive_index in 0..primitive_count {
+            let primitive = &self.gltf.meshes.as_ref().unwrap()[mesh_index].primitives[primitive_index];
+            let mode = primitive.mode.unwrap_or(primitive_mode::TRIANGLES);
+            if ![primitive_mode::TRIANGLES, primitive_mode::TRIANGLE_STRIP, primitive_mode::TRIANGLE_FAN].contains(&mode) {
+                continue;
+            }
+            
+            let accessor = |name: &str| primitive.attributes.get(name).copied().ok_or_else(|| {
+                GltfError::InvalidData(format!(
+                    "Primitive {} of mesh {} has no {} attribute", primitive_index, mesh_index, name
+                ))
+            });
+            
+            let reader = self.accessor_reader();
+            let positions = reader.read_positions(accessor("POSITION")?)?;
+            let normals = reader.read_vec3(accessor("NORMAL")?)?;
+            let texcoords = reader.read_vec2(accessor("TEXCOORD_0")?)?;
+            let triangles = reader.read_primitive_triangles(primitive)?;
+            
+            let tangents = geometry::generate_tangents(&positions, &normals, &texcoords, &triangles)?;
+            let tangent_accessor = self.add_float_attribute(tangents.as_flattened(), accessor_type::VEC4, false);
+            self.gltf.meshes.as_mut().unwrap()[mesh_index].primitives[primitive_index]
+                .attributes.insert("TANGENT".to_string(), tangent_accessor);
+        }
+        
+        Ok(())
+    }
+    
+    /// Generate normals for new geometry given without them, if enabled with `set_generate_normals`
+    ///
+    /// Returns `None` when the geometry has normals, generation is disabled, or the geometry is
+    /// invalid, in which case it is uploaded as it is.
+    pub(crate) fn generate_missing_normals(&self, positions: &[Point3<f32>], indices: &[Triangle],
+                                           normals: &Option<Vec<Vector3<f32>>>,
+                                           texcoords: &Option<Vec<Vec<Vector2<f32>>>>) -> Option<GeneratedNormals> {
+        let mode = self.options.generate_normals?;
+        if normals.is_some() || indices.is_empty() {
+            return None;
+        }
+        
+        // Other attributes are remapped to the new vertices, so they must match the positions
+        if texcoords.iter().flatten().any(|set| set.len() != positions.len()) {
+            return None;
+        }
+        
+        geometry::generate_normals(positions, indices, mode).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GltfBuilder;
+    use crate::primitives::generate_plane;
+    
+    #[test]
+    fn geometry_without_tangents_is_created_without_tangent_attribute() {
+        let mut builder = GltfBuilder::new();
+        builder.set_generate_tangents(true);
+        
+        // One texture coordinate short
+        let (positions, indices, normals, mut uvs) = generate_plane(1.0, 1.0, 1, 1);
+        uvs.pop();
+        let mesh = builder.create_custom_mesh(None, &positions, &indices, Some(normals), Some(vec![uvs]), None);
+        
+        let primitive = &builder.gltf.meshes.as_ref().unwrap()[mesh.index()].primitives[0];
+        assert!(!primitive.attributes.contains_key("TANGENT"));
+        assert!(builder.generate_mesh_tangents(mesh).is_err());
+    }
+}
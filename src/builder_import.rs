@@ -0,0 +1,267 @@
+//! # glTF/GLB Import Implementation
+//!
+//! This module implements loading existing glTF and GLB files back into a `GltfBuilder`.
+//! The JSON document is deserialized into the models in `models.rs`, and all buffers
+//! (the GLB `BIN` chunk, external `.bin` files and base64 data URIs) are resolved and
+//! merged into the builder's single binary buffer, so the result can be extended with
+//! the usual builder methods and exported again.
+//!
+//! Properties that have no counterpart in the data model are dropped during import.
+
+use std::fs;
+use std::path::Path;
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::builder::{BuilderOptions, GltfBuilder};
+use crate::error::{GltfError, Result};
+use crate::models::{Buffer, Gltf};
+
+/// GLB chunk type for the JSON chunk ("JSON")
+const CHUNK_JSON: u32 = 0x4E4F534A;
+
+/// GLB chunk type for the binary chunk ("BIN\0")
+const CHUNK_BIN: u32 = 0x004E4942;
+
+impl GltfBuilder {
+    /// Load a GLB file into a new builder
+    ///
+    /// External buffer URIs are resolved relative to the directory containing `path`.
+    pub fn from_glb(path: &str) -> Result<Self> {
+        let path = Path::new(path);
+        let bytes = fs::read(path)?;
+        
+        Self::from_glb_slice(&bytes, path.parent())
+    }
+    
+    /// Load GLB data from memory into a new builder
+    ///
+    /// Buffers referencing external files cannot be resolved and produce an error.
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::GltfBuilder;
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// let box_mesh = builder.create_box(1.0);
+    /// let node = builder.add_node(None, Some(box_mesh), None, None, None);
+    /// builder.add_scene(None, Some(vec![node]));
+    /// let bytes = builder.to_glb_bytes().unwrap();
+    ///
+    /// // Load the model back and add another node to it
+    /// let mut loaded = GltfBuilder::from_glb_bytes(&bytes).unwrap();
+    /// let sphere = loaded.create_sphere(0.5, 16, 8, None);
+    /// loaded.add_node(None, Some(sphere), Some([2.0, 0.0, 0.0]), None, None);
+    /// assert_eq!(loaded.gltf.meshes.as_ref().unwrap().len(), 2);
+    /// ```
+    pub fn from_glb_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::from_glb_slice(bytes, None)
+    }
+    
+    /// Load a `.gltf` JSON file into a new builder
+    ///
+    /// External buffer URIs are resolved relative to the directory containing `path`,
+    /// and base64 data URIs are decoded.
+    pub fn from_gltf(path: &str) -> Result<Self> {
+        let path = Path::new(path);
+        let json = fs::read(path)?;
+        let gltf: Gltf = serde_json::from_slice(&json)?;
+        
+        Self::from_document(gltf, None, path.parent())
+    }
+    
+    /// Parse the GLB container and build from its JSON and BIN chunks
+    fn from_glb_slice(bytes: &[u8], base_dir: Option<&Path>) -> Result<Self> {
+        if bytes.len() < 12 || &bytes[0..4] != b"glTF" {
+            return Err(GltfError::InvalidData("Not a GLB file".to_string()));
+        }
+        
+        let version = LittleEndian::read_u32(&bytes[4..8]);
+        if version != 2 {
+            return Err(GltfError::InvalidData(format!("Unsupported GLB version {}", version)));
+        }
+        
+        let total_len = (LittleEndian::read_u32(&bytes[8..12]) as usize).min(bytes.len());
+        
+        let mut json_chunk = None;
+        let mut bin_chunk = None;
+        let mut offset = 12;
+        
+        // Walk the chunks; unknown chunk types must be ignored
+        while offset + 8 <= total_len {
+            let chunk_len = LittleEndian::read_u32(&bytes[offset..offset + 4]) as usize;
+            let chunk_type = LittleEndian::read_u32(&bytes[offset + 4..offset + 8]);
+            let start = offset + 8;
+            let end = start + chunk_len;
+            
+            if end > total_len {
+                return Err(GltfError::InvalidData("GLB chunk exceeds file length".to_string()));
+            }
+            
+            match chunk_type {
+                CHUNK_JSON if json_chunk.is_none() => json_chunk = Some(&bytes[start..end]),
+                CHUNK_BIN if bin_chunk.is_none() => bin_chunk = Some(&bytes[start..end]),
+                _ => {}
+            }
+            
+            offset = end;
+        }
+        
+        let json = json_chunk.ok_or_else(|| GltfError::InvalidData("GLB file has no JSON chunk".to_string()))?;
+        let gltf: Gltf = serde_json::from_slice(json)?;
+        
+        Self::from_document(gltf, bin_chunk, base_dir)
+    }
+    
+    /// Create a builder from a parsed document, merging all of its buffers into one
+    fn from_document(mut gltf: Gltf, bin_chunk: Option<&[u8]>, base_dir: Option<&Path>) -> Result<Self> {
+        let buffers = gltf.buffers.take().unwrap_or_default();
+        
+        // Load every buffer and append it to the merged buffer data
+        let mut buffer_data = Vec::new();
+        let mut buffer_offsets = Vec::with_capacity(buffers.len());
+        
+        for (index, buffer) in buffers.iter().enumerate() {
+            let data = match &buffer.uri {
+                Some(uri) => load_uri(uri, base_dir)?,
+                None if index == 0 => bin_chunk
+                    .ok_or_else(|| GltfError::InvalidData("Buffer 0 has no URI and no GLB BIN chunk".to_string()))?
+                    .to_vec(),
+                None => return Err(GltfError::InvalidData(format!("Buffer {} has no URI", index))),
+            };
+            
+            if data.len() < buffer.byte_length {
+                return Err(GltfError::InvalidData(format!(
+                    "Buffer {} is {} bytes but byteLength is {}", index, data.len(), buffer.byte_length
+                )));
+            }
+            
+            while buffer_data.len() % 4 != 0 {
+                buffer_data.push(0);
+            }
+            
+            buffer_offsets.push(buffer_data.len());
+            buffer_data.extend_from_slice(&data[..buffer.byte_length]);
+        }
+        
+        // Point all buffer views at the merged buffer, keeping each one inside its own buffer
+        if let Some(buffer_views) = &mut gltf.buffer_views {
+            for (index, view) in buffer_views.iter_mut().enumerate() {
+                let base = buffer_offsets.get(view.buffer).copied().ok_or(GltfError::InvalidIndex)?;
+                let fits = view.byte_offset.checked_add(view.byte_length)
+                    .is_some_and(|end| end <= buffers[view.buffer].byte_length);
+                if !fits {
+                    return Err(GltfError::InvalidData(format!("Buffer view {} exceeds buffer {}", index, view.buffer)));
+                }
+                view.byte_offset = view.byte_offset.checked_add(base)
+                    .ok_or_else(|| GltfError::InvalidData(format!("Buffer view {} offset overflows", index)))?;
+                view.buffer = 0;
+            }
+        }
+        
+        gltf.buffers = Some(vec![Buffer {
+            byte_length: buffer_data.len(),
+            uri: None,
+        }]);
+        
+        Ok(GltfBuilder {
+            gltf,
+            buffer_data,
+            options: BuilderOptions::default(),
+        })
+    }
+}
+
+/// Load the contents of a buffer URI, either a base64 data URI or a relative file path
+pub(crate) fn load_uri(uri: &str, base_dir: Option<&Path>) -> Result<Vec<u8>> {
+    if let Some(data) = uri.strip_prefix("data:") {
+        let (_, encoded) = data.split_once(";base64,")
+            .ok_or_else(|| GltfError::InvalidData("Only base64 data URIs are supported".to_string()))?;
+        
+        return base64::decode(encoded)
+            .map_err(|err| GltfError::InvalidData(format!("Invalid base64 data URI: {}", err)));
+    }
+    
+    let base_dir = base_dir.ok_or_else(|| GltfError::InvalidData(format!(
+        "Cannot resolve external buffer '{}' without a base directory", uri
+    )))?;
+    
+    Ok(fs::read(base_dir.join(percent_decode(uri)))?)
+}
+
+/// Decode `%XX` escapes in a relative URI
+fn percent_decode(uri: &str) -> String {
+    let bytes = uri.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+            if let Ok(value) = u8::from_str_radix(hex, 16) {
+                decoded.push(value);
+                i += 3;
+                continue;
+            }
+        }
+        
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{GltfBuilder, GltfError};
+    
+    /// GLB data for a box, with the JSON of its first buffer view changed by `modify`
+    fn box_glb(modify: impl FnOnce(&mut serde_json::Value)) -> Vec<u8> {
+        let mut builder = GltfBuilder::new();
+        let mesh = builder.create_box(1.0);
+        let node = builder.add_node(None, Some(mesh), None, None, None);
+        builder.add_scene(None, Some(vec![node]));
+        let bytes = builder.to_glb_bytes().unwrap();
+        
+        // Rewrite the JSON chunk, keeping the BIN chunk that follows it
+        let json_length = u32::from_le_bytes(bytes[12..16].try_into().unwrap()) as usize;
+        let mut json: serde_json::Value = serde_json::from_slice(&bytes[20..20 + json_length]).unwrap();
+        modify(&mut json["bufferViews"][0]);
+        let mut json = serde_json::to_vec(&json).unwrap();
+        while json.len() % 4 != 0 {
+            json.push(b' ');
+        }
+        let bin = &bytes[20 + json_length..];
+        
+        let mut glb = bytes[..8].to_vec();
+        glb.extend_from_slice(&((20 + json.len() + bin.len()) as u32).to_le_bytes());
+        glb.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        glb.extend_from_slice(&bytes[16..20]);
+        glb.extend_from_slice(&json);
+        glb.extend_from_slice(bin);
+        glb
+    }
+    
+    #[test]
+    fn round_trip_keeps_buffer_views() {
+        let bytes = box_glb(|_| {});
+        
+        let builder = GltfBuilder::from_glb_bytes(&bytes).unwrap();
+        
+        assert!(builder.validate().is_valid());
+    }
+    
+    #[test]
+    fn buffer_view_offset_overflow_is_an_error() {
+        let bytes = box_glb(|view| view["byteOffset"] = (usize::MAX - 2).into());
+        
+        assert!(matches!(GltfBuilder::from_glb_bytes(&bytes), Err(GltfError::InvalidData(_))));
+    }
+    
+    #[test]
+    fn buffer_view_beyond_buffer_is_an_error() {
+        let bytes = box_glb(|view| view["byteLength"] = 4096.into());
+        
+        assert!(matches!(GltfBuilder::from_glb_bytes(&bytes), Err(GltfError::InvalidData(_))));
+    }
+}
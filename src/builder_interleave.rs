@@ -0,0 +1,305 @@
+//! # Interleaved Vertex Layout Implementation
+//!
+//! This module implements the interleaved vertex layout used for export when enabled with
+//! `GltfBuilder::set_interleave_vertices`. The builder writes every vertex attribute to its own
+//! tightly packed buffer view; on export, the attributes of each primitive are combined into a
+//! single buffer view with a byte stride, so that the data of one vertex is contiguous.
+//!
+//! Every attribute starts on a 4-byte boundary within the vertex, as glTF requires, so
+//! attributes whose size is not a multiple of 4 are padded. Attributes whose accessor or buffer
+//! view is shared with other parts of the document are left in their own buffer view, as are
+//! attributes that would make the stride exceed the glTF limit of 252 bytes.
+
+use std::collections::HashMap;
+
+use crate::accessor::{component_size, type_components};
+use crate::builder::GltfBuilder;
+use crate::constants::buffer_view_target;
+use crate::error::{GltfError, Result};
+use crate::models::{Accessor, BufferView};
+
+/// Largest byte stride allowed by glTF
+const MAX_BYTE_STRIDE: usize = 252;
+
+/// Attributes of one primitive that share an interleaved buffer view
+struct InterleavedGroup {
+    /// Accessor index, size of one element and offset within the vertex of each attribute
+    attributes: Vec<(usize, usize, usize)>,
+    /// Number of vertices
+    count: usize,
+    /// Size of one vertex, including padding
+    stride: usize,
+}
+
+impl GltfBuilder {
+    /// Copy the builder with the vertex attributes of each primitive interleaved
+    ///
+    /// The copy has interleaving disabled and otherwise the same settings.
+    pub(crate) fn interleaved(&self) -> Result<GltfBuilder> {
+        let mut gltf = self.gltf.clone();
+        let groups = self.interleaved_groups();
+        
+        // The first buffer view of each group is replaced by the interleaved data, the others
+        // are dropped
+        let mut group_of_view = HashMap::new();
+        for (group_index, group) in groups.iter().enumerate() {
+            for (position, &(accessor, _, _)) in group.attributes.iter().enumerate() {
+                let view = self.gltf.accessors.as_ref().unwrap()[accessor].buffer_view.unwrap();
+                group_of_view.insert(view, (group_index, position == 0));
+            }
+        }
+        
+        // Rebuild the buffer with the interleaved views and remap the remaining views
+        let mut buffer_data = Vec::new();
+        let mut buffer_views = Vec::new();
+        let mut remap = Vec::new();
+        let mut group_views = vec![0; groups.len()];
+        
+        for (index, mut view) in gltf.buffer_views.take().unwrap_or_default().into_iter().enumerate() {
+            let group = group_of_view.get(&index).copied();
+            if let Some((_, false)) = group {
+                remap.push(None);
+                continue;
+            }
+            
+            while buffer_data.len() % 4 != 0 {
+                buffer_data.push(0);
+            }
+            view.byte_offset = buffer_data.len();
+            
+            if let Some((group_index, _)) = group {
+                let group = &groups[group_index];
+                buffer_data.extend(self.interleave(group)?);
+                view.byte_length = group.stride * group.count;
+                view.byte_stride = Some(group.stride);
+                view.target = Some(buffer_view_target::ARRAY_BUFFER);
+                group_views[group_index] = buffer_views.len();
+            } else {
+                buffer_data.extend_from_slice(self.buffer_view_bytes(index)?);
+            }
+            
+            remap.push(Some(buffer_views.len()));
+            buffer_views.push(view);
+        }
+        
+        let remap_view = |view_index: usize| remap.get(view_index)
+            .copied()
+            .flatten()
+            .ok_or(GltfError::InvalidIndex);
+        
+        // Interleaved attributes move into the view of their group, at their offset in the vertex
+        let mut interleaved_accessors = HashMap::new();
+        for (group, view) in groups.iter().zip(group_views) {
+            for &(accessor, _, offset) in &group.attributes {
+                interleaved_accessors.insert(accessor, (view, offset));
+            }
+        }
+        
+        if let Some(accessors) = &mut gltf.accessors {
+            for (index, accessor) in accessors.iter_mut().enumerate() {
+                if let Some(&(view, offset)) = interleaved_accessors.get(&index) {
+                    accessor.buffer_view = Some(view);
+                    accessor.byte_offset = (offset > 0).then_some(offset);
+                } else if let Some(view_index) = accessor.buffer_view {
+                    accessor.buffer_view = Some(remap_view(view_index)?);
+                }
+                
+                if let Some(sparse) = &mut accessor.sparse {
+                    sparse.indices.buffer_view = remap_view(sparse.indices.buffer_view)?;
+                    sparse.values.buffer_view = remap_view(sparse.values.buffer_view)?;
+                }
+            }
+        }
+        
+        if let Some(images) = &mut gltf.images {
+            for image in images.iter_mut() {
+                if let Some(view_index) = image.buffer_view {
+                    image.buffer_view = Some(remap_view(view_index)?);
+                }
+            }
+        }
+        
+        gltf.buffer_views = Some(buffer_views);
+        if let Some(buffers) = &mut gltf.buffers {
+            if !buffers.is_empty() {
+                buffers[0].byte_length = buffer_data.len();
+            }
+        }
+        
+        let mut options = self.options.clone();
+        options.interleave_vertices = false;
+        
+        Ok(GltfBuilder {
+            gltf,
+            buffer_data,
+            options,
+        })
+    }
+    
+    /// Find the attributes of each primitive that can be interleaved
+    fn interleaved_groups(&self) -> Vec<InterleavedGroup> {
+        let accessors = self.gltf.accessors.as_deref().unwrap_or_default();
+        let views = self.gltf.buffer_views.as_deref().unwrap_or_default();
+        
+        // Count the references to every accessor and buffer view, to leave shared data alone
+        let mut accessor_uses = vec![0usize; accessors.len()];
+        let mut count_accessor = |accessor: usize| {
+            if let Some(uses) = accessor_uses.get_mut(accessor) {
+                *uses += 1;
+            }
+        };
+        for primitive in self.gltf.meshes.iter().flatten().flat_map(|mesh| &mesh.primitives) {
+            primitive.attributes.values().copied().for_each(&mut count_accessor);
+            primitive.indices.into_iter().for_each(&mut count_accessor);
+            primitive.targets.iter().flatten().flat_map(|target| target.values().copied()).for_each(&mut count_accessor);
+        }
+        for sampler in self.gltf.animations.iter().flatten().flat_map(|animation| animation.samplers.iter().flatten()) {
+            count_accessor(sampler.input);
+            count_accessor(sampler.output);
+        }
+        for skin in self.gltf.skins.iter().flatten() {
+            skin.inverse_bind_matrices.into_iter().for_each(&mut count_accessor);
+        }
+        
+        let mut view_uses = vec![0usize; views.len()];
+        let mut count_view = |view: usize| {
+            if let Some(uses) = view_uses.get_mut(view) {
+                *uses += 1;
+            }
+        };
+        for accessor in accessors {
+            accessor.buffer_view.into_iter().for_each(&mut count_view);
+            if let Some(sparse) = &accessor.sparse {
+                count_view(sparse.indices.buffer_view);
+                count_view(sparse.values.buffer_view);
+            }
+        }
+        for image in self.gltf.images.iter().flatten() {
+            image.buffer_view.into_iter().for_each(&mut count_view);
+        }
+        
+        let mut groups = Vec::new();
+        for primitive in self.gltf.meshes.iter().flatten().flat_map(|mesh| &mesh.primitives) {
+            let mut names: Vec<&String> = primitive.attributes.keys().collect();
+            names.sort_by_key(|name| (attribute_order(name), name.as_str()));
+            
+            let mut group = InterleavedGroup { attributes: Vec::new(), count: 0, stride: 0 };
+            for name in names {
+                let index = primitive.attributes[name];
+                let Some(accessor) = accessors.get(index) else {
+                    continue;
+                };
+                let Some(element_size) = element_size(accessor) else {
+                    continue;
+                };
+                let Some(view) = accessor.buffer_view.and_then(|view| views.get(view).map(|data| (view, data))) else {
+                    continue;
+                };
+                
+                if !is_separate(accessor, view.1, element_size)
+                    || accessor_uses[index] != 1
+                    || view_uses[view.0] != 1
+                    || (!group.attributes.is_empty() && accessor.count != group.count) {
+                    continue;
+                }
+                
+                let padded_size = element_size.next_multiple_of(4);
+                if group.stride + padded_size > MAX_BYTE_STRIDE {
+                    continue;
+                }
+                
+                group.attributes.push((index, element_size, group.stride));
+                group.count = accessor.count;
+                group.stride += padded_size;
+            }
+            
+            if group.attributes.len() > 1 {
+                groups.push(group);
+            }
+        }
+        
+        groups
+    }
+    
+    /// Build the interleaved vertex data of a group
+    fn interleave(&self, group: &InterleavedGroup) -> Result<Vec<u8>> {
+        let accessors = self.gltf.accessors.as_deref().unwrap_or_default();
+        let views = self.gltf.buffer_views.as_deref().unwrap_or_default();
+        
+        let mut data = vec![0; group.stride * group.count];
+        for &(accessor, element_size, offset) in &group.attributes {
+            let view_index = accessors[accessor].buffer_view.unwrap();
+            let source_stride = views[view_index].byte_stride.unwrap_or(element_size);
+            let source = self.buffer_view_bytes(view_index)?;
+            
+            for vertex in 0..group.count {
+                let element = source.get(vertex * source_stride..vertex * source_stride + element_size)
+                    .ok_or_else(|| GltfError::InvalidData(format!(
+                        "Accessor {} exceeds buffer view {}", accessor, view_index
+                    )))?;
+                let target = vertex * group.stride + offset;
+                data[target..target + element_size].copy_from_slice(element);
+            }
+        }
+        
+        Ok(data)
+    }
+}
+
+/// Position of an attribute within an interleaved vertex
+fn attribute_order(name: &str) -> usize {
+    ["POSITION", "NORMAL", "TANGENT", "TEXCOORD", "COLOR", "JOINTS", "WEIGHTS"].iter()
+        .position(|prefix| name.starts_with(prefix))
+        .unwrap_or(usize::MAX)
+}
+
+/// Size in bytes of one vertex attribute element, or `None` for types that are not vertex data
+fn element_size(accessor: &Accessor) -> Option<usize> {
+    let components = type_components(&accessor.type_).ok()?;
+    let value_size = component_size(accessor.component_type).ok()?;
+    (components <= 4).then_some(components * value_size)
+}
+
+/// Check that an accessor is plain data at the start of a buffer view of its own layout
+fn is_separate(accessor: &Accessor, view: &BufferView, element_size: usize) -> bool {
+    accessor.sparse.is_none()
+        && accessor.byte_offset.unwrap_or(0) == 0
+        && view.byte_stride.unwrap_or(element_size) >= element_size
+        && view.target != Some(buffer_view_target::ELEMENT_ARRAY_BUFFER)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{GltfBuilder, VertexColors};
+    use crate::primitives::generate_plane;
+    
+    #[test]
+    fn padded_byte_colors_are_interleaved() {
+        let mut builder = GltfBuilder::new();
+        let (positions, indices, normals, _) = generate_plane(1.0, 1.0, 1, 1);
+        let colors: Vec<[u8; 3]> = (0..positions.len() as u8).map(|i| [i, 100 + i, 200 + i]).collect();
+        let mesh = builder.create_custom_mesh_with_colors(None, &positions, &indices, Some(normals.clone()), None,
+                                                          VertexColors::RgbU8(colors.clone()), None).unwrap();
+        let node = builder.add_node(None, Some(mesh), None, None, None);
+        builder.add_scene(None, Some(vec![node]));
+        
+        let interleaved = builder.interleaved().unwrap();
+        
+        let primitive = &interleaved.gltf.meshes.as_ref().unwrap()[0].primitives[0];
+        let accessors = interleaved.gltf.accessors.as_ref().unwrap();
+        let color = &accessors[primitive.attributes["COLOR_0"]];
+        let view = &interleaved.gltf.buffer_views.as_ref().unwrap()[color.buffer_view.unwrap()];
+        
+        // Position and normal take 12 bytes each, the 3-byte color is padded to 4
+        assert_eq!(view.byte_stride, Some(28));
+        assert_eq!(color.byte_offset, Some(24));
+        assert_eq!(accessors[primitive.attributes["POSITION"]].buffer_view, color.buffer_view);
+        
+        let reader = interleaved.accessor_reader();
+        let read_colors = reader.read_u32(primitive.attributes["COLOR_0"]).unwrap();
+        let expected: Vec<u32> = colors.iter().flatten().map(|&c| c as u32).collect();
+        assert_eq!(read_colors, expected);
+        assert_eq!(reader.read_vec3(primitive.attributes["NORMAL"]).unwrap(), normals);
+        assert!(interleaved.validate().is_valid(), "{}", interleaved.validate());
+    }
+}
@@ -0,0 +1,189 @@
+//! # Light Implementation
+//!
+//! This module implements adding `KHR_lights_punctual` lights to the glTF document held by a
+//! `GltfBuilder` and attaching them to nodes. The light models themselves are created by the
+//! functions in the `light` module.
+//!
+//! The extension stores its lights in the document's top-level `extensions` object, under
+//! `KHR_lights_punctual.lights`, and nodes reference them by index through their own
+//! `extensions`. Adding a light registers the extension in `extensionsUsed`.
+
+use crate::builder::GltfBuilder;
+use crate::error::{GltfError, Result};
+use crate::handle::{LightId, NodeId};
+use crate::light;
+use crate::models::{Light, NodeExtensions, NodeLight};
+
+const EXTENSION_NAME: &str = "KHR_lights_punctual";
+
+impl GltfBuilder {
+    /// Add a directional light to the glTF document
+    ///
+    /// # Parameters
+    /// * `name` - Optional name for the light
+    /// * `color` - RGB color of the light in linear space
+    /// * `intensity` - Illuminance in lux
+    ///
+    /// # Returns
+    /// The index of the created light, or an error if the document's lights are malformed
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::GltfBuilder;
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// let floor = builder.create_plane(10.0, 10.0, 1, 1, None);
+    /// let floor_node = builder.add_node(None, Some(floor), None, None, None);
+    ///
+    /// // Sunlight coming in at an angle
+    /// let sun = builder.add_directional_light(Some("Sun".to_string()), [1.0, 0.95, 0.8], 3.0).unwrap();
+    /// let sun_node = builder.add_node(Some("Sun".to_string()), None, Some([4.0, 10.0, 6.0]), None, None);
+    /// builder.attach_light(sun_node, sun).unwrap();
+    /// builder.look_at(sun_node, [0.0, 0.0, 0.0], None).unwrap();
+    ///
+    /// // A lamp above the floor
+    /// let lamp = builder.add_point_light(Some("Lamp".to_string()), [1.0, 0.8, 0.6], 20.0, Some(10.0)).unwrap();
+    /// let lamp_node = builder.add_node(Some("Lamp".to_string()), None, Some([0.0, 2.5, 0.0]), None, None);
+    /// builder.attach_light(lamp_node, lamp).unwrap();
+    ///
+    /// builder.add_scene(None, Some(vec![floor_node, sun_node, lamp_node]));
+    ///
+    /// assert!(builder.gltf.extensions_used.as_ref().unwrap().contains(&"KHR_lights_punctual".to_string()));
+    /// ```
+    pub fn add_directional_light(&mut self, name: Option<String>, color: [f32; 3],
+                                 intensity: f32) -> Result<LightId> {
+        let light = light::create_directional_light(name, color, intensity);
+        self.add_light(light)
+    }
+    
+    /// Add a point light to the glTF document
+    ///
+    /// # Parameters
+    /// * `name` - Optional name for the light
+    /// * `color` - RGB color of the light in linear space
+    /// * `intensity` - Luminous intensity in candela
+    /// * `range` - Optional distance after which the light has no effect; omit for infinite range
+    ///
+    /// # Returns
+    /// The index of the created light, or an error if the document's lights are malformed
+    pub fn add_point_light(&mut self, name: Option<String>, color: [f32; 3], intensity: f32,
+                           range: Option<f32>) -> Result<LightId> {
+        let light = light::create_point_light(name, color, intensity, range);
+        self.add_light(light)
+    }
+    
+    /// Add a spot light to the glTF document
+    ///
+    /// # Parameters
+    /// * `name` - Optional name for the light
+    /// * `color` - RGB color of the light in linear space
+    /// * `intensity` - Luminous intensity in candela
+    /// * `range` - Optional distance after which the light has no effect; omit for infinite range
+    /// * `inner_cone_angle` - Angle in radians from the center of the cone where falloff begins
+    /// * `outer_cone_angle` - Angle in radians from the center of the cone where falloff ends
+    ///
+    /// # Returns
+    /// The index of the created light, or an error unless
+    /// `0 <= inner_cone_angle < outer_cone_angle <= PI / 2` or if the document's lights are malformed
+    pub fn add_spot_light(&mut self, name: Option<String>, color: [f32; 3], intensity: f32,
+                          range: Option<f32>, inner_cone_angle: f32,
+                          outer_cone_angle: f32) -> Result<LightId> {
+        let light = light::create_spot_light(name, color, intensity, range, inner_cone_angle, outer_cone_angle)?;
+        self.add_light(light)
+    }
+    
+    /// Add a light to the glTF document and register the KHR_lights_punctual extension
+    ///
+    /// # Returns
+    /// The index of the created light, or an error if the document's `extensions` value or its
+    /// `KHR_lights_punctual` entry is malformed, in which case the document is not changed
+    pub fn add_light(&mut self, light: Light) -> Result<LightId> {
+        let extensions = self.gltf.extensions.get_or_insert_with(|| serde_json::json!({}))
+            .as_object_mut()
+            .ok_or_else(|| GltfError::InvalidData("Document extensions are not a JSON object".to_string()))?;
+        let lights_punctual = extensions.entry(EXTENSION_NAME)
+            .or_insert_with(|| serde_json::json!({ "lights": [] }));
+        let lights = lights_punctual.get_mut("lights")
+            .and_then(|lights| lights.as_array_mut())
+            .ok_or_else(|| GltfError::InvalidData(format!("{} has no lights array", EXTENSION_NAME)))?;
+        lights.push(serde_json::to_value(light)?);
+        let light = LightId::from_index(lights.len() - 1);
+        
+        // Register the extension in the glTF document
+        let extensions_used = self.gltf.extensions_used.get_or_insert_with(Vec::new);
+        if !extensions_used.iter().any(|extension| extension == EXTENSION_NAME) {
+            extensions_used.push(EXTENSION_NAME.to_string());
+        }
+        
+        Ok(light)
+    }
+    
+    /// Get the lights defined in the glTF document
+    ///
+    /// # Returns
+    /// The lights of the KHR_lights_punctual extension, or an error if they are malformed
+    pub fn lights(&self) -> Result<Vec<Light>> {
+        match self.gltf.extensions.as_ref()
+            .and_then(|extensions| extensions.get(EXTENSION_NAME))
+            .and_then(|lights_punctual| lights_punctual.get("lights")) {
+            Some(lights) => Ok(serde_json::from_value(lights.clone())?),
+            None => Ok(Vec::new()),
+        }
+    }
+    
+    /// Attach a light to a node, which then defines the light's position and orientation
+    pub fn attach_light(&mut self, node: NodeId, light: LightId) -> Result<()> {
+        if light.index() >= self.lights()?.len() {
+            return Err(GltfError::InvalidIndex);
+        }
+        
+        let node = self.gltf.nodes.as_mut()
+            .and_then(|nodes| nodes.get_mut(node.index()))
+            .ok_or(GltfError::InvalidIndex)?;
+        node.extensions.get_or_insert_with(NodeExtensions::default).lights_punctual = Some(NodeLight {
+            light: light.index(),
+        });
+        
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{GltfBuilder, GltfError};
+    
+    #[test]
+    fn malformed_extensions_are_an_error() {
+        let mut builder = GltfBuilder::new();
+        builder.gltf.extensions = Some(serde_json::json!([]));
+        
+        let result = builder.add_point_light(None, [1.0; 3], 1.0, None);
+        
+        assert!(matches!(result, Err(GltfError::InvalidData(_))));
+        assert_eq!(builder.gltf.extensions, Some(serde_json::json!([])));
+        assert!(builder.gltf.extensions_used.is_none());
+    }
+    
+    #[test]
+    fn lights_extension_without_lights_is_an_error() {
+        let mut builder = GltfBuilder::new();
+        let extensions = serde_json::json!({ "KHR_lights_punctual": { "lights": 3 } });
+        builder.gltf.extensions = Some(extensions.clone());
+        
+        let result = builder.add_directional_light(None, [1.0; 3], 1.0);
+        
+        assert!(matches!(result, Err(GltfError::InvalidData(_))));
+        assert_eq!(builder.gltf.extensions, Some(extensions));
+    }
+    
+    #[test]
+    fn lights_are_appended_to_existing_lights() {
+        let mut builder = GltfBuilder::new();
+        let first = builder.add_directional_light(None, [1.0; 3], 1.0).unwrap();
+        let second = builder.add_point_light(None, [1.0; 3], 1.0, None).unwrap();
+        
+        assert_eq!((first.index(), second.index()), (0, 1));
+        assert_eq!(builder.lights().unwrap().len(), 2);
+        assert_eq!(builder.gltf.extensions_used, Some(vec!["KHR_lights_punctual".to_string()]));
+    }
+}
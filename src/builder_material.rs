@@ -1,4 +1,5 @@
 use crate::builder::GltfBuilder;
+use crate::handle::MaterialId;
 use crate::material;
 
 impl GltfBuilder {
@@ -54,13 +55,13 @@ impl GltfBuilder {
     /// );
     /// 
     /// // Use the material with a mesh
-    /// let cube = builder.create_box(1.0, Some(blue_metal));
+    /// let cube = builder.create_box_with_material(1.0, Some(blue_metal));
     /// ```
     pub fn add_material(&mut self, name: Option<String>, 
                         base_color: Option<[f32; 4]>,
                         metallic_factor: Option<f32>,
                         roughness_factor: Option<f32>,
-                        double_sided: Option<bool>) -> usize {
+                        double_sided: Option<bool>) -> MaterialId {
         let mut builder = material::MaterialBuilder::new(name);
         
         if let Some(color) = base_color {
@@ -81,44 +82,23 @@ impl GltfBuilder {
         
         let material = builder.build();
         
-        if let Some(materials) = &mut self.gltf.materials {
-            let index = materials.len();
-            materials.push(material);
-            index
-        } else {
-            self.gltf.materials = Some(vec![material]);
-            0
-        }
+        self.add_material_direct(material)
     }
 
     /// Create a basic material with the specified color
-    pub fn create_basic_material(&mut self, name: Option<String>, color: [f32; 4]) -> usize {
+    pub fn create_basic_material(&mut self, name: Option<String>, color: [f32; 4]) -> MaterialId {
         let material = material::create_basic_material(name, color);
         
-        if let Some(materials) = &mut self.gltf.materials {
-            let index = materials.len();
-            materials.push(material);
-            index
-        } else {
-            self.gltf.materials = Some(vec![material]);
-            0
-        }
+        self.add_material_direct(material)
     }
 
     /// Create a metallic material
     pub fn create_metallic_material(&mut self, name: Option<String>, 
                                    color: [f32; 4], 
                                    metallic: f32,
-                                   roughness: f32) -> usize {
+                                   roughness: f32) -> MaterialId {
         let material = material::create_metallic_material(name, color, metallic, roughness);
         
-        if let Some(materials) = &mut self.gltf.materials {
-            let index = materials.len();
-            materials.push(material);
-            index
-        } else {
-            self.gltf.materials = Some(vec![material]);
-            0
-        }
+        self.add_material_direct(material)
     }
 }
@@ -1,4 +1,5 @@
 use crate::builder::GltfBuilder;
+use crate::handle::{MaterialId, TextureId};
 use crate::models::{MaterialExtensions, PbrSpecularGlossiness, TextureInfo};
 
 impl GltfBuilder {
@@ -26,22 +27,23 @@ impl GltfBuilder {
     /// # Returns
     /// 
     /// The index of the created material in the glTF document
+    #[allow(clippy::too_many_arguments)]
     pub fn add_specular_glossiness_material(
         &mut self,
         name: Option<String>,
         diffuse_factor: Option<[f32; 4]>,
-        diffuse_texture: Option<usize>,
+        diffuse_texture: Option<TextureId>,
         specular_factor: Option<[f32; 3]>,
         glossiness_factor: Option<f32>,
-        specular_glossiness_texture: Option<usize>,
-        normal_texture: Option<usize>,
-        occlusion_texture: Option<usize>,
-        emissive_texture: Option<usize>,
+        specular_glossiness_texture: Option<TextureId>,
+        normal_texture: Option<TextureId>,
+        occlusion_texture: Option<TextureId>,
+        emissive_texture: Option<TextureId>,
         emissive_factor: Option<[f32; 3]>,
         alpha_mode: Option<String>,
         alpha_cutoff: Option<f32>,
         double_sided: Option<bool>,
-    ) -> usize {
+    ) -> MaterialId {
         // Register the extension in the glTF document
         if self.gltf.extensions_used.is_none() {
             self.gltf.extensions_used = Some(vec!["KHR_materials_pbrSpecularGlossiness".to_string()]);
@@ -52,8 +54,10 @@ impl GltfBuilder {
         }
         
         // Create the material
-        let mut material = crate::models::Material::default();
-        material.name = name;
+        let mut material = crate::models::Material {
+            name,
+            ..Default::default()
+        };
         
         // Set optional properties
         if alpha_mode.is_some() {
@@ -75,7 +79,7 @@ impl GltfBuilder {
         // Create texture infos if needed
         if let Some(normal_texture_index) = normal_texture {
             material.normal_texture = Some(crate::models::NormalTextureInfo {
-                index: normal_texture_index,
+                index: normal_texture_index.index(),
                 tex_coord: Some(0),
                 scale: Some(1.0),
             });
@@ -83,7 +87,7 @@ impl GltfBuilder {
         
         if let Some(occlusion_texture_index) = occlusion_texture {
             material.occlusion_texture = Some(crate::models::OcclusionTextureInfo {
-                index: occlusion_texture_index,
+                index: occlusion_texture_index.index(),
                 tex_coord: Some(0),
                 strength: Some(1.0),
             });
@@ -91,28 +95,29 @@ impl GltfBuilder {
         
         if let Some(emissive_texture_index) = emissive_texture {
             material.emissive_texture = Some(TextureInfo {
-                index: emissive_texture_index,
+                index: emissive_texture_index.index(),
                 tex_coord: Some(0),
             });
         }
         
         // Create specular-glossiness extension
-        let mut pbr_specular_glossiness = PbrSpecularGlossiness::default();
-        
-        pbr_specular_glossiness.diffuse_factor = diffuse_factor;
-        pbr_specular_glossiness.specular_factor = specular_factor;
-        pbr_specular_glossiness.glossiness_factor = glossiness_factor;
+        let mut pbr_specular_glossiness = PbrSpecularGlossiness {
+            diffuse_factor,
+            specular_factor,
+            glossiness_factor,
+            ..Default::default()
+        };
         
         if let Some(diffuse_texture_index) = diffuse_texture {
             pbr_specular_glossiness.diffuse_texture = Some(TextureInfo {
-                index: diffuse_texture_index,
+                index: diffuse_texture_index.index(),
                 tex_coord: Some(0),
             });
         }
         
         if let Some(specular_glossiness_texture_index) = specular_glossiness_texture {
             pbr_specular_glossiness.specular_glossiness_texture = Some(TextureInfo {
-                index: specular_glossiness_texture_index,
+                index: specular_glossiness_texture_index.index(),
                 tex_coord: Some(0),
             });
         }
@@ -146,7 +151,7 @@ impl GltfBuilder {
         diffuse_color: [f32; 4],
         specular_color: [f32; 3],
         glossiness: f32,
-    ) -> usize {
+    ) -> MaterialId {
         self.add_specular_glossiness_material(
             name,
             Some(diffuse_color),
@@ -167,14 +172,14 @@ impl GltfBuilder {
     /// Add a material directly to the glTF document
     ///
     /// This is an internal helper method used by the material creation methods.
-    fn add_material_direct(&mut self, material: crate::models::Material) -> usize {
+    pub(crate) fn add_material_direct(&mut self, material: crate::models::Material) -> MaterialId {
         if let Some(materials) = &mut self.gltf.materials {
             let index = materials.len();
             materials.push(material);
-            index
+            MaterialId::from_index(index)
         } else {
             self.gltf.materials = Some(vec![material]);
-            0
+            MaterialId::from_index(0)
         }
     }
 }
@@ -0,0 +1,207 @@
+//! # Multi-Primitive Mesh Implementation
+//!
+//! This module provides `MeshPrimitiveBuilder`, which assembles a single glTF mesh from several
+//! primitives, each with its own geometry and material. This is how models with per-region
+//! materials (terrain biomes, building facades, ...) are normally structured in glTF, instead of
+//! one mesh and node per material.
+
+use crate::builder::GltfBuilder;
+use crate::builder_primitives::Triangle;
+use crate::compat::{Point3, Vector2, Vector3};
+use crate::error::{GltfError, Result};
+use crate::handle::{MaterialId, MeshId};
+use crate::models::Primitive;
+
+/// Builder for a mesh made of several primitives
+///
+/// Created with `GltfBuilder::begin_mesh`. Geometry is uploaded to the buffer as each
+/// primitive is added; the mesh itself is added to the document by `build`. Primitives that
+/// only differ in their triangles and material, like the regions of a terrain, can share one
+/// set of vertices with `add_primitives_with_shared_vertices`.
+///
+/// # Example
+/// ```
+/// use mesh_tools::GltfBuilder;
+/// use mesh_tools::primitives::{generate_plane, generate_sphere};
+///
+/// let mut builder = GltfBuilder::new();
+/// let green = builder.create_basic_material(Some("Green".to_string()), [0.0, 1.0, 0.0, 1.0]);
+/// let blue = builder.create_basic_material(Some("Blue".to_string()), [0.0, 0.0, 1.0, 1.0]);
+///
+/// let (plane_positions, plane_indices, plane_normals, plane_uvs) = generate_plane(2.0, 2.0, 1, 1);
+/// let (sphere_positions, sphere_indices, sphere_normals, sphere_uvs) = generate_sphere(0.5, 16, 8);
+///
+/// let mut mesh = builder.begin_mesh(Some("Marker".to_string()));
+/// mesh.add_primitive(&plane_positions, &plane_indices, Some(plane_normals), Some(vec![plane_uvs]), Some(green))
+///     .add_primitive(&sphere_positions, &sphere_indices, Some(sphere_normals), Some(vec![sphere_uvs]), Some(blue));
+/// let marker = mesh.build().unwrap();
+///
+/// assert_eq!(builder.gltf.meshes.as_ref().unwrap()[marker.index()].primitives.len(), 2);
+/// ```
+pub struct MeshPrimitiveBuilder<'a> {
+    builder: &'a mut GltfBuilder,
+    name: Option<String>,
+    primitives: Vec<Primitive>,
+}
+
+impl<'a> MeshPrimitiveBuilder<'a> {
+    /// Add a primitive with its own geometry and material
+    ///
+    /// # Parameters
+    /// * `positions` - Vertex positions
+    /// * `indices` - List of triangles, each containing three vertex indices
+    /// * `normals` - Optional vertex normals
+    /// * `texcoords` - Optional array of UV coordinate sets. The first set becomes TEXCOORD_0,
+    ///   the second TEXCOORD_1, etc.
+    /// * `material` - Optional material to use for the primitive
+    pub fn add_primitive(&mut self,
+                         positions: &[Point3<f32>],
+                         indices: &[Triangle],
+                         normals: Option<Vec<Vector3<f32>>>,
+                         texcoords: Option<Vec<Vec<Vector2<f32>>>>,
+                         material: Option<MaterialId>) -> &mut Self {
+        let primitive = self.builder.create_custom_primitive(positions, indices, normals, texcoords, material);
+        self.primitives.push(primitive);
+        self
+    }
+    
+    /// Add several primitives that share their vertices, each with its own triangles and material
+    ///
+    /// The vertex attributes are uploaded once and referenced by every primitive, so only the
+    /// indices are stored per primitive.
+    ///
+    /// # Parameters
+    /// * `positions` - Vertex positions
+    /// * `normals` - Optional vertex normals
+    /// * `texcoords` - Optional array of UV coordinate sets. The first set becomes TEXCOORD_0,
+    ///   the second TEXCOORD_1, etc.
+    /// * `parts` - The triangles and optional material of each primitive
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::GltfBuilder;
+    /// use mesh_tools::primitives::generate_plane;
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// let grass = builder.create_basic_material(Some("Grass".to_string()), [0.2, 0.8, 0.2, 1.0]);
+    /// let rock = builder.create_basic_material(Some("Rock".to_string()), [0.5, 0.5, 0.5, 1.0]);
+    ///
+    /// let (positions, indices, normals, uvs) = generate_plane(2.0, 2.0, 2, 2);
+    /// let (grass_triangles, rock_triangles) = indices.split_at(4);
+    ///
+    /// let mut mesh = builder.begin_mesh(Some("Ground".to_string()));
+    /// mesh.add_primitives_with_shared_vertices(&positions, Some(normals), Some(vec![uvs]), &[
+    ///     (grass_triangles.to_vec(), Some(grass)),
+    ///     (rock_triangles.to_vec(), Some(rock)),
+    /// ]);
+    /// let ground = mesh.build().unwrap();
+    ///
+    /// let primitives = &builder.gltf.meshes.as_ref().unwrap()[ground.index()].primitives;
+    /// assert_eq!(primitives[0].attributes, primitives[1].attributes);
+    /// ```
+    pub fn add_primitives_with_shared_vertices(&mut self,
+                                               positions: &[Point3<f32>],
+                                               normals: Option<Vec<Vector3<f32>>>,
+                                               texcoords: Option<Vec<Vec<Vector2<f32>>>>,
+                                               parts: &[(Vec<Triangle>, Option<MaterialId>)]) -> &mut Self {
+        let primitives = self.builder.create_shared_vertex_primitives(positions, normals, texcoords, parts);
+        self.primitives.extend(primitives);
+        self
+    }
+    
+    /// Get the number of primitives added so far
+    pub fn primitive_count(&self) -> usize {
+        self.primitives.len()
+    }
+    
+    /// Add the mesh to the document
+    ///
+    /// glTF requires every mesh to have at least one primitive.
+    ///
+    /// # Returns
+    /// The handle of the created mesh, or an error if no primitive was added
+    pub fn build(self) -> Result<MeshId> {
+        if self.primitives.is_empty() {
+            return Err(GltfError::InvalidData("A mesh needs at least one primitive".to_string()));
+        }
+        
+        Ok(self.builder.add_mesh(self.name, self.primitives))
+    }
+}
+
+impl GltfBuilder {
+    /// Start building a mesh made of several primitives
+    ///
+    /// See `MeshPrimitiveBuilder` for an example.
+    ///
+    /// # Parameters
+    /// * `name` - Optional name for the mesh
+    pub fn begin_mesh(&mut self, name: Option<String>) -> MeshPrimitiveBuilder<'_> {
+        MeshPrimitiveBuilder {
+            builder: self,
+            name,
+            primitives: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GltfBuilder;
+    use crate::primitives::generate_plane;
+    
+    #[test]
+    fn mesh_without_primitives_is_an_error() {
+        let mut builder = GltfBuilder::new();
+        
+        assert!(builder.begin_mesh(None).build().is_err());
+        assert!(builder.gltf.meshes.as_ref().is_none_or(|meshes| meshes.is_empty()));
+    }
+    
+    #[test]
+    fn shared_vertices_are_uploaded_once() {
+        let mut builder = GltfBuilder::new();
+        let (positions, indices, normals, uvs) = generate_plane(1.0, 1.0, 2, 2);
+        let (first, second) = indices.split_at(3);
+        
+        let mut mesh = builder.begin_mesh(None);
+        mesh.add_primitives_with_shared_vertices(&positions, Some(normals), Some(vec![uvs]), &[
+            (first.to_vec(), None),
+            (second.to_vec(), None),
+        ]);
+        let mesh = mesh.build().unwrap();
+        
+        let primitives = &builder.gltf.meshes.as_ref().unwrap()[mesh.index()].primitives;
+        assert_eq!(primitives.len(), 2);
+        assert_eq!(primitives[0].attributes, primitives[1].attributes);
+        assert_ne!(primitives[0].indices, primitives[1].indices);
+        
+        // Positions, normals and UVs, plus one index accessor per primitive
+        assert_eq!(builder.gltf.accessors.as_ref().unwrap().len(), 5);
+        let counts: Vec<usize> = primitives.iter()
+            .map(|primitive| builder.gltf.accessors.as_ref().unwrap()[primitive.indices.unwrap()].count)
+            .collect();
+        assert_eq!(counts, vec![9, (indices.len() - 3) * 3]);
+    }
+    
+    #[test]
+    fn generated_normals_keep_the_triangles_of_each_primitive() {
+        let mut builder = GltfBuilder::new();
+        builder.set_generate_normals(Some(crate::geometry::NormalMode::Flat));
+        let (positions, indices, _, _) = generate_plane(1.0, 1.0, 1, 1);
+        
+        let mut mesh = builder.begin_mesh(None);
+        mesh.add_primitives_with_shared_vertices(&positions, None, None, &[
+            (indices[..1].to_vec(), None),
+            (indices[1..].to_vec(), None),
+        ]);
+        let mesh = mesh.build().unwrap();
+        
+        let primitives = &builder.gltf.meshes.as_ref().unwrap()[mesh.index()].primitives;
+        assert!(primitives[0].attributes.contains_key("NORMAL"));
+        assert_eq!(primitives[0].attributes, primitives[1].attributes);
+        for primitive in primitives {
+            assert_eq!(builder.gltf.accessors.as_ref().unwrap()[primitive.indices.unwrap()].count, 3);
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! # Morph Target Implementation
+//!
+//! This module implements morph targets (blend shapes) for meshes in the glTF document held by
+//! a `GltfBuilder`. A morph target stores per-vertex position, normal and tangent deltas; the
+//! rendered vertex is the base vertex plus the deltas of every target scaled by its weight.
+//!
+//! Every primitive of a mesh must have the same number of targets, so a target is added to all
+//! primitives of a mesh at once. The mesh stores the default weight of each target, and target
+//! names are stored in the mesh's `extras` as `targetNames`, the convention understood by
+//! Blender, three.js and Babylon.js. Weights are animated with `create_weights_animation`.
+
+use std::collections::HashMap;
+
+use crate::builder::GltfBuilder;
+use crate::compat::Vector3;
+use crate::constants::accessor_type;
+use crate::error::{GltfError, Result};
+use crate::handle::MeshId;
+
+/// Per-vertex deltas of one morph target for a single primitive
+///
+/// Each list must have one entry per vertex of the primitive.
+#[derive(Debug, Clone)]
+pub struct MorphTarget {
+    /// Position displacements
+    pub positions: Vec<Vector3<f32>>,
+    /// Optional normal displacements
+    pub normals: Option<Vec<Vector3<f32>>>,
+    /// Optional tangent displacements (XYZ only; tangent handedness cannot be morphed)
+    pub tangents: Option<Vec<Vector3<f32>>>,
+}
+
+impl MorphTarget {
+    /// Create a morph target that only displaces positions
+    pub fn new(positions: Vec<Vector3<f32>>) -> Self {
+        Self {
+            positions,
+            normals: None,
+            tangents: None,
+        }
+    }
+    
+    /// Add normal displacements to the morph target
+    pub fn with_normals(mut self, normals: Vec<Vector3<f32>>) -> Self {
+        self.normals = Some(normals);
+        self
+    }
+    
+    /// Add tangent displacements to the morph target
+    pub fn with_tangents(mut self, tangents: Vec<Vector3<f32>>) -> Self {
+        self.tangents = Some(tangents);
+        self
+    }
+}
+
+impl GltfBuilder {
+    /// Add a morph target to every primitive of a mesh
+    ///
+    /// # Parameters
+    /// * `mesh` - The mesh to add the target to
+    /// * `name` - Optional name for the target, stored in the mesh's `extras.targetNames`
+    /// * `default_weight` - Weight of the target when the mesh is not animated
+    /// * `primitive_targets` - The deltas for each primitive of the mesh, in primitive order
+    ///
+    /// # Returns
+    /// The index of the created target, or an error if the number of deltas does not match
+    /// the primitives and their vertices
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::{GltfBuilder, InterpolationType, MorphTarget};
+    /// use mesh_tools::compat::vector3;
+    /// use mesh_tools::primitives::generate_sphere;
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// let (positions, indices, normals, uvs) = generate_sphere(1.0, 16, 8);
+    /// let mesh = builder.create_custom_mesh(Some("Blob".to_string()), &positions, &indices,
+    ///                                       Some(normals), Some(vec![uvs]), None);
+    ///
+    /// // Stretch the sphere vertically and flatten it
+    /// let stretch = positions.iter().map(|p| vector3::new(0.0, p.y * 0.5, 0.0)).collect();
+    /// let flatten = positions.iter().map(|p| vector3::new(0.0, -p.y * 0.5, 0.0)).collect();
+    /// builder.add_morph_target(mesh, Some("Stretch".to_string()), 0.0, vec![MorphTarget::new(stretch)]).unwrap();
+    /// builder.add_morph_target(mesh, Some("Flatten".to_string()), 0.0, vec![MorphTarget::new(flatten)]).unwrap();
+    ///
+    /// let node = builder.add_node(Some("Blob".to_string()), Some(mesh), None, None, None);
+    /// builder.add_scene(None, Some(vec![node]));
+    ///
+    /// // Stretch, then flatten
+    /// let animation = builder.add_animation(Some("Squash".to_string()));
+    /// builder.create_weights_animation(
+    ///     animation,
+    ///     node,
+    ///     vec![0.0, 0.5, 1.0],
+    ///     vec![vec![0.0, 0.0], vec![1.0, 0.0], vec![0.0, 1.0]],
+    ///     InterpolationType::Linear,
+    /// ).unwrap();
+    /// ```
+    pub fn add_morph_target(&mut self, mesh: MeshId, name: Option<String>, default_weight: f32,
+                            primitive_targets: Vec<MorphTarget>) -> Result<usize> {
+        let mesh_index = mesh.index();
+        let mesh = self.gltf.meshes.as_ref()
+            .and_then(|meshes| meshes.get(mesh_index))
+            .ok_or(GltfError::InvalidIndex)?;
+        
+        if mesh.primitives.is_empty() {
+            return Err(GltfError::InvalidData("Mesh has no primitives".to_string()));
+        }
+        if primitive_targets.len() != mesh.primitives.len() {
+            return Err(GltfError::InvalidData(format!(
+                "Mesh has {} primitives but {} morph targets were given",
+                mesh.primitives.len(), primitive_targets.len()
+            )));
+        }
+        
+        // Check the deltas against the vertex count of each primitive before uploading anything
+        let reader = self.accessor_reader();
+        for (primitive_index, (primitive, target)) in mesh.primitives.iter().zip(&primitive_targets).enumerate() {
+            let position_accessor = primitive.attributes.get("POSITION").copied().ok_or_else(|| {
+                GltfError::InvalidData(format!("Primitive {} has no POSITION attribute", primitive_index))
+            })?;
+            let vertex_count = reader.accessor(position_accessor)?.count;
+            
+            let lengths = [
+                Some(target.positions.len()),
+                target.normals.as_ref().map(|normals| normals.len()),
+                target.tangents.as_ref().map(|tangents| tangents.len()),
+            ];
+            if lengths.into_iter().flatten().any(|len| len != vertex_count) {
+                return Err(GltfError::InvalidData(format!(
+                    "Morph target deltas for primitive {} must have one entry per vertex ({})",
+                    primitive_index, vertex_count
+                )));
+            }
+        }
+        
+        let mut target_attributes = Vec::with_capacity(primitive_targets.len());
+        for target in &primitive_targets {
+            let mut attributes = HashMap::new();
+            attributes.insert("POSITION".to_string(), self.add_delta_accessor(&target.positions, true));
+            if let Some(normals) = &target.normals {
+                attributes.insert("NORMAL".to_string(), self.add_delta_accessor(normals, false));
+            }
+            if let Some(tangents) = &target.tangents {
+                attributes.insert("TANGENT".to_string(), self.add_delta_accessor(tangents, false));
+            }
+            target_attributes.push(attributes);
+        }
+        
+        let mesh = &mut self.gltf.meshes.as_mut().unwrap()[mesh_index];
+        for (primitive, attributes) in mesh.primitives.iter_mut().zip(target_attributes) {
+            primitive.targets.get_or_insert_with(Vec::new).push(attributes);
+        }
+        
+        let target_index = mesh.primitives[0].targets.as_ref().map_or(0, |targets| targets.len() - 1);
+        
+        // Meshes without default weights use zero for every target
+        let weights = mesh.weights.get_or_insert_with(Vec::new);
+        weights.resize(target_index, 0.0);
+        weights.push(default_weight);
+        
+        // Keep the target names aligned with the targets, using empty names for unnamed targets
+        let has_names = mesh.extras.as_ref().is_some_and(|extras| extras.contains_key("targetNames"));
+        if name.is_some() || has_names {
+            let names = mesh.extras.get_or_insert_with(HashMap::new)
+                .entry("targetNames".to_string())
+                .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+            if let Some(names) = names.as_array_mut() {
+                names.resize(target_index, serde_json::Value::String(String::new()));
+                names.push(serde_json::Value::String(name.unwrap_or_default()));
+            }
+        }
+        
+        Ok(target_index)
+    }
+    
+    /// Set the default weights of a mesh's morph targets
+    ///
+    /// # Returns
+    /// An error if the number of weights does not match the number of targets
+    pub fn set_morph_weights(&mut self, mesh: MeshId, weights: Vec<f32>) -> Result<()> {
+        let mesh = self.gltf.meshes.as_mut()
+            .and_then(|meshes| meshes.get_mut(mesh.index()))
+            .ok_or(GltfError::InvalidIndex)?;
+        
+        let target_count = mesh.primitives.first()
+            .and_then(|primitive| primitive.targets.as_ref())
+            .map_or(0, |targets| targets.len());
+        if weights.len() != target_count {
+            return Err(GltfError::InvalidData(format!(
+                "Mesh has {} morph targets but {} weights were given",
+                target_count, weights.len()
+            )));
+        }
+        
+        mesh.weights = Some(weights);
+        
+        Ok(())
+    }
+    
+    /// Upload VEC3 deltas and create an accessor for them
+    ///
+    /// Position deltas get bounds, which glTF requires for every POSITION accessor.
+    fn add_delta_accessor(&mut self, deltas: &[Vector3<f32>], with_bounds: bool) -> usize {
+        let flat_deltas: Vec<f32> = deltas.iter().flat_map(|d| [d.x, d.y, d.z]).collect();
+        self.add_float_attribute(&flat_deltas, accessor_type::VEC3, with_bounds)
+    }
+}
@@ -0,0 +1,533 @@
+//! # Wavefront OBJ Import and Export Implementation
+//!
+//! This module implements exporting the scene held by a `GltfBuilder` to the Wavefront OBJ
+//! format together with an MTL material library, for tools that do not read glTF, and
+//! importing OBJ assets parsed by the `obj` module.
+//!
+//! The scene graph is flattened: every node with a mesh that is reachable from the default
+//! scene becomes an OBJ object whose vertices and normals are transformed into world space.
+//! Materials are mapped to MTL entries using the base color factor (`Kd`, `d`), the emissive
+//! factor (`Ke`) and the base color texture (`map_Kd`).
+//!
+//! On import, every OBJ object becomes a mesh with one primitive per material, referenced by
+//! a new node. MTL materials become non-metallic PBR materials in the same way.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::builder::{image_extension, GltfBuilder};
+use crate::builder_import::load_uri;
+use crate::error::{GltfError, Result};
+use crate::handle::{MaterialId, NodeId, TextureId};
+use crate::models::{Material, TextureInfo};
+use crate::obj::{self, MtlMaterial, ObjModel};
+use crate::texture::{self, TextureFormat};
+use crate::transform;
+
+/// Name of the MTL material used for primitives without a material
+const DEFAULT_MATERIAL: &str = "default";
+
+impl GltfBuilder {
+    /// Load an OBJ file into a new builder with a scene containing all of its objects
+    ///
+    /// See `import_obj` for how the file is converted.
+    pub fn from_obj(path: &str) -> Result<Self> {
+        let mut builder = Self::new();
+        let nodes = builder.import_obj(path)?;
+        builder.add_scene(None, Some(nodes));
+        
+        Ok(builder)
+    }
+    
+    /// Import an OBJ file together with its MTL material libraries and textures
+    ///
+    /// Material libraries and `map_Kd` textures are resolved relative to the directory
+    /// containing `path`. Each object becomes a mesh with one primitive per material and
+    /// a node referencing it; the nodes are not added to a scene.
+    ///
+    /// Missing or unreadable files do not fail the import: primitives using a material from
+    /// a library that cannot be read have no material, and materials whose texture cannot be
+    /// loaded use their diffuse color instead.
+    ///
+    /// # Parameters
+    /// * `path` - Path of the OBJ file
+    ///
+    /// # Returns
+    /// The indices of the created nodes, one per object
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::GltfBuilder;
+    ///
+    /// let directory = std::env::temp_dir();
+    /// std::fs::write(directory.join("import_obj_quad.mtl"), "newmtl Red\nKd 1 0 0\n").unwrap();
+    /// std::fs::write(directory.join("import_obj_quad.obj"), "\
+    /// mtllib import_obj_quad.mtl
+    /// o Quad
+    /// v 0 0 0
+    /// v 1 0 0
+    /// v 1 1 0
+    /// v 0 1 0
+    /// usemtl Red
+    /// f 1 2 3 4
+    /// ").unwrap();
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// let nodes = builder.import_obj(directory.join("import_obj_quad.obj").to_str().unwrap()).unwrap();
+    /// builder.add_scene(None, Some(nodes));
+    ///
+    /// let material = &builder.gltf.materials.as_ref().unwrap()[0];
+    /// assert_eq!(material.name.as_deref(), Some("Red"));
+    /// ```
+    pub fn import_obj(&mut self, path: &str) -> Result<Vec<NodeId>> {
+        let path = Path::new(path);
+        let directory = path.parent().unwrap_or_else(|| Path::new(""));
+        
+        let model = obj::parse_obj(&String::from_utf8_lossy(&fs::read(path)?))?;
+        
+        // Legacy assets often reference libraries that were not shipped with them; their
+        // materials are left out instead of failing the import
+        let mut materials = Vec::new();
+        for library in &model.material_libraries {
+            if let Ok(source) = fs::read(directory.join(library)) {
+                materials.extend(obj::parse_mtl(&String::from_utf8_lossy(&source))?);
+            }
+        }
+        
+        self.add_obj_model(&model, &materials, directory.to_str())
+    }
+    
+    /// Add the objects of a parsed OBJ model as meshes and nodes
+    ///
+    /// Materials are created on first use; primitives whose material is not found in
+    /// `materials` have no material. Materials whose `map_Kd` texture cannot be loaded
+    /// use their diffuse color instead.
+    ///
+    /// # Parameters
+    /// * `model` - The parsed OBJ model
+    /// * `materials` - Materials from the model's MTL libraries
+    /// * `texture_dir` - Directory that `map_Kd` texture paths are relative to
+    ///
+    /// # Returns
+    /// The indices of the created nodes, one per object
+    pub fn add_obj_model(&mut self, model: &ObjModel, materials: &[MtlMaterial],
+                         texture_dir: Option<&str>) -> Result<Vec<NodeId>> {
+        let mut material_indices: HashMap<&str, MaterialId> = HashMap::new();
+        let mut texture_indices: HashMap<&str, Option<TextureId>> = HashMap::new();
+        let mut nodes = Vec::new();
+        
+        for object in &model.objects {
+            let mut primitives = Vec::new();
+            
+            for primitive in &object.primitives {
+                let material = match primitive.material.as_deref()
+                    .and_then(|name| materials.iter().find(|material| material.name == name)) {
+                    Some(material) => match material_indices.get(material.name.as_str()) {
+                        Some(&index) => Some(index),
+                        None => {
+                            let index = self.add_mtl_material(material, texture_dir, &mut texture_indices)?;
+                            material_indices.insert(&material.name, index);
+                            Some(index)
+                        }
+                    },
+                    None => None,
+                };
+                
+                primitives.push(self.create_custom_primitive(
+                    &primitive.positions,
+                    &primitive.indices,
+                    primitive.normals.clone(),
+                    primitive.texcoords.clone().map(|texcoords| vec![texcoords]),
+                    material,
+                ));
+            }
+            
+            let mesh = self.add_mesh(object.name.clone(), primitives);
+            nodes.push(self.add_node(object.name.clone(), Some(mesh), None, None, None));
+        }
+        
+        Ok(nodes)
+    }
+    
+    /// Create a glTF material for an MTL material
+    fn add_mtl_material<'a>(&mut self, material: &'a MtlMaterial, texture_dir: Option<&str>,
+                            texture_indices: &mut HashMap<&'a str, Option<TextureId>>) -> Result<MaterialId> {
+        // Textures that cannot be loaded are remembered, so they are only tried once
+        let texture = material.diffuse_map.as_deref().and_then(|file| {
+            *texture_indices.entry(file)
+                .or_insert_with(|| self.add_mtl_texture(file, texture_dir).ok())
+        });
+        
+        let index = match texture {
+            Some(texture) => self.create_textured_material(Some(material.name.clone()), texture),
+            None => {
+                let [r, g, b] = material.diffuse;
+                self.add_material(Some(material.name.clone()), Some([r, g, b, 1.0]), None, None, None)
+            }
+        };
+        
+        // OBJ materials are diffuse, so make them non-metallic and fully rough. A diffuse
+        // texture replaces the diffuse color, but the opacity applies either way.
+        let gltf_material = &mut self.gltf.materials.as_mut().unwrap()[index.index()];
+        if let Some(pbr) = &mut gltf_material.pbr_metallic_roughness {
+            if material.dissolve < 1.0 {
+                let [r, g, b, _] = pbr.base_color_factor.unwrap_or([1.0; 4]);
+                pbr.base_color_factor = Some([r, g, b, material.dissolve]);
+            }
+            pbr.metallic_factor = Some(0.0);
+            pbr.roughness_factor = Some(1.0);
+        }
+        
+        gltf_material.emissive_factor = material.emissive.filter(|emissive| emissive.iter().any(|&c| c > 0.0));
+        if material.dissolve < 1.0 {
+            gltf_material.alpha_mode = Some("BLEND".to_string());
+        }
+        
+        Ok(index)
+    }
+    
+    /// Load a texture file referenced by an MTL library
+    ///
+    /// PNG and JPEG files are embedded as they are; other formats are converted to PNG.
+    fn add_mtl_texture(&mut self, file: &str, texture_dir: Option<&str>) -> Result<TextureId> {
+        // Libraries written on Windows often use backslash separators
+        let relative = file.replace('\\', "/");
+        let path = match texture_dir {
+            Some(directory) => Path::new(directory).join(&relative),
+            None => Path::new(&relative).to_path_buf(),
+        };
+        
+        let name = path.file_stem().and_then(|stem| stem.to_str()).map(str::to_string);
+        let bytes = fs::read(&path)?;
+        
+        let extension = path.extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+        
+        let image = match extension.as_deref() {
+            Some("png") => self.add_image_from_buffer(name.clone(), TextureFormat::PNG.mime_type().to_string(), &bytes),
+            Some("jpg") | Some("jpeg") => {
+                self.add_image_from_buffer(name.clone(), TextureFormat::JPEG.mime_type().to_string(), &bytes)
+            }
+            _ => {
+                let image = image::load_from_memory(&bytes).map_err(texture::TextureError::from)?;
+                self.add_image_from_dynamic_image(name.clone(), &image, TextureFormat::PNG)?
+            }
+        };
+        
+        let sampler = self.create_default_sampler();
+        
+        Ok(self.add_texture(name, image, Some(sampler)))
+    }
+    
+    /// Export the scene as a Wavefront OBJ file with an MTL material library
+    ///
+    /// The material library is written next to `path` with the same file stem and a `.mtl`
+    /// extension. Base color textures stored in the buffer are written as standalone image
+    /// files (`<stem>_image<N>.png` or `.jpg`) and referenced through `map_Kd`.
+    ///
+    /// Only triangle primitives are exported; texture coordinates come from `TEXCOORD_0`.
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::GltfBuilder;
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// let red = builder.create_basic_material(Some("Red".to_string()), [1.0, 0.0, 0.0, 1.0]);
+    /// let box_mesh = builder.create_box_with_material(1.0, Some(red));
+    /// let node = builder.add_node(None, Some(box_mesh), Some([0.0, 1.0, 0.0]), None, None);
+    /// builder.add_scene(None, Some(vec![node]));
+    ///
+    /// let path = std::env::temp_dir().join("export_obj_box.obj");
+    /// builder.export_obj(path.to_str().unwrap()).unwrap();
+    /// ```
+    pub fn export_obj(&self, path: &str) -> Result<()> {
+        let path = Path::new(path);
+        let stem = path.file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| GltfError::InvalidData(format!("Invalid output path: {}", path.display())))?;
+        let directory = path.parent().unwrap_or_else(|| Path::new(""));
+        let mtl_name = format!("{}.mtl", stem);
+        
+        let material_names = self.obj_material_names();
+        let reader = self.accessor_reader();
+        let nodes = self.gltf.nodes.as_deref().unwrap_or(&[]);
+        let meshes = self.gltf.meshes.as_deref().unwrap_or(&[]);
+        
+        let mut obj = BufWriter::new(File::create(path)?);
+        writeln!(obj, "# Exported by mesh-tools")?;
+        writeln!(obj, "mtllib {}", mtl_name)?;
+        
+        // OBJ indices are 1-based and global across the whole file
+        let mut vertex_base = 1;
+        let mut texcoord_base = 1;
+        let mut normal_base = 1;
+        let mut uses_default_material = false;
+        
+        for (node_index, world) in transform::mesh_instances(&self.gltf) {
+            let node = &nodes[node_index];
+            let mesh = node.mesh
+                .and_then(|mesh| meshes.get(mesh))
+                .ok_or(GltfError::InvalidIndex)?;
+            
+            let object_name = node.name.as_deref()
+                .or(mesh.name.as_deref())
+                .map(obj_name)
+                .unwrap_or_else(|| format!("node_{}", node_index));
+            writeln!(obj, "o {}", object_name)?;
+            
+            // Mirroring transforms flip the winding order
+            let flip = transform::determinant3(&world) < 0.0;
+            
+            for primitive in &mesh.primitives {
+                let triangles = reader.read_primitive_triangles(primitive)?;
+                if triangles.is_empty() {
+                    continue;
+                }
+                
+                let position_accessor = primitive.attributes.get("POSITION")
+                    .ok_or_else(|| GltfError::InvalidData("Primitive has no POSITION attribute".to_string()))?;
+                let positions = reader.read_positions(*position_accessor)?;
+                
+                let normals = match primitive.attributes.get("NORMAL") {
+                    Some(&accessor) => Some(reader.read_vec3(accessor)?),
+                    None => None,
+                };
+                
+                let texcoords = match primitive.attributes.get("TEXCOORD_0") {
+                    Some(&accessor) => Some(reader.read_vec2(accessor)?),
+                    None => None,
+                };
+                
+                if normals.as_ref().is_some_and(|normals| normals.len() != positions.len())
+                    || texcoords.as_ref().is_some_and(|texcoords| texcoords.len() != positions.len()) {
+                    return Err(GltfError::InvalidData("Primitive attributes have mismatched counts".to_string()));
+                }
+                
+                for p in &positions {
+                    let [x, y, z] = transform::transform_point(&world, [p.x, p.y, p.z]);
+                    writeln!(obj, "v {} {} {}", x, y, z)?;
+                }
+                
+                // OBJ texture coordinates have their origin at the bottom left
+                for uv in texcoords.iter().flatten() {
+                    writeln!(obj, "vt {} {}", uv.x, 1.0 - uv.y)?;
+                }
+                
+                for n in normals.iter().flatten() {
+                    let [x, y, z] = transform::transform_normal(&world, [n.x, n.y, n.z]);
+                    writeln!(obj, "vn {} {} {}", x, y, z)?;
+                }
+                
+                match primitive.material.and_then(|material| material_names.get(material)) {
+                    Some(name) => writeln!(obj, "usemtl {}", name)?,
+                    None => {
+                        uses_default_material = true;
+                        writeln!(obj, "usemtl {}", DEFAULT_MATERIAL)?;
+                    }
+                }
+                
+                for triangle in &triangles {
+                    let corners = if flip {
+                        [triangle.a, triangle.c, triangle.b]
+                    } else {
+                        [triangle.a, triangle.b, triangle.c]
+                    };
+                    
+                    write!(obj, "f")?;
+                    for index in corners {
+                        let index = index as usize;
+                        if index >= positions.len() {
+                            return Err(GltfError::InvalidData(format!(
+                                "Vertex index {} exceeds vertex count {}", index, positions.len()
+                            )));
+                        }
+                        
+                        let v = vertex_base + index;
+                        match (texcoords.is_some(), normals.is_some()) {
+                            (true, true) => write!(obj, " {}/{}/{}", v, texcoord_base + index, normal_base + index)?,
+                            (true, false) => write!(obj, " {}/{}", v, texcoord_base + index)?,
+                            (false, true) => write!(obj, " {}//{}", v, normal_base + index)?,
+                            (false, false) => write!(obj, " {}", v)?,
+                        }
+                    }
+                    writeln!(obj)?;
+                }
+                
+                vertex_base += positions.len();
+                texcoord_base += texcoords.map_or(0, |texcoords| texcoords.len());
+                normal_base += normals.map_or(0, |normals| normals.len());
+            }
+        }
+        
+        obj.flush()?;
+        
+        self.write_mtl(directory, stem, &mtl_name, &material_names, uses_default_material)
+    }
+    
+    /// Write the MTL material library for an OBJ export
+    fn write_mtl(&self, directory: &Path, stem: &str, mtl_name: &str,
+                 material_names: &[String], include_default: bool) -> Result<()> {
+        let mut mtl = BufWriter::new(File::create(directory.join(mtl_name))?);
+        writeln!(mtl, "# Exported by mesh-tools")?;
+        
+        let mut image_files = HashMap::new();
+        let materials = self.gltf.materials.as_deref().unwrap_or(&[]);
+        
+        for (material, name) in materials.iter().zip(material_names) {
+            let (color, texture) = diffuse_of(material);
+            
+            writeln!(mtl)?;
+            writeln!(mtl, "newmtl {}", name)?;
+            writeln!(mtl, "Kd {} {} {}", color[0], color[1], color[2])?;
+            
+            if color[3] < 1.0 {
+                writeln!(mtl, "d {}", color[3])?;
+            }
+            
+            if let Some(emissive) = material.emissive_factor {
+                writeln!(mtl, "Ke {} {} {}", emissive[0], emissive[1], emissive[2])?;
+            }
+            
+            if let Some(texture) = texture {
+                if let Some(file_name) = self.obj_texture_file(texture.index, directory, stem, &mut image_files)? {
+                    writeln!(mtl, "map_Kd {}", file_name)?;
+                }
+            }
+        }
+        
+        if include_default {
+            writeln!(mtl)?;
+            writeln!(mtl, "newmtl {}", DEFAULT_MATERIAL)?;
+            writeln!(mtl, "Kd 1 1 1")?;
+        }
+        
+        mtl.flush()?;
+        
+        Ok(())
+    }
+    
+    /// Get the image file referenced by a texture, writing embedded images to disk once
+    fn obj_texture_file(&self, texture_index: usize, directory: &Path, stem: &str,
+                        image_files: &mut HashMap<usize, String>) -> Result<Option<String>> {
+        let image_index = match self.gltf.textures.as_ref().and_then(|textures| textures.get(texture_index)) {
+            Some(texture) => texture.source,
+            None => return Ok(None),
+        };
+        
+        if let Some(file_name) = image_files.get(&image_index) {
+            return Ok(Some(file_name.clone()));
+        }
+        
+        let image = match self.gltf.images.as_ref().and_then(|images| images.get(image_index)) {
+            Some(image) => image,
+            None => return Ok(None),
+        };
+        
+        let file_name = match (&image.uri, image.buffer_view) {
+            // External images are referenced as they are
+            (Some(uri), _) if !uri.starts_with("data:") => uri.clone(),
+            (Some(uri), _) => {
+                let file_name = format!("{}_image{}.{}", stem, image_index, image_extension(image));
+                fs::write(directory.join(&file_name), load_uri(uri, None)?)?;
+                file_name
+            }
+            (None, Some(buffer_view)) => {
+                let file_name = format!("{}_image{}.{}", stem, image_index, image_extension(image));
+                fs::write(directory.join(&file_name), self.buffer_view_bytes(buffer_view)?)?;
+                file_name
+            }
+            (None, None) => return Ok(None),
+        };
+        
+        image_files.insert(image_index, file_name.clone());
+        
+        Ok(Some(file_name))
+    }
+    
+    /// Assign a unique MTL name to every material
+    fn obj_material_names(&self) -> Vec<String> {
+        let mut used = HashSet::new();
+        used.insert(DEFAULT_MATERIAL.to_string());
+        
+        self.gltf.materials.as_deref().unwrap_or(&[]).iter()
+            .enumerate()
+            .map(|(index, material)| {
+                let name = material.name.as_deref()
+                    .map(obj_name)
+                    .filter(|name| !name.is_empty() && !used.contains(name))
+                    .unwrap_or_else(|| format!("material_{}", index));
+                used.insert(name.clone());
+                name
+            })
+            .collect()
+    }
+}
+
+/// Get the diffuse color and texture of a material for either PBR workflow
+fn diffuse_of(material: &Material) -> ([f32; 4], Option<&TextureInfo>) {
+    if let Some(pbr) = &material.pbr_metallic_roughness {
+        return (pbr.base_color_factor.unwrap_or([1.0; 4]), pbr.base_color_texture.as_ref());
+    }
+    
+    if let Some(specular) = material.extensions.as_ref().and_then(|ext| ext.pbr_specular_glossiness.as_ref()) {
+        return (specular.diffuse_factor.unwrap_or([1.0; 4]), specular.diffuse_texture.as_ref());
+    }
+    
+    ([1.0; 4], None)
+}
+
+/// Make a name safe for use in OBJ/MTL statements, which are whitespace separated
+fn obj_name(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| if c.is_whitespace() { '_' } else { c })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GltfBuilder;
+    
+    const QUAD: &str = "o Quad\nv 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nusemtl Brick\nf 1 2 3 4\n";
+    
+    /// Write an OBJ file, and optionally an MTL library, to a directory of their own
+    fn write_obj(name: &str, obj: &str, mtl: Option<&str>) -> String {
+        let directory = std::env::temp_dir().join(format!("mesh_tools_{}", name));
+        std::fs::create_dir_all(&directory).unwrap();
+        if let Some(mtl) = mtl {
+            std::fs::write(directory.join(format!("{}.mtl", name)), mtl).unwrap();
+        }
+        let path = directory.join(format!("{}.obj", name));
+        std::fs::write(&path, obj).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+    
+    #[test]
+    fn missing_material_library_leaves_primitives_without_material() {
+        let path = write_obj("obj_missing_mtl", &format!("mtllib missing.mtl\n{}", QUAD), None);
+        
+        let builder = GltfBuilder::from_obj(&path).unwrap();
+        
+        let primitive = &builder.gltf.meshes.as_ref().unwrap()[0].primitives[0];
+        assert_eq!(primitive.material, None);
+    }
+    
+    #[test]
+    fn missing_texture_falls_back_to_diffuse_color() {
+        let obj = format!("mtllib obj_missing_texture.mtl\n{}", QUAD);
+        let mtl = "newmtl Brick\nKd 0.5 0.25 0\nmap_Kd textures/brick.png\n";
+        let path = write_obj("obj_missing_texture", &obj, Some(mtl));
+        
+        let builder = GltfBuilder::from_obj(&path).unwrap();
+        
+        let material = &builder.gltf.materials.as_ref().unwrap()[0];
+        let pbr = material.pbr_metallic_roughness.as_ref().unwrap();
+        assert_eq!(material.name.as_deref(), Some("Brick"));
+        assert!(pbr.base_color_texture.is_none());
+        assert_eq!(pbr.base_color_factor, Some([0.5, 0.25, 0.0, 1.0]));
+        assert!(builder.gltf.textures.as_ref().is_none_or(|textures| textures.is_empty()));
+    }
+}
@@ -0,0 +1,141 @@
+//! # PLY Mesh Implementation
+//!
+//! This module implements adding meshes read with the `ply` module to a `GltfBuilder`.
+//! Meshes with faces become triangle primitives through the same path as `create_custom_mesh`;
+//! point clouds become `POINTS` primitives without indices.
+//!
+//! Vertex colors are stored as `COLOR_0`, and other per-vertex scalar properties as custom
+//! attributes named after the property in upper case with a leading underscore, as required
+//! by the glTF specification for application-specific attributes.
+
+use std::collections::HashMap;
+
+use crate::builder::GltfBuilder;
+use crate::constants::{accessor_type, primitive_mode};
+use crate::error::Result;
+use crate::handle::{MaterialId, MeshId};
+use crate::models::Primitive;
+use crate::ply::{PlyMesh, PlyProperty};
+
+impl GltfBuilder {
+    /// Create a mesh from PLY vertex and face data
+    ///
+    /// # Parameters
+    /// * `name` - Optional name for the mesh
+    /// * `ply` - Data read with `ply::read_ply`
+    /// * `material` - Optional material index to use for the mesh
+    ///
+    /// # Returns
+    /// The index of the created mesh, or an error if the vertex attributes have mismatched lengths
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::GltfBuilder;
+    /// use mesh_tools::ply::read_ply;
+    ///
+    /// let cloud = read_ply(b"ply
+    /// format ascii 1.0
+    /// element vertex 2
+    /// property float x
+    /// property float y
+    /// property float z
+    /// property float intensity
+    /// end_header
+    /// 0 0 0 0.25
+    /// 1 2 3 0.5
+    /// ").unwrap();
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// let mesh = builder.create_ply_mesh(Some("Scan".to_string()), &cloud, None).unwrap();
+    ///
+    /// let primitive = &builder.gltf.meshes.as_ref().unwrap()[mesh.index()].primitives[0];
+    /// assert_eq!(primitive.mode, Some(mesh_tools::constants::primitive_mode::POINTS));
+    /// assert!(primitive.attributes.contains_key("_INTENSITY"));
+    /// ```
+    pub fn create_ply_mesh(&mut self, name: Option<String>, ply: &PlyMesh,
+                           material: Option<MaterialId>) -> Result<MeshId> {
+        ply.validate()?;
+        
+        // Generate normals if requested, carrying the other vertex data over to split vertices
+        let texcoords = ply.texcoords.clone().map(|texcoords| vec![texcoords]);
+        if let Some(generated) = self.generate_missing_normals(&ply.positions, &ply.indices, &ply.normals, &texcoords) {
+            let ply = PlyMesh {
+                texcoords: ply.texcoords.as_ref().map(|texcoords| generated.remap(texcoords)),
+                colors: ply.colors.as_ref().map(|colors| generated.remap(colors)),
+                properties: ply.properties.iter()
+                    .map(|property| PlyProperty {
+                        name: property.name.clone(),
+                        values: generated.remap(&property.values),
+                    })
+                    .collect(),
+                positions: generated.positions,
+                normals: Some(generated.normals),
+                indices: generated.indices,
+            };
+            return self.create_ply_mesh(name, &ply, material);
+        }
+        
+        let mut primitive = if ply.indices.is_empty() {
+            self.create_point_primitive(ply, material)
+        } else {
+            self.create_custom_primitive(
+                &ply.positions,
+                &ply.indices,
+                ply.normals.clone(),
+                texcoords,
+                material,
+            )
+        };
+        
+        if let Some(colors) = &ply.colors {
+            let flat_colors: Vec<f32> = colors.iter().flatten().copied().collect();
+            let accessor = self.add_float_attribute(&flat_colors, accessor_type::VEC4, false);
+            primitive.attributes.insert("COLOR_0".to_string(), accessor);
+        }
+        
+        for property in &ply.properties {
+            let accessor = self.add_float_attribute(&property.values, accessor_type::SCALAR, false);
+            primitive.attributes.insert(custom_attribute_name(&property.name), accessor);
+        }
+        
+        Ok(self.add_mesh(name, vec![primitive]))
+    }
+    
+    /// Create a `POINTS` primitive from the positions, normals and texture coordinates of a point cloud
+    fn create_point_primitive(&mut self, ply: &PlyMesh, material: Option<MaterialId>) -> Primitive {
+        let flat_positions: Vec<f32> = ply.positions.iter().flat_map(|p| [p.x, p.y, p.z]).collect();
+        
+        let mut attributes = HashMap::new();
+        attributes.insert(
+            "POSITION".to_string(),
+            self.add_float_attribute(&flat_positions, accessor_type::VEC3, true),
+        );
+        
+        if let Some(normals) = &ply.normals {
+            let flat_normals: Vec<f32> = normals.iter().flat_map(|n| [n.x, n.y, n.z]).collect();
+            attributes.insert("NORMAL".to_string(), self.add_float_attribute(&flat_normals, accessor_type::VEC3, false));
+        }
+        
+        if let Some(texcoords) = &ply.texcoords {
+            let flat_texcoords: Vec<f32> = texcoords.iter().flat_map(|uv| [uv.x, uv.y]).collect();
+            attributes.insert("TEXCOORD_0".to_string(), self.add_float_attribute(&flat_texcoords, accessor_type::VEC2, false));
+        }
+        
+        Primitive {
+            attributes,
+            indices: None,
+            material: material.map(MaterialId::index),
+            mode: Some(primitive_mode::POINTS),
+            targets: None,
+        }
+    }
+}
+
+/// Name of the custom glTF attribute for a PLY property, e.g. `_CONFIDENCE` for `confidence`
+fn custom_attribute_name(property: &str) -> String {
+    let name: String = property.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    
+    format!("_{}", name)
+}
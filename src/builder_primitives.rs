@@ -15,6 +15,7 @@
 
 use crate::builder::GltfBuilder;
 use crate::constants::{accessor_type, buffer_view_target, component_type};
+use crate::handle::{MaterialId, MeshId};
 use crate::models::Primitive;
 use crate::primitives;
 use std::collections::HashMap;
@@ -48,6 +49,42 @@ impl Triangle {
     }
 }
 
+/// Component type of the indices written for new primitives
+///
+/// The largest value of each type is reserved for primitive restart and may not be used as
+/// an index in glTF, so each width holds one vertex fewer than its range suggests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IndexWidth {
+    /// `UNSIGNED_BYTE` indices, for up to 255 vertices
+    U8,
+    /// `UNSIGNED_SHORT` indices, for up to 65,535 vertices
+    U16,
+    /// `UNSIGNED_INT` indices
+    U32,
+}
+
+impl IndexWidth {
+    /// Get the narrowest width that can store indices up to `max_index`
+    pub fn for_max_index(max_index: u32) -> Self {
+        if max_index < u8::MAX as u32 {
+            IndexWidth::U8
+        } else if max_index < u16::MAX as u32 {
+            IndexWidth::U16
+        } else {
+            IndexWidth::U32
+        }
+    }
+    
+    /// Get the glTF component type for this width
+    pub fn component_type(self) -> usize {
+        match self {
+            IndexWidth::U8 => component_type::UNSIGNED_BYTE,
+            IndexWidth::U16 => component_type::UNSIGNED_SHORT,
+            IndexWidth::U32 => component_type::UNSIGNED_INT,
+        }
+    }
+}
+
 impl GltfBuilder {
     /// Create a simple cubic box mesh with the specified size
     ///
@@ -66,7 +103,7 @@ impl GltfBuilder {
     /// let mut builder = GltfBuilder::new();
     /// let box_mesh = builder.create_box(2.0); // Creates a 2x2x2 cube
     /// ```
-    pub fn create_box(&mut self, size: f32) -> usize {
+    pub fn create_box(&mut self, size: f32) -> MeshId {
         // Box centered at origin with given size
         let half_size = size / 2.0;
         
@@ -144,7 +181,7 @@ impl GltfBuilder {
     }
 
     /// Create a box with the specified material
-    pub fn create_box_with_material(&mut self, size: f32, material: Option<usize>) -> usize {
+    pub fn create_box_with_material(&mut self, size: f32, material: Option<MaterialId>) -> MeshId {
         // Box centered at origin with given size
         let half_size = size / 2.0;
         
@@ -310,7 +347,7 @@ impl GltfBuilder {
     /// * `indices` - List of triangles, each containing three vertex indices
     /// * `normals` - Optional vertex normals as Vec<Vector3<f32>>
     /// * `texcoords` - Optional array of UV coordinate sets, each as Vec<Vector2<f32>>. 
-    ///                 The first set becomes TEXCOORD_0, the second TEXCOORD_1, etc.
+    ///   The first set becomes TEXCOORD_0, the second TEXCOORD_1, etc.
     /// * `material` - Optional material index to use for the mesh
     /// 
     /// # Returns
@@ -321,7 +358,76 @@ impl GltfBuilder {
                             indices: &[Triangle], 
                             normals: Option<Vec<Vector3<f32>>>, 
                             texcoords: Option<Vec<Vec<Vector2<f32>>>>,
-                            material: Option<usize>) -> usize {
+                            material: Option<MaterialId>) -> MeshId {
+        let primitive = self.create_custom_primitive(positions, indices, normals, texcoords, material);
+        
+        // Create and add mesh
+        self.add_mesh(name, vec![primitive])
+    }
+    
+    /// Upload custom geometry and create a primitive for it without adding a mesh
+    /// 
+    /// Takes the same geometry parameters as `create_custom_mesh`, which allows several
+    /// primitives to be combined into one mesh with `add_mesh`.
+    pub(crate) fn create_custom_primitive(&mut self, 
+                                          positions: &[Point3<f32>], 
+                                          indices: &[Triangle], 
+                                          normals: Option<Vec<Vector3<f32>>>, 
+                                          texcoords: Option<Vec<Vec<Vector2<f32>>>>,
+                                          material: Option<MaterialId>) -> Primitive {
+        self.create_shared_vertex_primitives(positions, normals, texcoords, &[(indices.to_vec(), material)])
+            .remove(0)
+    }
+    
+    /// Upload custom geometry once and create one primitive per group of triangles
+    /// 
+    /// The primitives share the vertex attribute accessors and only get their own index
+    /// accessor and material.
+    pub(crate) fn create_shared_vertex_primitives(&mut self, 
+                                                  positions: &[Point3<f32>], 
+                                                  normals: Option<Vec<Vector3<f32>>>, 
+                                                  texcoords: Option<Vec<Vec<Vector2<f32>>>>,
+                                                  parts: &[(Vec<Triangle>, Option<MaterialId>)]) -> Vec<Primitive> {
+        let indices: Vec<Triangle> = parts.iter().flat_map(|(triangles, _)| triangles.iter().copied()).collect();
+        
+        // Generate normals if requested, which may split vertices
+        if let Some(generated) = self.generate_missing_normals(positions, &indices, &normals, &texcoords) {
+            let texcoords = texcoords.map(|sets| sets.iter().map(|set| generated.remap(set)).collect());
+            
+            // The generated triangles are in the same order as the input triangles
+            let mut remaining = generated.indices.as_slice();
+            let parts: Vec<_> = parts.iter()
+                .map(|(triangles, material)| {
+                    let (part, rest) = remaining.split_at(triangles.len());
+                    remaining = rest;
+                    (part.to_vec(), *material)
+                })
+                .collect();
+            return self.create_shared_vertex_primitives(&generated.positions, Some(generated.normals),
+                                                        texcoords, &parts);
+        }
+        
+        let attributes = self.add_vertex_attributes(positions, &indices, &normals, &texcoords);
+        
+        parts.iter()
+            .map(|(triangles, material)| Primitive {
+                attributes: attributes.clone(),
+                indices: Some(self.add_index_accessor(triangles)),
+                material: material.map(MaterialId::index),
+                mode: None, // Default mode (triangles)
+                targets: None,
+            })
+            .collect()
+    }
+    
+    /// Upload vertex attributes and return the attributes map of a primitive using them
+    /// 
+    /// `indices` are all triangles using the vertices, which tangent generation needs.
+    fn add_vertex_attributes(&mut self, 
+                             positions: &[Point3<f32>], 
+                             indices: &[Triangle], 
+                             normals: &Option<Vec<Vector3<f32>>>, 
+                             texcoords: &Option<Vec<Vec<Vector2<f32>>>>) -> HashMap<String, usize> {
         // Calculate bounds for the positions
         let (min_point, max_point) = if !positions.is_empty() {
             let mut min = crate::compat::point3::new(f32::MAX, f32::MAX, f32::MAX);
@@ -371,32 +477,6 @@ impl GltfBuilder {
             max
         );
         
-        // Flatten the Triangle structs into a flat list of indices
-        let flat_indices: Vec<u32> = indices.iter()
-            .flat_map(|triangle| vec![triangle.a, triangle.b, triangle.c])
-            .collect();
-            
-        // Add index data to buffer
-        let idx_bytes = unsafe {
-            std::slice::from_raw_parts(
-                flat_indices.as_ptr() as *const u8,
-                flat_indices.len() * std::mem::size_of::<u32>()
-            )
-        };
-        let (idx_offset, idx_length) = self.add_buffer_data(idx_bytes);
-        let idx_buffer_view = self.add_buffer_view(idx_offset, idx_length, Some(buffer_view_target::ELEMENT_ARRAY_BUFFER));
-        
-        // Add index accessor
-        let idx_accessor = self.add_accessor(
-            idx_buffer_view,
-            component_type::UNSIGNED_INT,  // Use UNSIGNED_INT for u32 indices
-            flat_indices.len(),
-            accessor_type::SCALAR.to_string(),
-            None,
-            None,
-            None
-        );
-        
         // Build attributes map
         let mut attributes = HashMap::new();
         attributes.insert("POSITION".to_string(), pos_accessor);
@@ -429,7 +509,6 @@ impl GltfBuilder {
         }
         
         // Add texture coordinates if provided
-        let mut texcoord_accessors = Vec::new();
         if let Some(texcoord_sets) = texcoords {
             for (i, texcoord_data) in texcoord_sets.iter().enumerate() {
                 // Convert Vector2 to flat array for buffer
@@ -455,20 +534,55 @@ impl GltfBuilder {
                 );
                 
                 attributes.insert(format!("TEXCOORD_{}", i), tc_accessor);
-                texcoord_accessors.push(tc_accessor);
             }
         }
         
-        // Create primitive
-        let primitive = Primitive {
-            attributes,
-            indices: Some(idx_accessor),
-            material,
-            mode: None, // Default mode (triangles)
+        // Add tangents computed from the normals and first UV set if requested; geometry that
+        // tangents cannot be computed for is uploaded without them, as documented on
+        // `set_generate_tangents`
+        if self.options.generate_tangents {
+            if let (Some(normal_data), Some(uvs)) = (normals, texcoords.as_ref().and_then(|sets| sets.first())) {
+                if let Ok(tangents) = crate::geometry::generate_tangents(positions, normal_data, uvs, indices) {
+                    let tangent_accessor = self.add_float_attribute(tangents.as_flattened(), accessor_type::VEC4, false);
+                    attributes.insert("TANGENT".to_string(), tangent_accessor);
+                }
+            }
+        }
+        
+        attributes
+    }
+    
+    /// Upload triangle indices and create an index accessor for them
+    fn add_index_accessor(&mut self, indices: &[Triangle]) -> usize {
+        // Flatten the Triangle structs into a flat list of indices
+        let flat_indices: Vec<u32> = indices.iter()
+            .flat_map(|triangle| vec![triangle.a, triangle.b, triangle.c])
+            .collect();
+        
+        // Use the narrowest index type that fits, unless a wider one is configured
+        let max_index = flat_indices.iter().copied().max().unwrap_or(0);
+        let required_width = IndexWidth::for_max_index(max_index);
+        let index_width = self.options.index_width.map_or(required_width, |width| width.max(required_width));
+        
+        // Add index data to buffer
+        let idx_bytes: Vec<u8> = match index_width {
+            IndexWidth::U8 => flat_indices.iter().map(|&index| index as u8).collect(),
+            IndexWidth::U16 => flat_indices.iter().flat_map(|&index| (index as u16).to_le_bytes()).collect(),
+            IndexWidth::U32 => flat_indices.iter().flat_map(|index| index.to_le_bytes()).collect(),
         };
+        let (idx_offset, idx_length) = self.add_buffer_data(&idx_bytes);
+        let idx_buffer_view = self.add_buffer_view(idx_offset, idx_length, Some(buffer_view_target::ELEMENT_ARRAY_BUFFER));
         
-        // Create and add mesh
-        self.add_mesh(name, vec![primitive])
+        // Add index accessor
+        self.add_accessor(
+            idx_buffer_view,
+            index_width.component_type(),
+            flat_indices.len(),
+            accessor_type::SCALAR.to_string(),
+            None,
+            None,
+            None
+        )
     }
     
     
@@ -493,15 +607,9 @@ impl GltfBuilder {
                                indices: &[Triangle], 
                                normals: Option<Vec<Vector3<f32>>>, 
                                texcoords: Option<Vec<Vector2<f32>>>,
-                               material: Option<usize>) -> usize {
+                               material: Option<MaterialId>) -> MeshId {
         // If we have texture coordinates, create a texcoord set for the mesh
-        let texcoord_sets = if let Some(uvs) = texcoords {
-            let mut sets = Vec::new();
-            sets.push(uvs);
-            Some(sets)
-        } else {
-            None
-        };
+        let texcoord_sets = texcoords.map(|uvs| vec![uvs]);
         
         self.create_custom_mesh(name, positions, indices, normals, texcoord_sets, material)
     }
@@ -541,7 +649,7 @@ impl GltfBuilder {
                       depth: f32, 
                       width_segments: usize, 
                       depth_segments: usize,
-                      material: Option<usize>) -> usize {
+                      material: Option<MaterialId>) -> MeshId {
         // Get the mesh data directly as types
         let (positions, indices, normals, uvs) = primitives::generate_plane(
             width, depth, width_segments, depth_segments
@@ -583,7 +691,7 @@ impl GltfBuilder {
                        radius: f32, 
                        width_segments: usize, 
                        height_segments: usize,
-                       material: Option<usize>) -> usize {
+                       material: Option<MaterialId>) -> MeshId {
         // Get the mesh data directly as mint types
         let (positions, indices, normals, uvs) = primitives::generate_sphere(
             radius, width_segments, height_segments
@@ -633,6 +741,7 @@ impl GltfBuilder {
     ///     Some(blue_material)
     /// );
     /// ```
+    #[allow(clippy::too_many_arguments)]
     pub fn create_cylinder(&mut self, 
                          radius_top: f32, 
                          radius_bottom: f32, 
@@ -640,7 +749,7 @@ impl GltfBuilder {
                          radial_segments: usize, 
                          height_segments: usize,
                          open_ended: bool,
-                         material: Option<usize>) -> usize {
+                         material: Option<MaterialId>) -> MeshId {
         // Get the mesh data directly as types
         let (positions, indices, normals, uvs) = primitives::generate_cylinder(
             radius_top, radius_bottom, height, radial_segments, height_segments, open_ended
@@ -668,7 +777,7 @@ impl GltfBuilder {
                       radial_segments: usize, 
                       height_segments: usize,
                       open_ended: bool,
-                      material: Option<usize>) -> usize {
+                      material: Option<MaterialId>) -> MeshId {
         // Get the mesh data directly as types
         let (positions, indices, normals, uvs) = primitives::generate_cone(
             radius, height, radial_segments, height_segments, open_ended
@@ -694,7 +803,7 @@ impl GltfBuilder {
                        tube: f32, 
                        radial_segments: usize, 
                        tubular_segments: usize,
-                       material: Option<usize>) -> usize {
+                       material: Option<MaterialId>) -> MeshId {
         // Get the mesh data directly as types
         let (positions, indices, normals, uvs) = primitives::generate_torus(
             radius, tube, radial_segments, tubular_segments
@@ -714,7 +823,7 @@ impl GltfBuilder {
     /// The index of the created mesh
     pub fn create_icosahedron(&mut self, 
                             radius: f32,
-                            material: Option<usize>) -> usize {
+                            material: Option<MaterialId>) -> MeshId {
         // Get the mesh data directly as types
         let (positions, indices, normals, uvs) = primitives::generate_icosahedron(radius);
         
@@ -722,3 +831,54 @@ impl GltfBuilder {
         self.create_simple_mesh(None, &positions, &indices, Some(normals), Some(uvs), material)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compat::point3;
+    
+    /// Upload a mesh with `vertex_count` vertices and a triangle using the last one, and get
+    /// the component type of its indices and the triangle read back
+    fn index_type(builder: &mut GltfBuilder, vertex_count: u32) -> (usize, Vec<Triangle>) {
+        let positions: Vec<_> = (0..vertex_count).map(|i| point3::new(i as f32, 0.0, 0.0)).collect();
+        let triangle = Triangle::new(0, vertex_count - 2, vertex_count - 1);
+        let mesh = builder.create_custom_mesh(None, &positions, &[triangle], None, None, None);
+        
+        let primitive = &builder.gltf.meshes.as_ref().unwrap()[mesh.index()].primitives[0];
+        let accessor = &builder.gltf.accessors.as_ref().unwrap()[primitive.indices.unwrap()];
+        (accessor.component_type, builder.accessor_reader().read_primitive_triangles(primitive).unwrap())
+    }
+    
+    #[test]
+    fn narrowest_width_leaves_the_largest_value_free() {
+        assert_eq!(IndexWidth::for_max_index(254), IndexWidth::U8);
+        assert_eq!(IndexWidth::for_max_index(255), IndexWidth::U16);
+        assert_eq!(IndexWidth::for_max_index(65534), IndexWidth::U16);
+        assert_eq!(IndexWidth::for_max_index(65535), IndexWidth::U32);
+    }
+    
+    #[test]
+    fn indices_use_the_narrowest_width() {
+        let mut builder = GltfBuilder::new();
+        
+        for (vertex_count, expected) in [
+            (255, component_type::UNSIGNED_BYTE),
+            (256, component_type::UNSIGNED_SHORT),
+            (65535, component_type::UNSIGNED_SHORT),
+            (65536, component_type::UNSIGNED_INT),
+        ] {
+            let (component, triangles) = index_type(&mut builder, vertex_count);
+            assert_eq!(component, expected, "{} vertices", vertex_count);
+            assert_eq!(triangles, vec![Triangle::new(0, vertex_count - 2, vertex_count - 1)]);
+        }
+    }
+    
+    #[test]
+    fn configured_width_is_widened_when_needed() {
+        let mut builder = GltfBuilder::new();
+        builder.set_index_width(Some(IndexWidth::U16));
+        
+        assert_eq!(index_type(&mut builder, 3).0, component_type::UNSIGNED_SHORT);
+        assert_eq!(index_type(&mut builder, 65536).0, component_type::UNSIGNED_INT);
+    }
+}
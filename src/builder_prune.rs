@@ -0,0 +1,417 @@
+//! # Pruning Implementation
+//!
+//! This module implements removing the parts of the glTF document held by a `GltfBuilder` that
+//! no scene uses. Generators often create resources speculatively and only place some of them
+//! in the scene; `GltfBuilder::prune` walks the document from its scenes and removes every
+//! node, mesh, accessor, buffer view, material, texture, image and sampler that cannot be
+//! reached, then compacts the buffer data and redirects every remaining reference.
+//!
+//! Skins are kept while a remaining node uses them, and their joints are kept with them.
+//! Animation channels targeting removed nodes are dropped, and animations left without any
+//! channel are removed. Cameras and lights are left as they are.
+
+use crate::builder::GltfBuilder;
+use crate::builder_dedup::material_textures;
+use crate::error::{GltfError, Result};
+
+impl GltfBuilder {
+    /// Remove everything the scenes of the document do not use
+    ///
+    /// Nodes are kept when they can be reached from a scene, through node children and the
+    /// joints of the skins they use. Meshes, accessors, buffer views, materials, textures,
+    /// images and samplers are kept when a remaining object references them. Everything else
+    /// is removed, the buffer data is compacted and all indices in the document are updated.
+    ///
+    /// Handles returned before pruning refer to the old indices and must not be used
+    /// afterwards. A document without scenes is emptied entirely.
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error if the document references an object that does not
+    /// exist, in which case the document is left unchanged
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::GltfBuilder;
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// let red = builder.create_basic_material(Some("Red".to_string()), [1.0, 0.0, 0.0, 1.0]);
+    /// let blue = builder.create_basic_material(Some("Blue".to_string()), [0.0, 0.0, 1.0, 1.0]);
+    /// let used = builder.create_box_with_material(1.0, Some(red));
+    /// let _unused = builder.create_sphere(1.0, 16, 8, Some(blue));
+    ///
+    /// let node = builder.add_node(None, Some(used), None, None, None);
+    /// builder.add_scene(None, Some(vec![node]));
+    ///
+    /// builder.prune().unwrap();
+    ///
+    /// assert_eq!(builder.gltf.meshes.as_ref().unwrap().len(), 1);
+    /// assert_eq!(builder.gltf.materials.as_ref().unwrap()[0].name.as_deref(), Some("Red"));
+    /// assert!(builder.validate().is_valid());
+    /// ```
+    pub fn prune(&mut self) -> Result<()> {
+        // Work on a copy, so that an invalid reference leaves the document untouched
+        let mut pruned = self.gltf.clone();
+        let gltf = &mut pruned;
+        
+        // Walk the node hierarchy from the scenes, following skins to their joints
+        let mut used_nodes = vec![false; count(&gltf.nodes)];
+        let mut used_skins = vec![false; count(&gltf.skins)];
+        let mut pending: Vec<usize> = gltf.scenes.iter().flatten()
+            .flat_map(|scene| scene.nodes.iter().flatten())
+            .copied()
+            .collect();
+        while let Some(index) = pending.pop() {
+            if !mark(&mut used_nodes, index)? {
+                continue;
+            }
+            
+            let node = &gltf.nodes.as_ref().unwrap()[index];
+            pending.extend(node.children.iter().flatten());
+            if let Some(skin_index) = node.skin {
+                mark(&mut used_skins, skin_index)?;
+                let skin = &gltf.skins.as_ref().unwrap()[skin_index];
+                pending.extend(&skin.joints);
+                pending.extend(skin.skeleton);
+            }
+        }
+        
+        let node_map = retain_used(&mut gltf.nodes, &used_nodes);
+        let skin_map = retain_used(&mut gltf.skins, &used_skins);
+        
+        let mut used_meshes = vec![false; count(&gltf.meshes)];
+        for node in gltf.nodes.iter().flatten() {
+            if let Some(mesh) = node.mesh {
+                mark(&mut used_meshes, mesh)?;
+            }
+        }
+        let mesh_map = retain_used(&mut gltf.meshes, &used_meshes);
+        
+        for scene in gltf.scenes.iter_mut().flatten() {
+            for node in scene.nodes.iter_mut().flatten() {
+                *node = remap(*node, &node_map)?;
+            }
+        }
+        for node in gltf.nodes.iter_mut().flatten() {
+            for child in node.children.iter_mut().flatten() {
+                *child = remap(*child, &node_map)?;
+            }
+            remap_option(&mut node.mesh, &mesh_map)?;
+            remap_option(&mut node.skin, &skin_map)?;
+        }
+        for skin in gltf.skins.iter_mut().flatten() {
+            for joint in skin.joints.iter_mut() {
+                *joint = remap(*joint, &node_map)?;
+            }
+            remap_option(&mut skin.skeleton, &node_map)?;
+        }
+        
+        // Drop the animation channels of removed nodes and the samplers no channel uses
+        for animation in gltf.animations.iter_mut().flatten() {
+            let mut channels = animation.channels.take().unwrap_or_default();
+            channels.retain(|channel| used_nodes.get(channel.target.node) == Some(&true));
+            
+            let mut used_samplers = vec![false; count(&animation.samplers)];
+            for channel in &channels {
+                mark(&mut used_samplers, channel.sampler)?;
+            }
+            let sampler_map = retain_used(&mut animation.samplers, &used_samplers);
+            
+            for channel in channels.iter_mut() {
+                channel.sampler = remap(channel.sampler, &sampler_map)?;
+                channel.target.node = remap(channel.target.node, &node_map)?;
+            }
+            animation.channels = Some(channels);
+        }
+        if let Some(animations) = &mut gltf.animations {
+            animations.retain(|animation| animation.channels.as_ref().is_some_and(|channels| !channels.is_empty()));
+            if animations.is_empty() {
+                gltf.animations = None;
+            }
+        }
+        
+        // Accessors and materials used by the remaining meshes, animations and skins
+        let mut used_accessors = vec![false; count(&gltf.accessors)];
+        let mut used_materials = vec![false; count(&gltf.materials)];
+        for primitive in gltf.meshes.iter().flatten().flat_map(|mesh| &mesh.primitives) {
+            for &accessor in primitive.attributes.values() {
+                mark(&mut used_accessors, accessor)?;
+            }
+            if let Some(accessor) = primitive.indices {
+                mark(&mut used_accessors, accessor)?;
+            }
+            for &accessor in primitive.targets.iter().flatten().flat_map(|target| target.values()) {
+                mark(&mut used_accessors, accessor)?;
+            }
+            if let Some(material) = primitive.material {
+                mark(&mut used_materials, material)?;
+            }
+        }
+        for sampler in gltf.animations.iter().flatten().flat_map(|animation| animation.samplers.iter().flatten()) {
+            mark(&mut used_accessors, sampler.input)?;
+            mark(&mut used_accessors, sampler.output)?;
+        }
+        for skin in gltf.skins.iter().flatten() {
+            if let Some(accessor) = skin.inverse_bind_matrices {
+                mark(&mut used_accessors, accessor)?;
+            }
+        }
+        
+        let accessor_map = retain_used(&mut gltf.accessors, &used_accessors);
+        let material_map = retain_used(&mut gltf.materials, &used_materials);
+        
+        for primitive in gltf.meshes.iter_mut().flatten().flat_map(|mesh| mesh.primitives.iter_mut()) {
+            for accessor in primitive.attributes.values_mut() {
+                *accessor = remap(*accessor, &accessor_map)?;
+            }
+            remap_option(&mut primitive.indices, &accessor_map)?;
+            for accessor in primitive.targets.iter_mut().flatten().flat_map(|target| target.values_mut()) {
+                *accessor = remap(*accessor, &accessor_map)?;
+            }
+            remap_option(&mut primitive.material, &material_map)?;
+        }
+        for sampler in gltf.animations.iter_mut().flatten().flat_map(|animation| animation.samplers.iter_mut().flatten()) {
+            sampler.input = remap(sampler.input, &accessor_map)?;
+            sampler.output = remap(sampler.output, &accessor_map)?;
+        }
+        for skin in gltf.skins.iter_mut().flatten() {
+            remap_option(&mut skin.inverse_bind_matrices, &accessor_map)?;
+        }
+        
+        // Textures used by the remaining materials, and their images and samplers
+        let mut used_textures = vec![false; count(&gltf.textures)];
+        for material in gltf.materials.iter_mut().flatten() {
+            for texture in material_textures(material) {
+                mark(&mut used_textures, *texture)?;
+            }
+        }
+        let texture_map = retain_used(&mut gltf.textures, &used_textures);
+        for material in gltf.materials.iter_mut().flatten() {
+            for texture in material_textures(material) {
+                *texture = remap(*texture, &texture_map)?;
+            }
+        }
+        
+        let mut used_images = vec![false; count(&gltf.images)];
+        let mut used_samplers = vec![false; count(&gltf.samplers)];
+        for texture in gltf.textures.iter().flatten() {
+            mark(&mut used_images, texture.source)?;
+            if let Some(sampler) = texture.sampler {
+                mark(&mut used_samplers, sampler)?;
+            }
+        }
+        let image_map = retain_used(&mut gltf.images, &used_images);
+        let sampler_map = retain_used(&mut gltf.samplers, &used_samplers);
+        for texture in gltf.textures.iter_mut().flatten() {
+            texture.source = remap(texture.source, &image_map)?;
+            remap_option(&mut texture.sampler, &sampler_map)?;
+        }
+        
+        // Buffer views holding the data of the remaining accessors and images
+        let mut used_views = vec![false; count(&gltf.buffer_views)];
+        for accessor in gltf.accessors.iter().flatten() {
+            if let Some(view) = accessor.buffer_view {
+                mark(&mut used_views, view)?;
+            }
+            if let Some(sparse) = &accessor.sparse {
+                mark(&mut used_views, sparse.indices.buffer_view)?;
+                mark(&mut used_views, sparse.values.buffer_view)?;
+            }
+        }
+        for image in gltf.images.iter().flatten() {
+            if let Some(view) = image.buffer_view {
+                mark(&mut used_views, view)?;
+            }
+        }
+        
+        // Rebuild the buffer with the data of the remaining views
+        let mut buffer_data = Vec::new();
+        let mut buffer_views = Vec::new();
+        let mut view_map = Vec::new();
+        for (index, view) in self.gltf.buffer_views.iter().flatten().enumerate() {
+            if !used_views[index] {
+                view_map.push(None);
+                continue;
+            }
+            
+            while buffer_data.len() % 4 != 0 {
+                buffer_data.push(0);
+            }
+            
+            let mut view = view.clone();
+            view.byte_offset = buffer_data.len();
+            buffer_data.extend_from_slice(self.buffer_view_bytes(index)?);
+            
+            view_map.push(Some(buffer_views.len()));
+            buffer_views.push(view);
+        }
+        
+        let gltf = &mut pruned;
+        for accessor in gltf.accessors.iter_mut().flatten() {
+            remap_option(&mut accessor.buffer_view, &view_map)?;
+            if let Some(sparse) = &mut accessor.sparse {
+                sparse.indices.buffer_view = remap(sparse.indices.buffer_view, &view_map)?;
+                sparse.values.buffer_view = remap(sparse.values.buffer_view, &view_map)?;
+            }
+        }
+        for image in gltf.images.iter_mut().flatten() {
+            remap_option(&mut image.buffer_view, &view_map)?;
+        }
+        
+        gltf.buffer_views = (!buffer_views.is_empty()).then_some(buffer_views);
+        if let Some(buffers) = &mut gltf.buffers {
+            if !buffers.is_empty() {
+                buffers[0].byte_length = buffer_data.len();
+            }
+        }
+        self.gltf = pruned;
+        self.buffer_data = buffer_data;
+        
+        Ok(())
+    }
+}
+
+/// Number of objects in an optional list
+fn count<T>(items: &Option<Vec<T>>) -> usize {
+    items.as_ref().map_or(0, Vec::len)
+}
+
+/// Mark an object as used, returning whether it was unmarked before
+fn mark(used: &mut [bool], index: usize) -> Result<bool> {
+    let flag = used.get_mut(index).ok_or(GltfError::InvalidIndex)?;
+    Ok(!std::mem::replace(flag, true))
+}
+
+/// Keep only the used objects of a list
+///
+/// Returns the new index of every original object, or `None` for removed objects. A list left
+/// empty is removed as well.
+fn retain_used<T>(items: &mut Option<Vec<T>>, used: &[bool]) -> Vec<Option<usize>> {
+    let Some(list) = items.take() else {
+        return Vec::new();
+    };
+    
+    let mut map = Vec::with_capacity(list.len());
+    let mut kept = Vec::new();
+    for (item, &is_used) in list.into_iter().zip(used) {
+        if is_used {
+            map.push(Some(kept.len()));
+            kept.push(item);
+        } else {
+            map.push(None);
+        }
+    }
+    
+    // glTF does not allow empty arrays
+    *items = (!kept.is_empty()).then_some(kept);
+    map
+}
+
+/// Redirect an index through a map from old to new indices
+fn remap(index: usize, map: &[Option<usize>]) -> Result<usize> {
+    map.get(index).copied().flatten().ok_or(GltfError::InvalidIndex)
+}
+
+/// Redirect an optional index through a map from old to new indices
+fn remap_option(index: &mut Option<usize>, map: &[Option<usize>]) -> Result<()> {
+    if let Some(index) = index {
+        *index = remap(*index, map)?;
+    }
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{GltfBuilder, InterpolationType};
+    
+    #[test]
+    fn dangling_material_leaves_document_unchanged() {
+        let mut builder = GltfBuilder::new();
+        let used = builder.create_box(1.0);
+        builder.create_sphere(1.0, 8, 4, None);
+        let node = builder.add_node(None, Some(used), None, None, None);
+        builder.add_node(None, None, None, None, None);
+        builder.add_scene(None, Some(vec![node]));
+        builder.gltf.meshes.as_mut().unwrap()[used.index()].primitives[0].material = Some(7);
+        
+        let before = serde_json::to_string(&builder.gltf).unwrap();
+        let buffer_length = builder.buffer_data.len();
+        
+        assert!(builder.prune().is_err());
+        assert_eq!(serde_json::to_string(&builder.gltf).unwrap(), before);
+        assert_eq!(builder.buffer_data.len(), buffer_length);
+    }
+    
+    #[test]
+    fn skin_joints_outside_the_scene_are_kept() {
+        let mut builder = GltfBuilder::new();
+        let root = builder.add_node(Some("Root".to_string()), None, None, None, None);
+        let tip = builder.add_node(Some("Tip".to_string()), None, Some([0.0, 1.0, 0.0]), None, None);
+        builder.add_child_to_node(root, tip).unwrap();
+        builder.add_node(Some("Unused".to_string()), None, None, None, None);
+        let skin = builder.add_skin(None, vec![root, tip], Some(root)).unwrap();
+        
+        let mesh = builder.create_box(1.0);
+        let vertex_count = builder.primitive_vertex_count(mesh, 0).unwrap();
+        builder.add_skin_attributes(mesh, 0, &vec![[0, 1, 0, 0]; vertex_count], &vec![[0.5, 0.5, 0.0, 0.0]; vertex_count]).unwrap();
+        let body = builder.add_node(Some("Body".to_string()), Some(mesh), None, None, None);
+        builder.attach_skin(body, skin).unwrap();
+        builder.add_scene(None, Some(vec![body]));
+        
+        builder.prune().unwrap();
+        
+        let names: Vec<_> = builder.gltf.nodes.iter().flatten().filter_map(|node| node.name.as_deref()).collect();
+        assert_eq!(names, vec!["Root", "Tip", "Body"]);
+        assert_eq!(builder.gltf.skins.as_ref().unwrap()[0].joints, vec![0, 1]);
+        assert!(builder.validate().is_valid(), "{}", builder.validate());
+    }
+    
+    #[test]
+    fn animations_of_removed_nodes_are_dropped() {
+        let mut builder = GltfBuilder::new();
+        builder.add_node(None, None, None, None, None);
+        let removed = builder.add_node(None, None, None, None, None);
+        let kept = builder.add_node(None, None, None, None, None);
+        builder.add_scene(None, Some(vec![kept]));
+        
+        for node in [removed, kept] {
+            let animation = builder.add_animation(None);
+            builder.create_translation_animation(animation, node, vec![0.0, 1.0], vec![[0.0; 3], [1.0; 3]],
+                                                 InterpolationType::Linear).unwrap();
+        }
+        
+        builder.prune().unwrap();
+        
+        let animations = builder.gltf.animations.as_ref().unwrap();
+        assert_eq!(animations.len(), 1);
+        assert_eq!(animations[0].channels.as_ref().unwrap()[0].target.node, 0);
+        assert_eq!(builder.gltf.accessors.as_ref().unwrap().len(), 2);
+        let times = builder.accessor_reader().read_scalars(animations[0].samplers.as_ref().unwrap()[0].input).unwrap();
+        assert_eq!(times, vec![0.0, 1.0]);
+        assert!(builder.validate().is_valid(), "{}", builder.validate());
+    }
+    
+    #[test]
+    fn embedded_images_keep_their_data() {
+        let mut builder = GltfBuilder::new();
+        let unused_texture = builder.create_checkerboard_texture(8, 8, 2, [255, 0, 0], [0, 0, 0]).unwrap();
+        let texture = builder.create_checkerboard_texture(16, 16, 4, [0, 0, 255], [255, 255, 255]).unwrap();
+        builder.create_textured_material(None, unused_texture);
+        let material = builder.create_textured_material(None, texture);
+        
+        let image_view = builder.gltf.images.as_ref().unwrap()[1].buffer_view.unwrap();
+        let image_bytes = builder.buffer_view_bytes(image_view).unwrap().to_vec();
+        
+        let mesh = builder.create_box_with_material(1.0, Some(material));
+        let node = builder.add_node(None, Some(mesh), None, None, None);
+        builder.add_scene(None, Some(vec![node]));
+        
+        builder.prune().unwrap();
+        
+        let images = builder.gltf.images.as_ref().unwrap();
+        assert_eq!(images.len(), 1);
+        assert_eq!(builder.buffer_view_bytes(images[0].buffer_view.unwrap()).unwrap(), image_bytes.as_slice());
+        assert_eq!(builder.gltf.textures.as_ref().unwrap()[0].source, 0);
+        assert!(builder.validate().is_valid(), "{}", builder.validate());
+    }
+}
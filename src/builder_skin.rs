@@ -0,0 +1,274 @@
+//! # Skinning Implementation
+//!
+//! This module implements skins for the glTF document held by a `GltfBuilder`. A skin lists the
+//! joint nodes that deform a mesh, together with their inverse bind matrices, which transform
+//! vertices from mesh space into the space of each joint in its bind pose. Skinned primitives
+//! carry `JOINTS_0` and `WEIGHTS_0` attributes with up to four joint influences per vertex.
+//!
+//! Joints are ordinary nodes, so a skeleton is built with `add_node` and `add_child_to_node`
+//! and animated with the regular animation API.
+
+use crate::builder::GltfBuilder;
+use crate::constants::{accessor_type, buffer_view_target, component_type};
+use crate::error::{GltfError, Result};
+use crate::handle::{MeshId, NodeId, SkinId};
+use crate::models::Skin;
+use crate::transform;
+
+impl GltfBuilder {
+    /// Add a skin whose inverse bind matrices are computed from the current joint transforms
+    ///
+    /// The current world transform of each joint is taken as its bind pose, so the skeleton
+    /// should be set up in the pose matching the mesh before calling this. glTF ignores the
+    /// transform of the node a skinned mesh is attached to, so the mesh vertices are expected
+    /// in the same space as the scene root.
+    ///
+    /// # Parameters
+    /// * `name` - Optional name for the skin
+    /// * `joints` - Indices of the joint nodes; `JOINTS_0` values index into this list
+    /// * `skeleton` - Optional index of the common root of the joint hierarchy
+    ///
+    /// # Returns
+    /// The index of the created skin, or an error if the joints are missing, duplicated
+    /// or have a singular transform
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::{GltfBuilder, InterpolationType};
+    /// use mesh_tools::primitives::generate_cylinder;
+    ///
+    /// let mut builder = GltfBuilder::new();
+    ///
+    /// // A two-bone arm along +Y
+    /// let shoulder = builder.add_node(Some("Shoulder".to_string()), None, None, None, None);
+    /// let elbow = builder.add_node(Some("Elbow".to_string()), None, Some([0.0, 1.0, 0.0]), None, None);
+    /// builder.add_child_to_node(shoulder, elbow).unwrap();
+    /// let skin = builder.add_skin(Some("Arm".to_string()), vec![shoulder, elbow], Some(shoulder)).unwrap();
+    ///
+    /// // A tube from y = 0 to y = 2, blending from the shoulder to the elbow around y = 1
+    /// let (positions, indices, normals, uvs) = generate_cylinder(0.2, 0.2, 2.0, 12, 8, false);
+    /// let positions: Vec<_> = positions.iter()
+    ///     .map(|p| mesh_tools::compat::point3::new(p.x, p.y + 1.0, p.z))
+    ///     .collect();
+    /// let mesh = builder.create_custom_mesh(Some("Arm".to_string()), &positions, &indices,
+    ///                                       Some(normals), Some(vec![uvs]), None);
+    ///
+    /// let joints = vec![[0, 1, 0, 0]; positions.len()];
+    /// let weights: Vec<[f32; 4]> = positions.iter()
+    ///     .map(|p| {
+    ///         let t = (p.y - 0.5).clamp(0.0, 1.0);
+    ///         [1.0 - t, t, 0.0, 0.0]
+    ///     })
+    ///     .collect();
+    /// builder.add_skin_attributes(mesh, 0, &joints, &weights).unwrap();
+    ///
+    /// let arm = builder.add_node(Some("Arm".to_string()), Some(mesh), None, None, None);
+    /// builder.attach_skin(arm, skin).unwrap();
+    /// builder.add_scene(None, Some(vec![shoulder, arm]));
+    ///
+    /// // Bend the elbow
+    /// let animation = builder.add_animation(Some("Bend".to_string()));
+    /// builder.create_rotation_animation(
+    ///     animation,
+    ///     elbow,
+    ///     vec![0.0, 1.0],
+    ///     vec![[0.0, 0.0, 0.0, 1.0], [0.0, 0.0, 0.7071068, 0.7071068]],
+    ///     InterpolationType::Linear,
+    /// ).unwrap();
+    /// ```
+    pub fn add_skin(&mut self, name: Option<String>, joints: Vec<NodeId>,
+                    skeleton: Option<NodeId>) -> Result<SkinId> {
+        self.check_joints(&joints, skeleton)?;
+        
+        let mut inverse_bind_matrices = Vec::with_capacity(joints.len());
+        for joint in joints.iter().map(|joint| joint.index()) {
+            let world = transform::world_matrix(&self.gltf, joint).ok_or_else(|| {
+                GltfError::InvalidData(format!("Joint node {} is part of a cycle", joint))
+            })?;
+            let inverse = transform::invert(&world).ok_or_else(|| {
+                GltfError::InvalidData(format!("Joint node {} has a singular transform", joint))
+            })?;
+            inverse_bind_matrices.push(inverse);
+        }
+        
+        self.add_skin_with_inverse_bind_matrices(name, joints, skeleton, &inverse_bind_matrices)
+    }
+    
+    /// Add a skin with explicit inverse bind matrices
+    ///
+    /// # Parameters
+    /// * `name` - Optional name for the skin
+    /// * `joints` - Indices of the joint nodes; `JOINTS_0` values index into this list
+    /// * `skeleton` - Optional index of the common root of the joint hierarchy
+    /// * `inverse_bind_matrices` - One column-major matrix per joint
+    ///
+    /// # Returns
+    /// The index of the created skin, or an error if the joints are missing or duplicated,
+    /// or the number of matrices does not match the number of joints
+    pub fn add_skin_with_inverse_bind_matrices(&mut self, name: Option<String>, joints: Vec<NodeId>,
+                                               skeleton: Option<NodeId>,
+                                               inverse_bind_matrices: &[[f32; 16]]) -> Result<SkinId> {
+        self.check_joints(&joints, skeleton)?;
+        
+        if inverse_bind_matrices.len() != joints.len() {
+            return Err(GltfError::InvalidData(format!(
+                "Skin has {} joints but {} inverse bind matrices",
+                joints.len(), inverse_bind_matrices.len()
+            )));
+        }
+        
+        let matrix_data: Vec<u8> = inverse_bind_matrices.iter()
+            .flatten()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        let (offset, length) = self.add_buffer_data(&matrix_data);
+        let buffer_view = self.add_buffer_view(offset, length, None);
+        let accessor = self.add_accessor(
+            buffer_view,
+            component_type::FLOAT,
+            inverse_bind_matrices.len(),
+            accessor_type::MAT4.to_string(),
+            None,
+            None,
+            None
+        );
+        
+        let skin = Skin {
+            name,
+            inverse_bind_matrices: Some(accessor),
+            skeleton: skeleton.map(NodeId::index),
+            joints: joints.into_iter().map(NodeId::index).collect(),
+        };
+        
+        if let Some(skins) = &mut self.gltf.skins {
+            let index = skins.len();
+            skins.push(skin);
+            Ok(SkinId::from_index(index))
+        } else {
+            self.gltf.skins = Some(vec![skin]);
+            Ok(SkinId::from_index(0))
+        }
+    }
+    
+    /// Attach a skin to a node with a skinned mesh
+    pub fn attach_skin(&mut self, node: NodeId, skin: SkinId) -> Result<()> {
+        let skin_count = self.gltf.skins.as_ref().map_or(0, |skins| skins.len());
+        if skin.index() >= skin_count {
+            return Err(GltfError::InvalidIndex);
+        }
+        
+        let node_index = node.index();
+        let node = self.gltf.nodes.as_mut()
+            .and_then(|nodes| nodes.get_mut(node_index))
+            .ok_or(GltfError::InvalidIndex)?;
+        
+        if node.mesh.is_none() {
+            return Err(GltfError::InvalidData(format!(
+                "Node {} has no mesh to skin", node_index
+            )));
+        }
+        node.skin = Some(skin.index());
+        
+        Ok(())
+    }
+    
+    /// Add joint indices and weights to a primitive as `JOINTS_0` and `WEIGHTS_0`
+    ///
+    /// Joint indices are stored as unsigned bytes when they all fit, and as unsigned shorts
+    /// otherwise. The weights of each vertex are normalized to sum to one.
+    ///
+    /// # Parameters
+    /// * `mesh` - The mesh containing the primitive
+    /// * `primitive_index` - The primitive within the mesh
+    /// * `joints` - Four indices into the skin's joint list per vertex
+    /// * `weights` - Four weights per vertex; unused influences should have a weight of zero
+    ///
+    /// # Returns
+    /// An error if the primitive does not exist, the number of values does not match the
+    /// number of vertices, or a vertex has negative or only zero weights
+    pub fn add_skin_attributes(&mut self, mesh: MeshId, primitive_index: usize,
+                               joints: &[[u16; 4]], weights: &[[f32; 4]]) -> Result<()> {
+        let vertex_count = self.primitive_vertex_count(mesh, primitive_index)?;
+        
+        if joints.len() != vertex_count || weights.len() != vertex_count {
+            return Err(GltfError::InvalidData(format!(
+                "Primitive has {} vertices but {} joint sets and {} weight sets",
+                vertex_count, joints.len(), weights.len()
+            )));
+        }
+        
+        let mut normalized_weights = Vec::with_capacity(weights.len());
+        for (vertex, weight) in weights.iter().enumerate() {
+            let sum: f32 = weight.iter().sum();
+            if weight.iter().any(|&w| w < 0.0) || sum <= 0.0 {
+                return Err(GltfError::InvalidData(format!(
+                    "Vertex {} must have non-negative weights with a positive sum", vertex
+                )));
+            }
+            normalized_weights.push(weight.map(|w| w / sum));
+        }
+        
+        // Use the smallest component type that can hold every joint index
+        let max_joint = joints.iter().flatten().copied().max().unwrap_or(0);
+        let (joint_data, joint_component): (Vec<u8>, usize) = if max_joint <= u8::MAX as u16 {
+            (joints.iter().flatten().map(|&joint| joint as u8).collect(), component_type::UNSIGNED_BYTE)
+        } else {
+            (joints.iter().flatten().flat_map(|joint| joint.to_le_bytes()).collect(), component_type::UNSIGNED_SHORT)
+        };
+        
+        let (joint_offset, joint_length) = self.add_buffer_data(&joint_data);
+        let joint_buffer_view = self.add_buffer_view(joint_offset, joint_length, Some(buffer_view_target::ARRAY_BUFFER));
+        let joint_accessor = self.add_accessor(
+            joint_buffer_view,
+            joint_component,
+            vertex_count,
+            accessor_type::VEC4.to_string(),
+            None,
+            None,
+            None
+        );
+        
+        let weight_data: Vec<u8> = normalized_weights.iter()
+            .flatten()
+            .flat_map(|weight| weight.to_le_bytes())
+            .collect();
+        let (weight_offset, weight_length) = self.add_buffer_data(&weight_data);
+        let weight_buffer_view = self.add_buffer_view(weight_offset, weight_length, Some(buffer_view_target::ARRAY_BUFFER));
+        let weight_accessor = self.add_accessor(
+            weight_buffer_view,
+            component_type::FLOAT,
+            vertex_count,
+            accessor_type::VEC4.to_string(),
+            None,
+            None,
+            None
+        );
+        
+        let primitive = &mut self.gltf.meshes.as_mut().unwrap()[mesh.index()].primitives[primitive_index];
+        primitive.attributes.insert("JOINTS_0".to_string(), joint_accessor);
+        primitive.attributes.insert("WEIGHTS_0".to_string(), weight_accessor);
+        
+        Ok(())
+    }
+    
+    /// Check that the joints of a skin are existing, distinct nodes
+    fn check_joints(&self, joints: &[NodeId], skeleton: Option<NodeId>) -> Result<()> {
+        let node_count = self.gltf.nodes.as_ref().map_or(0, |nodes| nodes.len());
+        
+        if joints.is_empty() {
+            return Err(GltfError::InvalidData("A skin needs at least one joint".to_string()));
+        }
+        if joints.iter().chain(skeleton.iter()).any(|node| node.index() >= node_count) {
+            return Err(GltfError::InvalidIndex);
+        }
+        for (i, joint) in joints.iter().enumerate() {
+            if joints[..i].contains(joint) {
+                return Err(GltfError::InvalidData(format!(
+                    "Node {} is listed more than once as a joint", joint.index()
+                )));
+            }
+        }
+        
+        Ok(())
+    }
+}
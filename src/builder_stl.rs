@@ -0,0 +1,96 @@
+//! # STL Export Implementation
+//!
+//! This module implements exporting the scene held by a `GltfBuilder` to STL for 3D printing.
+//! Every triangle primitive reachable from the default scene is transformed into world space
+//! and written as a facet; materials, texture coordinates and vertex normals are dropped.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::builder::GltfBuilder;
+use crate::error::{GltfError, Result};
+use crate::stl::{self, Facet, StlFormat};
+use crate::transform;
+
+impl GltfBuilder {
+    /// Export all triangles in the scene, with world transforms applied, as an STL file
+    ///
+    /// # Parameters
+    /// * `path` - Output file path
+    /// * `format` - Binary or ASCII STL
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::GltfBuilder;
+    /// use mesh_tools::stl::StlFormat;
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// let sphere = builder.create_sphere(1.0, 32, 16, None);
+    /// let node = builder.add_node(None, Some(sphere), None, None, Some([10.0, 10.0, 10.0]));
+    /// builder.add_scene(None, Some(vec![node]));
+    ///
+    /// let path = std::env::temp_dir().join("export_stl_sphere.stl");
+    /// builder.export_stl(path.to_str().unwrap(), StlFormat::Binary).unwrap();
+    /// ```
+    pub fn export_stl(&self, path: &str, format: StlFormat) -> Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_stl(&mut writer, format)?;
+        writer.flush()?;
+        
+        Ok(())
+    }
+    
+    /// Write all triangles in the scene, with world transforms applied, as STL data
+    ///
+    /// The ASCII encoding uses the name of the exported scene as the solid name.
+    pub fn write_stl<W: Write>(&self, writer: &mut W, format: StlFormat) -> Result<()> {
+        let name = self.gltf.scenes.as_ref()
+            .and_then(|scenes| scenes.get(self.gltf.scene.unwrap_or(0)))
+            .and_then(|scene| scene.name.as_deref())
+            .unwrap_or("mesh");
+        
+        stl::write_stl(writer, name, &self.world_facets()?, format)
+    }
+    
+    /// Collect every triangle reachable from the default scene in world space
+    fn world_facets(&self) -> Result<Vec<Facet>> {
+        let reader = self.accessor_reader();
+        let nodes = self.gltf.nodes.as_deref().unwrap_or(&[]);
+        let meshes = self.gltf.meshes.as_deref().unwrap_or(&[]);
+        let mut facets = Vec::new();
+        
+        for (node_index, world) in transform::mesh_instances(&self.gltf) {
+            let mesh = nodes[node_index].mesh
+                .and_then(|mesh| meshes.get(mesh))
+                .ok_or(GltfError::InvalidIndex)?;
+            
+            // Mirroring transforms flip the winding order
+            let flip = transform::determinant3(&world) < 0.0;
+            
+            for primitive in &mesh.primitives {
+                let triangles = reader.read_primitive_triangles(primitive)?;
+                if triangles.is_empty() {
+                    continue;
+                }
+                
+                let position_accessor = primitive.attributes.get("POSITION")
+                    .ok_or_else(|| GltfError::InvalidData("Primitive has no POSITION attribute".to_string()))?;
+                let positions: Vec<[f32; 3]> = reader.read_positions(*position_accessor)?
+                    .iter()
+                    .map(|p| transform::transform_point(&world, [p.x, p.y, p.z]))
+                    .collect();
+                
+                for triangle in &triangles {
+                    let corner = |index: u32| positions.get(index as usize).copied().ok_or_else(|| {
+                        GltfError::InvalidData(format!("Vertex index {} exceeds vertex count {}", index, positions.len()))
+                    });
+                    
+                    let (b, c) = if flip { (triangle.c, triangle.b) } else { (triangle.b, triangle.c) };
+                    facets.push([corner(triangle.a)?, corner(b)?, corner(c)?]);
+                }
+            }
+        }
+        
+        Ok(facets)
+    }
+}
@@ -1,24 +1,26 @@
 use crate::builder::GltfBuilder;
 use crate::constants::{sampler_filter, sampler_wrap};
 use crate::error::Result;
+use crate::handle::{MaterialId, SamplerId, TextureId};
 use crate::models::{Image, Sampler, Texture};
 use crate::texture;
 use image::DynamicImage;
 
 impl GltfBuilder {
     /// Add a material with a texture to the glTF document
+    #[allow(clippy::too_many_arguments)]
     pub fn add_textured_material(&mut self, name: Option<String>, 
-                               base_color_texture: Option<usize>,
-                               metallic_roughness_texture: Option<usize>,
-                               normal_texture: Option<usize>,
-                               occlusion_texture: Option<usize>,
-                               emissive_texture: Option<usize>,
+                               base_color_texture: Option<TextureId>,
+                               metallic_roughness_texture: Option<TextureId>,
+                               normal_texture: Option<TextureId>,
+                               occlusion_texture: Option<TextureId>,
+                               emissive_texture: Option<TextureId>,
                                emissive_factor: Option<[f32; 3]>,
                                metallic_factor: Option<f32>,
                                roughness_factor: Option<f32>,
                                alpha_mode: Option<String>,
                                alpha_cutoff: Option<f32>,
-                               double_sided: Option<bool>) -> usize {
+                               double_sided: Option<bool>) -> MaterialId {
         use crate::material;
         
         let material = material::create_textured_material(
@@ -36,19 +38,12 @@ impl GltfBuilder {
             double_sided
         );
         
-        if let Some(materials) = &mut self.gltf.materials {
-            let index = materials.len();
-            materials.push(material);
-            index
-        } else {
-            self.gltf.materials = Some(vec![material]);
-            0
-        }
+        self.add_material_direct(material)
     }
     
     /// Create a basic textured material
     pub fn create_textured_material(&mut self, name: Option<String>, 
-                                  base_color_texture: usize) -> usize {
+                                  base_color_texture: TextureId) -> MaterialId {
         self.add_textured_material(
             name,
             Some(base_color_texture),
@@ -75,19 +70,19 @@ impl GltfBuilder {
     }
     
     /// Create a default texture sampler with reasonable settings
-    pub fn create_default_sampler(&mut self) -> usize {
-        self.add_sampler(
+    pub fn create_default_sampler(&mut self) -> SamplerId {
+        SamplerId::from_index(self.add_sampler(
             Some(sampler_filter::LINEAR),
             Some(sampler_filter::LINEAR_MIPMAP_LINEAR),
             Some(sampler_wrap::REPEAT),
             Some(sampler_wrap::REPEAT)
-        )
+        ))
     }
     
     /// Create a default texture from a DynamicImage (uses default sampler)
     pub fn create_texture_from_image(&mut self, name: Option<String>, 
                                    image: &DynamicImage, 
-                                   format: texture::TextureFormat) -> Result<usize> {
+                                   format: texture::TextureFormat) -> Result<TextureId> {
         // Add the image
         let image_index = self.add_image_from_dynamic_image(name.clone(), image, format)?;
         
@@ -104,7 +99,7 @@ impl GltfBuilder {
                                      height: u32, 
                                      cell_size: u32,
                                      color1: [u8; 3],
-                                     color2: [u8; 3]) -> Result<usize> {
+                                     color2: [u8; 3]) -> Result<TextureId> {
         let image = texture::create_colored_checkerboard(
             width, 
             height, 
@@ -117,7 +112,7 @@ impl GltfBuilder {
     }
     
     /// Create a UV test pattern texture (for testing)
-    pub fn create_uv_test_texture(&mut self, width: u32, height: u32) -> Result<usize> {
+    pub fn create_uv_test_texture(&mut self, width: u32, height: u32) -> Result<TextureId> {
         let image = texture::create_uv_test_pattern(width, height);
         
         self.create_texture_from_image(Some("uv_test".to_string()), &image, texture::TextureFormat::PNG)
@@ -127,10 +122,10 @@ impl GltfBuilder {
     pub(crate) fn add_sampler(&mut self, mag_filter: Option<usize>, min_filter: Option<usize>,
                       wrap_s: Option<usize>, wrap_t: Option<usize>) -> usize {
         let sampler = Sampler {
-            mag_filter: mag_filter,
-            min_filter: min_filter,
-            wrap_s: wrap_s,
-            wrap_t: wrap_t,
+            mag_filter,
+            min_filter,
+            wrap_s,
+            wrap_t,
         };
         
         if let Some(samplers) = &mut self.gltf.samplers {
@@ -172,20 +167,20 @@ impl GltfBuilder {
     
     /// Add a texture to the glTF document
     pub(crate) fn add_texture(&mut self, name: Option<String>, source: usize, 
-                      sampler: Option<usize>) -> usize {
+                      sampler: Option<SamplerId>) -> TextureId {
         let texture = Texture {
             name,
             source,
-            sampler,
+            sampler: sampler.map(SamplerId::index),
         };
         
         if let Some(textures) = &mut self.gltf.textures {
             let index = textures.len();
             textures.push(texture);
-            index
+            TextureId::from_index(index)
         } else {
             self.gltf.textures = Some(vec![texture]);
-            0
+            TextureId::from_index(0)
         }
     }
 }
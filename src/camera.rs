@@ -0,0 +1,138 @@
+//! # Camera Creation
+//!
+//! This module provides functions for creating glTF cameras and for orienting the nodes
+//! that carry them.
+//!
+//! A glTF camera only describes a projection. Its position and orientation come from the node
+//! it is attached to: the camera looks down the node's local -Z axis, with +Y up and +X to the
+//! right. `look_at_rotation` computes the node rotation that points a camera at a target.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use mesh_tools::camera;
+//!
+//! // A 60 degree perspective camera with an infinite far plane
+//! let perspective = camera::create_perspective_camera(
+//!     Some("Main".to_string()),
+//!     60.0_f32.to_radians(),
+//!     Some(16.0 / 9.0),
+//!     0.1,
+//!     None,
+//! );
+//!
+//! // Rotation for a camera at (0, 5, 10) looking at the origin
+//! let rotation = camera::look_at_rotation([0.0, 5.0, 10.0], [0.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+//! ```
+
+use crate::models::{Camera, CameraOrthographic, CameraPerspective};
+
+/// Create a perspective camera
+///
+/// # Parameters
+/// * `name` - Optional name for the camera
+/// * `yfov` - Vertical field of view in radians
+/// * `aspect_ratio` - Optional aspect ratio (width / height); viewers use the viewport's when omitted
+/// * `znear` - Distance to the near clipping plane
+/// * `zfar` - Optional distance to the far clipping plane; omit for an infinite projection
+pub fn create_perspective_camera(
+    name: Option<String>,
+    yfov: f32,
+    aspect_ratio: Option<f32>,
+    znear: f32,
+    zfar: Option<f32>,
+) -> Camera {
+    Camera {
+        name,
+        type_: "perspective".to_string(),
+        perspective: Some(CameraPerspective {
+            aspect_ratio,
+            yfov,
+            zfar,
+            znear,
+        }),
+        orthographic: None,
+    }
+}
+
+/// Create an orthographic camera
+///
+/// # Parameters
+/// * `name` - Optional name for the camera
+/// * `xmag` - Half the width of the view volume
+/// * `ymag` - Half the height of the view volume
+/// * `znear` - Distance to the near clipping plane
+/// * `zfar` - Distance to the far clipping plane
+pub fn create_orthographic_camera(
+    name: Option<String>,
+    xmag: f32,
+    ymag: f32,
+    znear: f32,
+    zfar: f32,
+) -> Camera {
+    Camera {
+        name,
+        type_: "orthographic".to_string(),
+        perspective: None,
+        orthographic: Some(CameraOrthographic {
+            xmag,
+            ymag,
+            zfar,
+            znear,
+        }),
+    }
+}
+
+/// Compute the rotation quaternion `[x, y, z, w]` that points a camera at a target
+///
+/// The node's -Z axis is turned towards `target` and its +Y axis towards `up` as far as
+/// possible. If `up` is parallel to the viewing direction, another up vector is chosen.
+///
+/// # Parameters
+/// * `eye` - Position of the camera
+/// * `target` - Point to look at
+/// * `up` - Approximate up direction, usually `[0.0, 1.0, 0.0]`
+///
+/// # Returns
+/// The rotation for the node carrying the camera, or the identity rotation if `eye` and
+/// `target` coincide
+pub fn look_at_rotation(eye: [f32; 3], target: [f32; 3], up: [f32; 3]) -> [f32; 4] {
+    use crate::compat::{cross, dot, normalize, vector3};
+    
+    let forward = vector3::new(target[0] - eye[0], target[1] - eye[1], target[2] - eye[2]);
+    if dot(forward, forward) == 0.0 {
+        return [0.0, 0.0, 0.0, 1.0];
+    }
+    
+    // The camera looks down -Z, so the local Z axis points away from the target
+    let z = normalize(vector3::new(-forward.x, -forward.y, -forward.z));
+    
+    let mut x = cross(vector3::new(up[0], up[1], up[2]), z);
+    if dot(x, x) < 1e-12 {
+        // Up is parallel to the viewing direction, so fall back to another axis
+        let fallback = if z.y.abs() < 0.9 { vector3::new(0.0, 1.0, 0.0) } else { vector3::new(0.0, 0.0, -1.0) };
+        x = cross(fallback, z);
+    }
+    let x = normalize(x);
+    let y = cross(z, x);
+    
+    // Convert the rotation matrix with columns x, y, z to a quaternion
+    let trace = x.x + y.y + z.z;
+    let (qx, qy, qz, qw) = if trace > 0.0 {
+        let s = (trace + 1.0).sqrt() * 2.0;
+        ((y.z - z.y) / s, (z.x - x.z) / s, (x.y - y.x) / s, 0.25 * s)
+    } else if x.x > y.y && x.x > z.z {
+        let s = (1.0 + x.x - y.y - z.z).sqrt() * 2.0;
+        (0.25 * s, (y.x + x.y) / s, (z.x + x.z) / s, (y.z - z.y) / s)
+    } else if y.y > z.z {
+        let s = (1.0 + y.y - x.x - z.z).sqrt() * 2.0;
+        ((y.x + x.y) / s, 0.25 * s, (z.y + y.z) / s, (z.x - x.z) / s)
+    } else {
+        let s = (1.0 + z.z - x.x - y.y).sqrt() * 2.0;
+        ((z.x + x.z) / s, (z.y + y.z) / s, 0.25 * s, (x.y - y.x) / s)
+    };
+    
+    let length = (qx * qx + qy * qy + qz * qz + qw * qw).sqrt();
+    
+    [qx / length, qy / length, qz / length, qw / length]
+}
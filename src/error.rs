@@ -9,6 +9,8 @@
 //! - JSON serialization/deserialization
 //! - Invalid data or parameters
 //! - Texture processing issues
+//! - Inconsistent animation keyframes
+//! - Documents failing validation before export
 //!
 //! The module also provides a convenient `Result` type alias for functions
 //! that may return a `GltfError`.
@@ -16,6 +18,7 @@
 use std::io;
 use thiserror::Error;
 use crate::texture;
+use crate::validation::ValidationReport;
 
 /// Comprehensive error type for glTF export operations
 #[derive(Error, Debug)]
@@ -34,6 +37,21 @@ pub enum GltfError {
     
     #[error("Texture error: {0:?}")]
     Texture(#[from] texture::TextureError),
+    
+    #[error("Animation has {timestamps} timestamps but {values} keyframe values")]
+    KeyframeCountMismatch { timestamps: usize, values: usize },
+    
+    #[error("Keyframe timestamps must be non-negative and strictly increasing (keyframe {index})")]
+    NonIncreasingTimestamps { index: usize },
+    
+    #[error("Node {0} does not exist")]
+    MissingNode(usize),
+    
+    #[error("CUBICSPLINE animation with {keyframes} keyframes needs an in-tangent, value and out-tangent per keyframe, but got {values} values")]
+    MissingCubicSplineTangents { keyframes: usize, values: usize },
+    
+    #[error("Validation failed:\n{0}")]
+    Validation(ValidationReport),
 }
 
 /// Result type for glTF export operations
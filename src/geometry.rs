@@ -0,0 +1,534 @@
+//! # Vertex Attribute Generation
+//!
+//! This module derives vertex attributes from mesh geometry: normals for meshes that have none,
+//! and tangents for normal mapping.
+//!
+//! `generate_normals` supports several shading styles, selected with `NormalMode`:
+//!
+//! - Smooth normals average the normals of the triangles around each position, weighted by
+//!   triangle area or by the angle of the triangle at the vertex. Vertices at the same position,
+//!   such as the two sides of a UV seam, get the same normal.
+//! - Flat normals give every triangle its own face normal, which splits shared vertices.
+//! - Auto-smoothing only averages across edges where the faces meet at less than a crease
+//!   angle, so curved surfaces look smooth while sharp edges stay faceted.
+//!
+//! Modes that split vertices return the new geometry together with the original vertex of
+//! each new vertex, so that other attributes can be carried over with `GeneratedNormals::remap`.
+//!
+//! `generate_tangents` computes the `TANGENT` attribute needed for normal mapping. It follows
+//! the MikkTSpace algorithm used by Blender, Substance and most glTF viewers, so normal maps
+//! baked against MikkTSpace tangents are displayed as intended:
+//!
+//! - Vertices with identical position, normal and texture coordinate are welded.
+//! - Every triangle corner contributes the triangle's UV tangent, projected onto the plane of
+//!   the corner's normal and weighted by the angle of the corner.
+//! - The handedness (`w`) is `1.0` where the UV mapping preserves orientation and `-1.0` where
+//!   it is mirrored, so that `bitangent = cross(normal, tangent.xyz) * w`. As in MikkTSpace,
+//!   the bitangent points towards increasing `1 - v`, since glTF's V axis points down.
+//!
+//! MikkTSpace gives a vertex shared by mirrored and non-mirrored triangles two tangent spaces.
+//! A single vertex can only store one tangent, so such vertices get the tangent space of the
+//! side with the larger total corner angle; meshes exported for normal mapping normally have
+//! their vertices split along UV mirror seams already.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use mesh_tools::geometry::{generate_normals, NormalMode};
+//! use mesh_tools::primitives::generate_icosahedron;
+//!
+//! // Faceted shading for a low-poly shape
+//! let (positions, indices, _, uvs) = generate_icosahedron(1.0);
+//! let flat = generate_normals(&positions, &indices, NormalMode::Flat).unwrap();
+//! let uvs = flat.remap(&uvs);
+//! assert_eq!(flat.positions.len(), uvs.len());
+//! ```
+//!
+//! ```rust
+//! use mesh_tools::geometry::generate_tangents;
+//! use mesh_tools::primitives::generate_sphere;
+//!
+//! let (positions, indices, normals, uvs) = generate_sphere(1.0, 16, 8);
+//! let tangents = generate_tangents(&positions, &normals, &uvs, &indices).unwrap();
+//! assert_eq!(tangents.len(), positions.len());
+//! ```
+
+use std::collections::HashMap;
+
+use crate::builder_primitives::Triangle;
+use crate::compat::{cross, dot, vector3, Point3, Vector2, Vector3};
+use crate::error::{GltfError, Result};
+
+/// How `generate_normals` computes vertex normals
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NormalMode {
+    /// Smooth normals, with each triangle weighted by its area
+    SmoothArea,
+    /// Smooth normals, with each triangle weighted by its angle at the vertex, which does not
+    /// depend on how the surface is triangulated
+    SmoothAngle,
+    /// One normal per triangle, splitting vertices shared by triangles facing different ways
+    Flat,
+    /// Smooth normals that stay sharp across edges where the faces meet at an angle larger
+    /// than `crease_angle` (in radians); triangles are weighted by their angle at the vertex.
+    /// A crease angle of PI or more smooths every edge.
+    AutoSmooth {
+        /// Largest angle between face normals that is still smoothed
+        crease_angle: f32,
+    },
+}
+
+/// Geometry with generated normals
+///
+/// Modes that split vertices produce more vertices than the input; `source_vertices` maps each
+/// vertex back to the input vertex it was copied from.
+#[derive(Debug, Clone)]
+pub struct GeneratedNormals {
+    /// Vertex positions
+    pub positions: Vec<Point3<f32>>,
+    /// Triangles indexing the new vertices
+    pub indices: Vec<Triangle>,
+    /// One unit normal per vertex
+    pub normals: Vec<Vector3<f32>>,
+    /// The input vertex of each vertex
+    pub source_vertices: Vec<u32>,
+}
+
+impl GeneratedNormals {
+    /// Carry a per-vertex attribute of the input geometry over to the generated vertices
+    ///
+    /// # Panics
+    /// Panics if `values` has fewer entries than the input geometry had vertices.
+    pub fn remap<T: Copy>(&self, values: &[T]) -> Vec<T> {
+        self.source_vertices.iter().map(|&vertex| values[vertex as usize]).collect()
+    }
+}
+
+/// Generate vertex normals for an indexed triangle mesh
+///
+/// # Parameters
+/// * `positions` - Vertex positions
+/// * `indices` - List of triangles, each containing three vertex indices
+/// * `mode` - How to compute the normals
+///
+/// # Returns
+/// The geometry with normals, which has new vertices for `Flat` and `AutoSmooth`, or an error
+/// if an index is out of range. Vertices without a non-degenerate triangle get the normal +Y.
+///
+/// # Example
+/// ```
+/// use mesh_tools::geometry::{generate_normals, NormalMode};
+/// use mesh_tools::primitives::generate_cylinder;
+///
+/// // Smooth sides with sharp rims
+/// let (positions, indices, _, _) = generate_cylinder(1.0, 1.0, 2.0, 32, 1, false);
+/// let result = generate_normals(&positions, &indices, NormalMode::AutoSmooth {
+///     crease_angle: 30.0_f32.to_radians(),
+/// }).unwrap();
+/// ```
+pub fn generate_normals(positions: &[Point3<f32>], indices: &[Triangle], mode: NormalMode) -> Result<GeneratedNormals> {
+    check_indices(indices, positions.len())?;
+    
+    match mode {
+        NormalMode::SmoothArea | NormalMode::SmoothAngle => {
+            let by_angle = mode == NormalMode::SmoothAngle;
+            Ok(GeneratedNormals {
+                positions: positions.to_vec(),
+                indices: indices.to_vec(),
+                normals: smooth_normals(positions, indices, by_angle),
+                source_vertices: (0..positions.len() as u32).collect(),
+            })
+        }
+        NormalMode::Flat => Ok(split_normals(positions, indices, None)),
+        NormalMode::AutoSmooth { crease_angle } => {
+            // A crease angle of PI or more smooths across every edge
+            let min_cos = crease_angle.clamp(0.0, std::f32::consts::PI).cos();
+            Ok(split_normals(positions, indices, Some(min_cos)))
+        }
+    }
+}
+
+/// Compute smooth normals for every vertex, keeping the vertices as they are
+fn smooth_normals(positions: &[Point3<f32>], indices: &[Triangle], by_angle: bool) -> Vec<Vector3<f32>> {
+    let groups = position_groups(positions);
+    let mut sums = vec![vector3::new(0.0, 0.0, 0.0); positions.len()];
+    
+    for triangle in indices {
+        let corners = [triangle.a as usize, triangle.b as usize, triangle.c as usize];
+        let face_normal = face_normal(positions, &corners);
+        
+        for corner in 0..3 {
+            // The unnormalized face normal is already weighted by twice the triangle area
+            let contribution = if by_angle {
+                normalize_or_none(face_normal)
+                    .map_or(face_normal, |normal| scale(normal, corner_angle(positions, &corners, corner)))
+            } else {
+                face_normal
+            };
+            let group = groups[corners[corner]];
+            sums[group] = add(sums[group], contribution);
+        }
+    }
+    
+    // Vertices only used by degenerate triangles have no direction to point in; +Y is as good as any
+    (0..positions.len())
+        .map(|vertex| normalize_or_none(sums[groups[vertex]]).unwrap_or(vector3::new(0.0, 1.0, 0.0)))
+        .collect()
+}
+
+/// Compute a normal for every triangle corner from the angle-weighted unit normals of the
+/// triangles around the same position whose normals are within the crease threshold, then
+/// merge corners of the same vertex that ended up with the same normal
+///
+/// `min_cos` is the cosine of the crease angle; `None` gives flat normals.
+fn split_normals(positions: &[Point3<f32>], indices: &[Triangle], min_cos: Option<f32>) -> GeneratedNormals {
+    let groups = position_groups(positions);
+    let face_normals: Vec<Option<Vector3<f32>>> = indices.iter()
+        .map(|t| normalize_or_none(face_normal(positions, &[t.a as usize, t.b as usize, t.c as usize])))
+        .collect();
+    
+    // Triangle corners around each position
+    let mut incident: HashMap<usize, Vec<(usize, usize)>> = HashMap::new();
+    for (face, triangle) in indices.iter().enumerate() {
+        for (corner, vertex) in [triangle.a, triangle.b, triangle.c].into_iter().enumerate() {
+            incident.entry(groups[vertex as usize]).or_default().push((face, corner));
+        }
+    }
+    
+    let mut result = GeneratedNormals {
+        positions: Vec::new(),
+        indices: Vec::with_capacity(indices.len()),
+        normals: Vec::new(),
+        source_vertices: Vec::new(),
+    };
+    let mut vertex_lookup: HashMap<(u32, [u32; 3]), u32> = HashMap::new();
+    
+    for (face, triangle) in indices.iter().enumerate() {
+        let corners = [triangle.a, triangle.b, triangle.c];
+        let mut new_corners = [0u32; 3];
+        
+        for (corner, &vertex) in corners.iter().enumerate() {
+            let normal = match (face_normals[face], min_cos) {
+                (Some(normal), None) => normal,
+                (Some(normal), Some(min_cos)) => {
+                    let mut sum = vector3::new(0.0, 0.0, 0.0);
+                    for &(other_face, other_corner) in &incident[&groups[vertex as usize]] {
+                        if let Some(other_normal) = face_normals[other_face] {
+                            if dot(normal, other_normal) >= min_cos {
+                                let other_corners = [indices[other_face].a as usize, indices[other_face].b as usize, indices[other_face].c as usize];
+                                let weight = corner_angle(positions, &other_corners, other_corner);
+                                sum = add(sum, scale(other_normal, weight));
+                            }
+                        }
+                    }
+                    normalize_or_none(sum).unwrap_or(normal)
+                }
+                // Degenerate triangles have no direction, so use the same +Y as `smooth_normals`
+                (None, _) => vector3::new(0.0, 1.0, 0.0),
+            };
+            
+            let key = (vertex, [normal.x, normal.y, normal.z].map(f32::to_bits));
+            new_corners[corner] = *vertex_lookup.entry(key).or_insert_with(|| {
+                result.positions.push(positions[vertex as usize]);
+                result.normals.push(normal);
+                result.source_vertices.push(vertex);
+                (result.positions.len() - 1) as u32
+            });
+        }
+        
+        result.indices.push(Triangle::new(new_corners[0], new_corners[1], new_corners[2]));
+    }
+    
+    result
+}
+
+/// Map every vertex to the first vertex with the same position
+fn position_groups(positions: &[Point3<f32>]) -> Vec<usize> {
+    let mut first: HashMap<[u32; 3], usize> = HashMap::new();
+    
+    positions.iter().enumerate()
+        .map(|(i, p)| *first.entry([p.x, p.y, p.z].map(f32::to_bits)).or_insert(i))
+        .collect()
+}
+
+/// Unnormalized face normal of a triangle, whose length is twice its area
+fn face_normal(positions: &[Point3<f32>], corners: &[usize; 3]) -> Vector3<f32> {
+    let p1 = positions[corners[0]];
+    cross(sub(positions[corners[1]], p1), sub(positions[corners[2]], p1))
+}
+
+/// Angle of a triangle at one of its corners
+fn corner_angle(positions: &[Point3<f32>], corners: &[usize; 3], corner: usize) -> f32 {
+    let p = positions[corners[corner]];
+    let previous = positions[corners[(corner + 2) % 3]];
+    let next = positions[corners[(corner + 1) % 3]];
+    
+    match (normalize_or_none(sub(previous, p)), normalize_or_none(sub(next, p))) {
+        (Some(v1), Some(v2)) => dot(v1, v2).clamp(-1.0, 1.0).acos(),
+        _ => 0.0,
+    }
+}
+
+/// Check that every triangle index refers to a vertex
+fn check_indices(indices: &[Triangle], vertex_count: usize) -> Result<()> {
+    if let Some(index) = indices.iter()
+        .flat_map(|t| [t.a, t.b, t.c])
+        .find(|&index| index as usize >= vertex_count) {
+        return Err(GltfError::InvalidData(format!(
+            "Triangle index {} is out of range for {} vertices", index, vertex_count
+        )));
+    }
+    
+    Ok(())
+}
+
+/// Generate MikkTSpace-compatible tangents for an indexed triangle mesh
+///
+/// # Parameters
+/// * `positions` - Vertex positions
+/// * `normals` - Vertex normals, one per position
+/// * `texcoords` - Texture coordinates the normal map is sampled with, one per position
+/// * `indices` - List of triangles, each containing three vertex indices
+///
+/// # Returns
+/// One tangent `[x, y, z, w]` per vertex, where `w` is the bitangent sign, or an error if the
+/// attribute lengths differ or an index is out of range. Vertices that are not part of a
+/// triangle with a usable UV mapping get an arbitrary tangent perpendicular to their normal.
+pub fn generate_tangents(
+    positions: &[Point3<f32>],
+    normals: &[Vector3<f32>],
+    texcoords: &[Vector2<f32>],
+    indices: &[Triangle],
+) -> Result<Vec<[f32; 4]>> {
+    let vertex_count = positions.len();
+    if normals.len() != vertex_count || texcoords.len() != vertex_count {
+        return Err(GltfError::InvalidData(format!(
+            "Tangent generation needs one normal and texture coordinate per vertex, got {} positions, {} normals and {} texture coordinates",
+            vertex_count, normals.len(), texcoords.len()
+        )));
+    }
+    check_indices(indices, vertex_count)?;
+    
+    // Weld vertices with identical attributes, like MikkTSpace does
+    let mut welded: HashMap<[u32; 8], usize> = HashMap::new();
+    let groups: Vec<usize> = (0..vertex_count)
+        .map(|i| {
+            let p = positions[i];
+            let n = normals[i];
+            let t = texcoords[i];
+            let key = [p.x, p.y, p.z, n.x, n.y, n.z, t.x, t.y].map(f32::to_bits);
+            *welded.entry(key).or_insert(i)
+        })
+        .collect();
+    
+    // Angle-weighted tangent sums per welded vertex, for orientation preserving and mirrored corners
+    let mut sums = vec![[vector3::new(0.0, 0.0, 0.0); 2]; vertex_count];
+    let mut weights = vec![[0.0f32; 2]; vertex_count];
+    
+    for triangle in indices {
+        let corners = [triangle.a as usize, triangle.b as usize, triangle.c as usize];
+        let (p1, p2, p3) = (positions[corners[0]], positions[corners[1]], positions[corners[2]]);
+        let (t1, t2, t3) = (texcoords[corners[0]], texcoords[corners[1]], texcoords[corners[2]]);
+        
+        let d1 = sub(p2, p1);
+        let d2 = sub(p3, p1);
+        // glTF puts the UV origin at the top left, so flip V to get MikkTSpace's bottom-left origin
+        let (t21x, t21y) = (t2.x - t1.x, t1.y - t2.y);
+        let (t31x, t31y) = (t3.x - t1.x, t1.y - t3.y);
+        
+        let signed_area = t21x * t31y - t21y * t31x;
+        if signed_area.abs() <= f32::MIN_POSITIVE {
+            continue;
+        }
+        let orientation = if signed_area > 0.0 { 0 } else { 1 };
+        
+        // Direction of increasing U on the triangle; the unnormalized vector is scaled by the signed UV area
+        let face_tangent = match normalize_or_none(vector3::new(
+            t31y * d1.x - t21y * d2.x,
+            t31y * d1.y - t21y * d2.y,
+            t31y * d1.z - t21y * d2.z,
+        )) {
+            Some(tangent) => scale(tangent, signed_area.signum()),
+            None => continue,
+        };
+        
+        for corner in 0..3 {
+            let vertex = corners[corner];
+            let n = normals[vertex];
+            
+            let tangent = match normalize_or_none(project(face_tangent, n)) {
+                Some(tangent) => tangent,
+                None => continue,
+            };
+            
+            let p = positions[vertex];
+            let previous = positions[corners[(corner + 2) % 3]];
+            let next = positions[corners[(corner + 1) % 3]];
+            let angle = match (normalize_or_none(project(sub(previous, p), n)),
+                               normalize_or_none(project(sub(next, p), n))) {
+                (Some(v1), Some(v2)) => dot(v1, v2).clamp(-1.0, 1.0).acos(),
+                _ => 0.0,
+            };
+            
+            let group = groups[vertex];
+            let sum = &mut sums[group][orientation];
+            *sum = add(*sum, scale(tangent, angle));
+            weights[group][orientation] += angle;
+        }
+    }
+    
+    Ok((0..vertex_count)
+        .map(|vertex| {
+            let group = groups[vertex];
+            let orientation = if weights[group][1] > weights[group][0] { 1 } else { 0 };
+            let n = normals[vertex];
+            
+            let tangent = normalize_or_none(sums[group][orientation])
+                .unwrap_or_else(|| perpendicular(n));
+            let w = if orientation == 0 { 1.0 } else { -1.0 };
+            
+            [tangent.x, tangent.y, tangent.z, w]
+        })
+        .collect())
+}
+
+fn sub(a: Point3<f32>, b: Point3<f32>) -> Vector3<f32> {
+    vector3::new(a.x - b.x, a.y - b.y, a.z - b.z)
+}
+
+fn add(a: Vector3<f32>, b: Vector3<f32>) -> Vector3<f32> {
+    vector3::new(a.x + b.x, a.y + b.y, a.z + b.z)
+}
+
+fn scale(v: Vector3<f32>, s: f32) -> Vector3<f32> {
+    vector3::new(v.x * s, v.y * s, v.z * s)
+}
+
+/// Remove the component of `v` along the normal `n`
+fn project(v: Vector3<f32>, n: Vector3<f32>) -> Vector3<f32> {
+    let d = dot(n, v);
+    vector3::new(v.x - d * n.x, v.y - d * n.y, v.z - d * n.z)
+}
+
+fn normalize_or_none(v: Vector3<f32>) -> Option<Vector3<f32>> {
+    let length = dot(v, v).sqrt();
+    if length > f32::MIN_POSITIVE {
+        Some(scale(v, 1.0 / length))
+    } else {
+        None
+    }
+}
+
+/// Any unit vector perpendicular to `n`, or +X if `n` is zero
+fn perpendicular(n: Vector3<f32>) -> Vector3<f32> {
+    let axis = if n.x.abs() < 0.9 { vector3::new(1.0, 0.0, 0.0) } else { vector3::new(0.0, 1.0, 0.0) };
+    normalize_or_none(cross(cross(n, axis), n)).unwrap_or(vector3::new(1.0, 0.0, 0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compat::{point3, vector2};
+    
+    /// Two triangles meeting at a right angle along a ridge on the Z axis at y = 1
+    fn ridge() -> (Vec<Point3<f32>>, Vec<Triangle>) {
+        let positions = vec![
+            point3::new(0.0, 1.0, 0.0),
+            point3::new(0.0, 1.0, 1.0),
+            point3::new(-1.0, 0.0, 0.0),
+            point3::new(1.0, 0.0, 0.0),
+        ];
+        (positions, vec![Triangle::new(0, 2, 1), Triangle::new(0, 1, 3)])
+    }
+    
+    fn assert_close(actual: Vector3<f32>, expected: [f32; 3]) {
+        let close = [actual.x, actual.y, actual.z].iter().zip(expected).all(|(a, e)| (a - e).abs() < 1e-5);
+        assert!(close, "{:?} != {:?}", actual, expected);
+    }
+    
+    #[test]
+    fn smooth_normals_average_the_faces() {
+        let (positions, indices) = ridge();
+        
+        for mode in [NormalMode::SmoothArea, NormalMode::SmoothAngle] {
+            let result = generate_normals(&positions, &indices, mode).unwrap();
+            assert_eq!(result.positions.len(), 4);
+            assert_close(result.normals[0], [0.0, 1.0, 0.0]);
+            assert_close(result.normals[2], [-0.5_f32.sqrt(), 0.5_f32.sqrt(), 0.0]);
+        }
+    }
+    
+    #[test]
+    fn flat_normals_split_the_ridge() {
+        let (positions, indices) = ridge();
+        
+        let result = generate_normals(&positions, &indices, NormalMode::Flat).unwrap();
+        
+        assert_eq!(result.positions.len(), 6);
+        assert_eq!(result.source_vertices, vec![0, 2, 1, 0, 1, 3]);
+        assert_close(result.normals[0], [-0.5_f32.sqrt(), 0.5_f32.sqrt(), 0.0]);
+        assert_close(result.normals[3], [0.5_f32.sqrt(), 0.5_f32.sqrt(), 0.0]);
+    }
+    
+    #[test]
+    fn auto_smooth_splits_only_beyond_the_crease_angle() {
+        let (positions, indices) = ridge();
+        let auto_smooth = |degrees: f32| {
+            generate_normals(&positions, &indices, NormalMode::AutoSmooth { crease_angle: degrees.to_radians() }).unwrap()
+        };
+        
+        assert_eq!(auto_smooth(30.0).positions.len(), 6);
+        
+        for degrees in [120.0, 180.0, 360.0] {
+            let result = auto_smooth(degrees);
+            assert_eq!(result.positions.len(), 4);
+            assert_close(result.normals[0], [0.0, 1.0, 0.0]);
+        }
+    }
+    
+    #[test]
+    fn degenerate_triangles_get_an_up_normal() {
+        let positions = [point3::new(0.0, 0.0, 0.0), point3::new(1.0, 0.0, 0.0), point3::new(2.0, 0.0, 0.0)];
+        let indices = [Triangle::new(0, 1, 2)];
+        
+        for mode in [NormalMode::SmoothArea, NormalMode::Flat] {
+            let result = generate_normals(&positions, &indices, mode).unwrap();
+            assert_close(result.normals[0], [0.0, 1.0, 0.0]);
+        }
+    }
+    
+    /// A unit quad in the XY plane facing +Z, with the given UV of each corner
+    fn quad_tangents(uvs: [[f32; 2]; 4]) -> Vec<[f32; 4]> {
+        let positions = [
+            point3::new(0.0, 0.0, 0.0),
+            point3::new(1.0, 0.0, 0.0),
+            point3::new(1.0, 1.0, 0.0),
+            point3::new(0.0, 1.0, 0.0),
+        ];
+        let normals = [vector3::new(0.0, 0.0, 1.0); 4];
+        let texcoords = uvs.map(|[u, v]| vector2::new(u, v));
+        let indices = [Triangle::new(0, 1, 2), Triangle::new(0, 2, 3)];
+        
+        generate_tangents(&positions, &normals, &texcoords, &indices).unwrap()
+    }
+    
+    #[test]
+    fn quad_tangents_are_right_handed() {
+        // V points down in glTF, so the top of the quad has v = 0
+        let tangents = quad_tangents([[0.0, 1.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]]);
+        
+        for tangent in tangents {
+            assert_eq!(tangent, [1.0, 0.0, 0.0, 1.0]);
+        }
+    }
+    
+    #[test]
+    fn mirrored_uvs_flip_the_handedness() {
+        // U runs from right to left
+        let tangents = quad_tangents([[1.0, 1.0], [0.0, 1.0], [0.0, 0.0], [1.0, 0.0]]);
+        
+        for tangent in tangents {
+            assert_eq!(tangent, [-1.0, 0.0, 0.0, -1.0]);
+        }
+    }
+}
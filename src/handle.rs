@@ -0,0 +1,116 @@
+//! # Typed Handles
+//!
+//! This module provides the handles returned and accepted by `GltfBuilder`. Every kind of
+//! glTF object has its own handle type wrapping its index in the document, so passing a
+//! texture where a material is expected is a compile error instead of a broken file.
+//!
+//! The glTF data model in the `models` module still uses plain indices, as in the JSON.
+//! `index` converts a handle to the index used there, and `from_index` wraps an index read
+//! from the document, for example after importing a file.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use mesh_tools::{GltfBuilder, NodeId};
+//!
+//! let mut builder = GltfBuilder::new();
+//! let material = builder.create_basic_material(None, [1.0, 0.0, 0.0, 1.0]);
+//! let cube = builder.create_box_with_material(1.0, Some(material));
+//! let node = builder.add_node(None, Some(cube), None, None, None);
+//!
+//! // Look up the node in the document
+//! assert_eq!(builder.gltf.nodes.as_ref().unwrap()[node.index()].mesh, Some(cube.index()));
+//!
+//! // Wrap an index from the document to pass it back to the builder
+//! let first = NodeId::from_index(0);
+//! builder.add_scene(None, Some(vec![first]));
+//! ```
+
+macro_rules! handle {
+    ($(#[$doc:meta])* $name:ident) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(usize);
+        
+        impl $name {
+            /// Wrap an index into the corresponding glTF array
+            pub fn from_index(index: usize) -> Self {
+                Self(index)
+            }
+            
+            /// Get the index into the corresponding glTF array
+            pub fn index(self) -> usize {
+                self.0
+            }
+        }
+        
+        impl From<$name> for usize {
+            fn from(handle: $name) -> usize {
+                handle.0
+            }
+        }
+    };
+}
+
+handle! {
+    /// Handle to a scene
+    SceneId
+}
+
+handle! {
+    /// Handle to a node
+    NodeId
+}
+
+handle! {
+    /// Handle to a mesh
+    MeshId
+}
+
+handle! {
+    /// Handle to a material
+    MaterialId
+}
+
+handle! {
+    /// Handle to a texture, which combines an image with a sampler
+    TextureId
+}
+
+handle! {
+    /// Handle to a texture sampler
+    SamplerId
+}
+
+handle! {
+    /// Handle to an accessor
+    AccessorId
+}
+
+handle! {
+    /// Handle to an animation
+    AnimationId
+}
+
+handle! {
+    /// Handle to a sampler within an animation
+    ///
+    /// Animation samplers are numbered per animation, so a handle is only meaningful for the
+    /// animation it was created in.
+    AnimationSamplerId
+}
+
+handle! {
+    /// Handle to a camera
+    CameraId
+}
+
+handle! {
+    /// Handle to a KHR_lights_punctual light
+    LightId
+}
+
+handle! {
+    /// Handle to a skin
+    SkinId
+}
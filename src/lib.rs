@@ -7,12 +7,24 @@
 //!
 //! ## Key Features
 //! 
-//! - Create and manipulate 3D geometry (vertices, normals, UVs, indices)
+//! - Create and manipulate 3D geometry (vertices, normals, UVs, vertex colors, indices)
 //! - Generate primitive shapes (boxes, spheres, planes, cylinders, etc.)
 //! - Define materials with physically-based rendering (PBR) properties
+//! - Smooth, flat and auto-smoothed normal generation
+//! - MikkTSpace-compatible tangent generation for normal mapping
 //! - Support for textures and image data
 //! - Create complex hierarchical scenes with node parent-child relationships
+//! - Typed handles that keep meshes, nodes, materials and textures from being mixed up
+//! - Perspective and orthographic cameras attached to nodes
+//! - Directional, point and spot lights (KHR_lights_punctual)
+//! - Skinning with joint hierarchies, inverse bind matrices and joint weights
+//! - Morph targets (blend shapes) with animated weights
+//! - Validation against the glTF 2.0 specification, optionally before every export
 //! - Export models in both glTF (JSON+binary) and GLB (single binary) formats
+//! - Import existing glTF/GLB files for further editing and re-export
+//! - Import Wavefront OBJ/MTL assets and export flattened scenes back to OBJ
+//! - Read and write binary and ASCII STL for 3D printing
+//! - Read and write PLY meshes and point clouds with vertex colors and custom properties
 //! - Lightweight math types via the mint crate
 //!
 //! ## Math Types
@@ -70,7 +82,16 @@ pub mod models;      // glTF data model definitions
 pub mod constants;   // glTF format constants
 pub mod compat;      // Compatibility layer for mint math types
 pub mod material;    // Material creation and management
+pub mod camera;      // Camera creation and orientation
+pub mod light;       // KHR_lights_punctual light creation
 pub mod mesh;        // Mesh creation and manipulation
+pub mod geometry;    // Vertex attribute generation (normals, tangents)
+pub mod accessor;    // Typed reading of accessor data
+pub mod obj;         // Wavefront OBJ/MTL parsing
+pub mod stl;         // STL reading and writing
+pub mod ply;         // PLY reading and writing
+pub mod handle;      // Typed handles to document objects
+pub mod validation;  // glTF 2.0 document validation
 pub mod builder;     // Main GltfBuilder implementation
 
 // Internal implementation modules
@@ -79,12 +100,34 @@ mod builder_texture;          // Implementations for texture handling
 mod builder_material;         // Implementations for material handling
 mod builder_material_specular; // Implementations for specular material handling
 mod builder_animation;        // Implementations for animation handling
+mod builder_import;           // Implementations for glTF/GLB import
+mod builder_obj;              // Implementations for Wavefront OBJ import and export
+mod builder_stl;              // Implementations for STL export
+mod builder_ply;              // Implementations for PLY meshes and point clouds
+mod builder_mesh;             // Multi-primitive mesh builder
+mod builder_camera;           // Implementations for camera handling
+mod builder_light;            // Implementations for KHR_lights_punctual lights
+mod builder_skin;             // Implementations for skins and skinned vertex attributes
+mod builder_morph;            // Implementations for morph targets
+mod builder_color;            // Implementations for vertex colors
+mod builder_geometry;         // Implementations for generated vertex attributes
+mod builder_interleave;       // Interleaved vertex buffer layout for export
+mod builder_dedup;            // Deduplication of identical resources for export
+mod builder_prune;            // Removal of resources no scene uses
+mod transform;                // Node transform helpers for flattening scenes
 
 // Re-exports
 pub use error::{GltfError, Result};
 pub use models::*;
 pub use builder::GltfBuilder;
-pub use builder_primitives::Triangle;
+pub use handle::{
+    AccessorId, AnimationId, AnimationSamplerId, CameraId, LightId, MaterialId, MeshId, NodeId,
+    SamplerId, SceneId, SkinId, TextureId,
+};
+pub use builder_primitives::{IndexWidth, Triangle};
+pub use builder_mesh::MeshPrimitiveBuilder;
+pub use builder_morph::MorphTarget;
+pub use builder_color::VertexColors;
 
 // Constants re-exports
 pub use constants::accessor_type;
@@ -0,0 +1,106 @@
+//! # Light Creation
+//!
+//! This module provides functions for creating the punctual lights defined by the
+//! `KHR_lights_punctual` extension: directional, point and spot lights.
+//!
+//! Like cameras, lights are positioned and oriented by the node they are attached to.
+//! Directional and spot lights shine down the node's local -Z axis, so
+//! `camera::look_at_rotation` can also be used to aim them.
+//!
+//! Intensities follow the extension's physical units: lux (lm/m²) for directional lights
+//! and candela (lm/sr) for point and spot lights.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use mesh_tools::light;
+//!
+//! // Warm sunlight
+//! let sun = light::create_directional_light(Some("Sun".to_string()), [1.0, 0.95, 0.8], 3.0);
+//!
+//! // A ceiling spot with a 30 degree outer cone
+//! let spot = light::create_spot_light(
+//!     Some("Downlight".to_string()),
+//!     [1.0, 1.0, 1.0],
+//!     50.0,
+//!     Some(8.0),
+//!     20.0_f32.to_radians(),
+//!     30.0_f32.to_radians(),
+//! ).unwrap();
+//! ```
+
+use std::f32::consts::FRAC_PI_2;
+
+use crate::error::{GltfError, Result};
+use crate::models::{Light, LightSpot};
+
+/// Create a directional light, which emits parallel light down the node's -Z axis
+///
+/// # Parameters
+/// * `name` - Optional name for the light
+/// * `color` - RGB color of the light in linear space
+/// * `intensity` - Illuminance in lux
+pub fn create_directional_light(name: Option<String>, color: [f32; 3], intensity: f32) -> Light {
+    Light {
+        name,
+        type_: "directional".to_string(),
+        color: Some(color),
+        intensity: Some(intensity),
+        range: None,
+        spot: None,
+    }
+}
+
+/// Create a point light, which emits light in all directions from the node's position
+///
+/// # Parameters
+/// * `name` - Optional name for the light
+/// * `color` - RGB color of the light in linear space
+/// * `intensity` - Luminous intensity in candela
+/// * `range` - Optional distance after which the light has no effect; omit for infinite range
+pub fn create_point_light(name: Option<String>, color: [f32; 3], intensity: f32,
+                          range: Option<f32>) -> Light {
+    Light {
+        name,
+        type_: "point".to_string(),
+        color: Some(color),
+        intensity: Some(intensity),
+        range,
+        spot: None,
+    }
+}
+
+/// Create a spot light, which emits a cone of light down the node's -Z axis
+///
+/// # Parameters
+/// * `name` - Optional name for the light
+/// * `color` - RGB color of the light in linear space
+/// * `intensity` - Luminous intensity in candela
+/// * `range` - Optional distance after which the light has no effect; omit for infinite range
+/// * `inner_cone_angle` - Angle in radians from the center of the cone where falloff begins
+/// * `outer_cone_angle` - Angle in radians from the center of the cone where falloff ends
+///
+/// # Returns
+/// The light, or an error unless `0 <= inner_cone_angle < outer_cone_angle <= PI / 2`
+pub fn create_spot_light(name: Option<String>, color: [f32; 3], intensity: f32,
+                         range: Option<f32>, inner_cone_angle: f32,
+                         outer_cone_angle: f32) -> Result<Light> {
+    if !(0.0..outer_cone_angle).contains(&inner_cone_angle) || outer_cone_angle > FRAC_PI_2 {
+        return Err(GltfError::InvalidData(format!(
+            "Invalid spot light cone angles: inner {} and outer {} must satisfy 0 <= inner < outer <= PI / 2",
+            inner_cone_angle, outer_cone_angle
+        )));
+    }
+    
+    Ok(Light {
+        name,
+        type_: "spot".to_string(),
+        color: Some(color),
+        intensity: Some(intensity),
+        range,
+        spot: Some(LightSpot {
+            inner_cone_angle: Some(inner_cone_angle),
+            outer_cone_angle: Some(outer_cone_angle),
+        }),
+    })
+}
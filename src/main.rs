@@ -3,7 +3,7 @@ fn main() {
     println!("===================");
     println!("This is a library crate with examples in the 'examples' directory.");
     println!("Run examples with 'cargo run --example <example_name>'");
-    println!("");
+    println!();
     println!("Available examples:");
     println!("  - simple_box      Simple box export example");
     println!("  - materials_demo  Demo of different material types");
@@ -27,6 +27,7 @@
 //! );
 //! ```
 
+use crate::handle::TextureId;
 use crate::models::{Material, NormalTextureInfo, OcclusionTextureInfo, PbrMetallicRoughness, TextureInfo};
 
 /// Builder for creating and configuring glTF materials with PBR properties
@@ -37,8 +38,10 @@ pub struct MaterialBuilder {
 impl MaterialBuilder {
     /// Create a new material builder
     pub fn new(name: Option<String>) -> Self {
-        let mut material = Material::default();
-        material.name = name;
+        let material = Material {
+            name,
+            ..Default::default()
+        };
         
         Self { material }
     }
@@ -83,15 +86,16 @@ impl MaterialBuilder {
     }
     
     /// Set base color texture
-    pub fn with_base_color_texture(mut self, texture_index: usize, tex_coord: Option<usize>) -> Self {
+    pub fn with_base_color_texture(mut self, texture: TextureId, tex_coord: Option<usize>) -> Self {
         if self.material.pbr_metallic_roughness.is_none() {
             self.material.pbr_metallic_roughness = Some(PbrMetallicRoughness::default());
         }
         
         if let Some(pbr) = &mut self.material.pbr_metallic_roughness {
-            let mut texture_info = TextureInfo::default();
-            texture_info.index = texture_index;
-            texture_info.tex_coord = tex_coord;
+            let texture_info = TextureInfo {
+                index: texture.index(),
+                tex_coord,
+            };
             
             pbr.base_color_texture = Some(texture_info);
         }
@@ -100,15 +104,16 @@ impl MaterialBuilder {
     }
     
     /// Set metallic roughness texture
-    pub fn with_metallic_roughness_texture(mut self, texture_index: usize, tex_coord: Option<usize>) -> Self {
+    pub fn with_metallic_roughness_texture(mut self, texture: TextureId, tex_coord: Option<usize>) -> Self {
         if self.material.pbr_metallic_roughness.is_none() {
             self.material.pbr_metallic_roughness = Some(PbrMetallicRoughness::default());
         }
         
         if let Some(pbr) = &mut self.material.pbr_metallic_roughness {
-            let mut texture_info = TextureInfo::default();
-            texture_info.index = texture_index;
-            texture_info.tex_coord = tex_coord;
+            let texture_info = TextureInfo {
+                index: texture.index(),
+                tex_coord,
+            };
             
             pbr.metallic_roughness_texture = Some(texture_info);
         }
@@ -117,11 +122,12 @@ impl MaterialBuilder {
     }
     
     /// Set normal texture
-    pub fn with_normal_texture(mut self, texture_index: usize, tex_coord: Option<usize>, scale: Option<f32>) -> Self {
-        let mut normal_info = NormalTextureInfo::default();
-        normal_info.index = texture_index;
-        normal_info.tex_coord = tex_coord;
-        normal_info.scale = scale;
+    pub fn with_normal_texture(mut self, texture: TextureId, tex_coord: Option<usize>, scale: Option<f32>) -> Self {
+        let normal_info = NormalTextureInfo {
+            index: texture.index(),
+            tex_coord,
+            scale,
+        };
         
         self.material.normal_texture = Some(normal_info);
         
@@ -129,11 +135,12 @@ impl MaterialBuilder {
     }
     
     /// Set occlusion texture
-    pub fn with_occlusion_texture(mut self, texture_index: usize, tex_coord: Option<usize>, strength: Option<f32>) -> Self {
-        let mut occlusion_info = OcclusionTextureInfo::default();
-        occlusion_info.index = texture_index;
-        occlusion_info.tex_coord = tex_coord;
-        occlusion_info.strength = strength;
+    pub fn with_occlusion_texture(mut self, texture: TextureId, tex_coord: Option<usize>, strength: Option<f32>) -> Self {
+        let occlusion_info = OcclusionTextureInfo {
+            index: texture.index(),
+            tex_coord,
+            strength,
+        };
         
         self.material.occlusion_texture = Some(occlusion_info);
         
@@ -141,10 +148,11 @@ impl MaterialBuilder {
     }
     
     /// Set emissive texture
-    pub fn with_emissive_texture(mut self, texture_index: usize, tex_coord: Option<usize>) -> Self {
-        let mut texture_info = TextureInfo::default();
-        texture_info.index = texture_index;
-        texture_info.tex_coord = tex_coord;
+    pub fn with_emissive_texture(mut self, texture: TextureId, tex_coord: Option<usize>) -> Self {
+        let texture_info = TextureInfo {
+            index: texture.index(),
+            tex_coord,
+        };
         
         self.material.emissive_texture = Some(texture_info);
         
@@ -198,13 +206,14 @@ pub fn create_metallic_material(
 }
 
 /// Create a textured material with additional options
+#[allow(clippy::too_many_arguments)]
 pub fn create_textured_material(
     name: Option<String>,
-    base_color_texture: Option<usize>,
-    metallic_roughness_texture: Option<usize>,
-    normal_texture: Option<usize>,
-    occlusion_texture: Option<usize>,
-    emissive_texture: Option<usize>,
+    base_color_texture: Option<TextureId>,
+    metallic_roughness_texture: Option<TextureId>,
+    normal_texture: Option<TextureId>,
+    occlusion_texture: Option<TextureId>,
+    emissive_texture: Option<TextureId>,
     emissive_factor: Option<[f32; 3]>,
     metallic_factor: Option<f32>,
     roughness_factor: Option<f32>,
@@ -18,28 +18,59 @@
 //! ## Example
 //!
 //! ```rust
+//! use mesh_tools::GltfBuilder;
 //! use mesh_tools::mesh::MeshBuilder;
 //!
-//! // Create a mesh with custom data
+//! let mut builder = GltfBuilder::new();
+//!
+//! // Create a mesh with custom data and upload it to the document
 //! let mesh = MeshBuilder::new(Some("CustomMesh".to_string()))
 //!     .with_positions(vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0])
 //!     .with_indices(vec![0, 1, 2])
 //!     .with_normals(vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0])
-//!     .build();
+//!     .build_into(&mut builder)
+//!     .unwrap();
+//!
+//! let node = builder.add_node(None, Some(mesh), None, None, None);
+//! builder.add_scene(None, Some(vec![node]));
+//! assert!(builder.validate().is_valid());
 //! ```
 
+use crate::builder::GltfBuilder;
+use crate::builder_primitives::Triangle;
+use crate::compat::{point3, vector2, vector3};
+use crate::error::{GltfError, Result};
+use crate::handle::{MaterialId, MeshId};
 use crate::models::{Primitive, Mesh};
 use std::collections::HashMap;
 
+/// Function turning flat positions, indices, normals, texture coordinate sets and a material
+/// into a primitive, for `MeshBuilder::with_primitive_builder`
+pub type PrimitiveBuilderFn = dyn FnMut(&[f32], &[u32], Option<&[f32]>, Option<Vec<Vec<f32>>>, Option<MaterialId>) -> Primitive;
+
 /// Builder for creating and configuring 3D mesh objects
+///
+/// Geometry is given as flat arrays: three floats per position and normal, two per texture
+/// coordinate and three indices per triangle. The `with_*` methods describe the current
+/// primitive; `next_primitive` finishes it and starts another one in the same mesh.
 pub struct MeshBuilder {
-    builder: Option<Box<dyn FnMut(&[f32], &[u16], Option<&[f32]>, Option<Vec<Vec<f32>>>, Option<usize>) -> Primitive>>,
+    builder: Option<Box<PrimitiveBuilderFn>>,
     positions: Vec<f32>,
-    indices: Vec<u16>,
+    indices: Vec<u32>,
     normals: Option<Vec<f32>>,
     texcoords: Option<Vec<Vec<f32>>>,
-    material: Option<usize>,
+    material: Option<MaterialId>,
     name: Option<String>,
+    finished: Vec<PrimitiveData>,
+}
+
+/// Geometry of a primitive finished with `MeshBuilder::next_primitive`
+struct PrimitiveData {
+    positions: Vec<f32>,
+    indices: Vec<u32>,
+    normals: Option<Vec<f32>>,
+    texcoords: Option<Vec<Vec<f32>>>,
+    material: Option<MaterialId>,
 }
 
 impl MeshBuilder {
@@ -53,6 +84,7 @@ impl MeshBuilder {
             texcoords: None,
             material: None,
             name,
+            finished: Vec::new(),
         }
     }
     
@@ -63,7 +95,7 @@ impl MeshBuilder {
     }
     
     /// Set the indices for the mesh
-    pub fn with_indices(mut self, indices: Vec<u16>) -> Self {
+    pub fn with_indices(mut self, indices: Vec<u32>) -> Self {
         self.indices = indices;
         self
     }
@@ -76,9 +108,7 @@ impl MeshBuilder {
     
     /// Set a single set of texture coordinates for the mesh
     pub fn with_texcoords(mut self, texcoords: Vec<f32>) -> Self {
-        let mut texcoord_sets = Vec::new();
-        texcoord_sets.push(texcoords);
-        self.texcoords = Some(texcoord_sets);
+        self.texcoords = Some(vec![texcoords]);
         self
     }
     
@@ -88,56 +118,203 @@ impl MeshBuilder {
         self
     }
     
-    /// Set the material index for the mesh
-    pub fn with_material(mut self, material: usize) -> Self {
+    /// Set the material for the mesh
+    pub fn with_material(mut self, material: MaterialId) -> Self {
         self.material = Some(material);
         self
     }
     
+    /// Finish the current primitive and start a new, empty one
+    ///
+    /// # Example
+    /// ```
+    /// use mesh_tools::GltfBuilder;
+    /// use mesh_tools::mesh::MeshBuilder;
+    ///
+    /// let mut builder = GltfBuilder::new();
+    /// let red = builder.create_basic_material(None, [1.0, 0.0, 0.0, 1.0]);
+    /// let blue = builder.create_basic_material(None, [0.0, 0.0, 1.0, 1.0]);
+    ///
+    /// // Two triangles with different materials in one mesh
+    /// let mesh = MeshBuilder::new(Some("Flags".to_string()))
+    ///     .with_positions(vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0])
+    ///     .with_indices(vec![0, 1, 2])
+    ///     .with_material(red)
+    ///     .next_primitive()
+    ///     .with_positions(vec![2.0, 0.0, 0.0, 3.0, 0.0, 0.0, 2.0, 1.0, 0.0])
+    ///     .with_indices(vec![0, 1, 2])
+    ///     .with_material(blue)
+    ///     .build_into(&mut builder)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(builder.gltf.meshes.as_ref().unwrap()[mesh.index()].primitives.len(), 2);
+    /// ```
+    pub fn next_primitive(mut self) -> Self {
+        self.finished.push(PrimitiveData {
+            positions: std::mem::take(&mut self.positions),
+            indices: std::mem::take(&mut self.indices),
+            normals: self.normals.take(),
+            texcoords: self.texcoords.take(),
+            material: self.material.take(),
+        });
+        self
+    }
+    
     /// Set the primitive builder function
     pub fn with_primitive_builder(
         mut self,
-        builder: Box<dyn FnMut(&[f32], &[u16], Option<&[f32]>, Option<Vec<Vec<f32>>>, Option<usize>) -> Primitive>,
+        builder: Box<PrimitiveBuilderFn>,
     ) -> Self {
         self.builder = Some(builder);
         self
     }
     
     /// Build the mesh
-    pub fn build(self) -> Mesh {
-        let mut mesh = Mesh::default();
-        mesh.name = self.name;
+    ///
+    /// Each primitive is created by the function set with `with_primitive_builder`, which is
+    /// responsible for uploading its geometry. Without one, the primitives only carry their
+    /// material and no geometry; use `build_into` to add the mesh to a `GltfBuilder` instead.
+    pub fn build(mut self) -> Mesh {
+        let mut mesh = Mesh {
+            name: self.name.take(),
+            ..Default::default()
+        };
+        
+        let mut builder = self.builder.take();
+        for data in self.into_primitives() {
+            if let Some(builder) = &mut builder {
+                let primitive = builder(
+                    &data.positions,
+                    &data.indices,
+                    data.normals.as_deref(),
+                    data.texcoords,
+                    data.material,
+                );
+                mesh.primitives.push(primitive);
+            } else {
+                // Default implementation if no builder provided
+                let attributes = HashMap::new();
+                // Would need accessor indices which are typically provided by GltfBuilder
+                
+                let primitive = Primitive {
+                    attributes,
+                    indices: None, // Would need accessor index
+                    material: data.material.map(MaterialId::index),
+                    mode: None,    // Default to triangles
+                    targets: None,
+                };
+                
+                mesh.primitives.push(primitive);
+            }
+        }
         
-        let normals_ref = self.normals.as_deref();
+        mesh
+    }
+    
+    /// Upload the geometry to a `GltfBuilder` and add the mesh to its document
+    ///
+    /// Every primitive goes through the same path as `GltfBuilder::create_custom_mesh`, so
+    /// the builder's normal, tangent and index width settings apply. All primitives are
+    /// checked before anything is uploaded; the primitive builder function is not used.
+    ///
+    /// # Parameters
+    /// * `builder` - The builder to add the mesh to
+    ///
+    /// # Returns
+    /// The handle of the created mesh, or an error if a primitive has no positions or indices,
+    /// array lengths do not match the number of vertices, an index is out of range, or the
+    /// material does not exist in `builder`
+    pub fn build_into(mut self, builder: &mut GltfBuilder) -> Result<MeshId> {
+        let name = self.name.take();
+        let primitives = self.into_primitives();
+        let material_count = builder.gltf.materials.as_ref().map_or(0, |materials| materials.len());
+        for (i, data) in primitives.iter().enumerate() {
+            check_primitive_data(i, data, material_count)?;
+        }
         
-        if let Some(mut builder) = self.builder {
-            let primitive = builder(
-                &self.positions,
-                &self.indices,
-                normals_ref,
-                self.texcoords,
-                self.material,
-            );
-            mesh.primitives.push(primitive);
-        } else {
-            // Default implementation if no builder provided
-            let attributes = HashMap::new();
-            // Would need accessor indices which are typically provided by GltfBuilder
+        let primitives = primitives.into_iter().map(|data| {
+            let positions: Vec<_> = data.positions.chunks_exact(3)
+                .map(|p| point3::new(p[0], p[1], p[2]))
+                .collect();
+            let indices: Vec<_> = data.indices.chunks_exact(3)
+                .map(|t| Triangle::new(t[0], t[1], t[2]))
+                .collect();
+            let normals = data.normals.map(|normals| {
+                normals.chunks_exact(3).map(|n| vector3::new(n[0], n[1], n[2])).collect()
+            });
+            let texcoords = data.texcoords.map(|sets| {
+                sets.iter()
+                    .map(|set| set.chunks_exact(2).map(|uv| vector2::new(uv[0], uv[1])).collect())
+                    .collect()
+            });
             
-            let primitive = Primitive {
-                attributes,
-                indices: None, // Would need accessor index
+            builder.create_custom_primitive(&positions, &indices, normals, texcoords, data.material)
+        }).collect();
+        
+        Ok(builder.add_mesh(name, primitives))
+    }
+    
+    /// All primitives, finishing the current one unless it is empty and not the only one
+    fn into_primitives(self) -> Vec<PrimitiveData> {
+        let current_is_empty = self.positions.is_empty() && self.indices.is_empty()
+            && self.normals.is_none() && self.texcoords.is_none() && self.material.is_none();
+        
+        let mut finished = self.finished;
+        if !current_is_empty || finished.is_empty() {
+            finished.push(PrimitiveData {
+                positions: self.positions,
+                indices: self.indices,
+                normals: self.normals,
+                texcoords: self.texcoords,
                 material: self.material,
-                mode: None,    // Default to triangles
-            };
-            
-            mesh.primitives.push(primitive);
+            });
         }
-        
-        mesh
+        finished
     }
 }
 
+/// Check that the arrays of a primitive describe the same vertices and that its material exists
+fn check_primitive_data(primitive: usize, data: &PrimitiveData, material_count: usize) -> Result<()> {
+    let invalid = |message: String| Err(GltfError::InvalidData(format!("Primitive {} has {}", primitive, message)));
+    
+    if data.positions.is_empty() {
+        return invalid("no positions".to_string());
+    }
+    if data.positions.len() % 3 != 0 {
+        return invalid(format!("{} position values, which is not a multiple of 3", data.positions.len()));
+    }
+    
+    let vertex_count = data.positions.len() / 3;
+    if let Some(normals) = &data.normals {
+        if normals.len() != vertex_count * 3 {
+            return invalid(format!("{} normal values for {} vertices", normals.len(), vertex_count));
+        }
+    }
+    for (set, texcoords) in data.texcoords.iter().flatten().enumerate() {
+        if texcoords.len() != vertex_count * 2 {
+            return invalid(format!(
+                "{} values in texture coordinate set {} for {} vertices", texcoords.len(), set, vertex_count
+            ));
+        }
+    }
+    
+    if data.indices.is_empty() {
+        return invalid("no indices".to_string());
+    }
+    if data.indices.len() % 3 != 0 {
+        return invalid(format!("{} indices, which is not a multiple of 3", data.indices.len()));
+    }
+    if let Some(&index) = data.indices.iter().find(|&&index| index as usize >= vertex_count) {
+        return invalid(format!("index {} out of range for {} vertices", index, vertex_count));
+    }
+    
+    if let Some(material) = data.material.filter(|material| material.index() >= material_count) {
+        return invalid(format!("material {}, but there are only {} materials", material.index(), material_count));
+    }
+    
+    Ok(())
+}
+
 /// Calculate min and max bounds of positions
 pub fn calculate_bounds(positions: &[f32]) -> (Vec<f32>, Vec<f32>) {
     if positions.is_empty() {
@@ -187,3 +364,36 @@ pub fn create_attribute_mapping(
     
     attributes
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn triangle() -> MeshBuilder {
+        MeshBuilder::new(None)
+            .with_positions(vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0])
+            .with_indices(vec![0, 1, 2])
+    }
+    
+    #[test]
+    fn missing_material_is_rejected_before_uploading() {
+        let mut builder = GltfBuilder::new();
+        
+        let result = triangle().with_material(MaterialId::from_index(3)).build_into(&mut builder);
+        
+        assert!(matches!(result, Err(GltfError::InvalidData(_))));
+        assert!(builder.buffer_data.is_empty());
+        assert!(builder.gltf.meshes.as_ref().unwrap().is_empty());
+    }
+    
+    #[test]
+    fn existing_material_is_used() {
+        let mut builder = GltfBuilder::new();
+        let material = builder.create_basic_material(None, [1.0, 0.0, 0.0, 1.0]);
+        
+        let mesh = triangle().with_material(material).build_into(&mut builder).unwrap();
+        
+        let primitive = &builder.gltf.meshes.as_ref().unwrap()[mesh.index()].primitives[0];
+        assert_eq!(primitive.material, Some(material.index()));
+    }
+}
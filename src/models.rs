@@ -13,7 +13,7 @@ use std::collections::HashMap;
 ///
 /// A glTF document contains all the resources and metadata needed to represent a 3D scene or model.
 /// It includes scenes, nodes, meshes, materials, textures, and binary data references.
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct Gltf {
     pub asset: Asset,
     
@@ -54,6 +54,12 @@ pub struct Gltf {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub animations: Option<Vec<Animation>>,
     
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cameras: Option<Vec<Camera>>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skins: Option<Vec<Skin>>,
+    
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extensions: Option<serde_json::Value>,
     
@@ -70,7 +76,7 @@ pub struct Gltf {
 }
 
 /// Represents the glTF asset information
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct Asset {
     pub version: String,
     
@@ -82,7 +88,7 @@ pub struct Asset {
 }
 
 /// Represents a glTF scene
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct Scene {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
@@ -92,7 +98,7 @@ pub struct Scene {
 }
 
 /// Represents a glTF node
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct Node {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
@@ -100,6 +106,12 @@ pub struct Node {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mesh: Option<usize>,
     
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera: Option<usize>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skin: Option<usize>,
+    
     #[serde(skip_serializing_if = "Option::is_none")]
     pub translation: Option<[f32; 3]>,
     
@@ -114,19 +126,135 @@ pub struct Node {
     
     #[serde(skip_serializing_if = "Option::is_none")]
     pub children: Option<Vec<usize>>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<NodeExtensions>,
+}
+
+/// Represents node extensions for glTF
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct NodeExtensions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "KHR_lights_punctual")]
+    pub lights_punctual: Option<NodeLight>,
+}
+
+/// Represents a reference from a node to a light of the KHR_lights_punctual extension
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NodeLight {
+    pub light: usize,
+}
+
+/// Represents a glTF skin, the joints that deform a skinned mesh
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Skin {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "inverseBindMatrices")]
+    pub inverse_bind_matrices: Option<usize>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skeleton: Option<usize>,
+    
+    pub joints: Vec<usize>,
+}
+
+/// Represents a glTF camera
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Camera {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    
+    #[serde(rename = "type")]
+    pub type_: String,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub perspective: Option<CameraPerspective>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub orthographic: Option<CameraOrthographic>,
+}
+
+/// Represents the projection of a perspective camera
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CameraPerspective {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "aspectRatio")]
+    pub aspect_ratio: Option<f32>,
+    
+    pub yfov: f32,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zfar: Option<f32>,
+    
+    pub znear: f32,
+}
+
+/// Represents the projection of an orthographic camera
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CameraOrthographic {
+    pub xmag: f32,
+    
+    pub ymag: f32,
+    
+    pub zfar: f32,
+    
+    pub znear: f32,
+}
+
+/// Represents a light of the KHR_lights_punctual extension
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Light {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    
+    #[serde(rename = "type")]
+    pub type_: String,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<[f32; 3]>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intensity: Option<f32>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<f32>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spot: Option<LightSpot>,
+}
+
+/// Represents the cone of a spot light
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LightSpot {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "innerConeAngle")]
+    pub inner_cone_angle: Option<f32>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "outerConeAngle")]
+    pub outer_cone_angle: Option<f32>,
 }
 
 /// Represents a glTF mesh
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct Mesh {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     
     pub primitives: Vec<Primitive>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weights: Option<Vec<f32>>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extras: Option<HashMap<String, serde_json::Value>>,
 }
 
 /// Represents a glTF mesh primitive
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct Primitive {
     pub attributes: HashMap<String, usize>,
     
@@ -138,13 +266,17 @@ pub struct Primitive {
     
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mode: Option<usize>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub targets: Option<Vec<HashMap<String, usize>>>,
 }
 
 /// Represents a glTF accessor
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Accessor {
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "bufferView")]
-    pub buffer_view: usize,
+    pub buffer_view: Option<usize>,
     #[serde(rename = "componentType")]
     pub component_type: usize,
     pub count: usize,
@@ -163,12 +295,51 @@ pub struct Accessor {
     
     #[serde(skip_serializing_if = "Option::is_none")]
     pub normalized: Option<bool>,
+    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sparse: Option<AccessorSparse>,
+}
+
+/// Represents sparse storage of accessor elements that deviate from their initialization value
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AccessorSparse {
+    pub count: usize,
+    
+    pub indices: AccessorSparseIndices,
+    
+    pub values: AccessorSparseValues,
+}
+
+/// Represents the indices of the accessor elements replaced by sparse storage
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AccessorSparseIndices {
+    #[serde(rename = "bufferView")]
+    pub buffer_view: usize,
+    
+    #[serde(default)]
+    #[serde(rename = "byteOffset")]
+    pub byte_offset: usize,
+    
+    #[serde(rename = "componentType")]
+    pub component_type: usize,
+}
+
+/// Represents the replacement values of the accessor elements in sparse storage
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AccessorSparseValues {
+    #[serde(rename = "bufferView")]
+    pub buffer_view: usize,
+    
+    #[serde(default)]
+    #[serde(rename = "byteOffset")]
+    pub byte_offset: usize,
 }
 
 /// Represents a glTF buffer view
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BufferView {
     pub buffer: usize,
+    #[serde(default)]
     #[serde(rename = "byteOffset")]
     pub byte_offset: usize,
     #[serde(rename = "byteLength")]
@@ -183,7 +354,7 @@ pub struct BufferView {
 }
 
 /// Represents a glTF buffer
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Buffer {
     #[serde(rename = "byteLength")]
     pub byte_length: usize,
@@ -193,7 +364,7 @@ pub struct Buffer {
 }
 
 /// Represents a glTF material
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct Material {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
@@ -235,7 +406,7 @@ pub struct Material {
 }
 
 /// Represents a glTF PBR material
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct PbrMetallicRoughness {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "baseColorFactor")]
@@ -259,7 +430,7 @@ pub struct PbrMetallicRoughness {
 }
 
 /// Represents basic texture reference information
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct TextureInfo {
     pub index: usize,
     
@@ -269,7 +440,7 @@ pub struct TextureInfo {
 }
 
 /// Represents normal texture reference information
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct NormalTextureInfo {
     pub index: usize,
     
@@ -282,7 +453,7 @@ pub struct NormalTextureInfo {
 }
 
 /// Represents occlusion texture reference information
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct OcclusionTextureInfo {
     pub index: usize,
     
@@ -295,7 +466,7 @@ pub struct OcclusionTextureInfo {
 }
 
 /// Represents a glTF texture
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Texture {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
@@ -307,7 +478,7 @@ pub struct Texture {
 }
 
 /// Represents a glTF image
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Image {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
@@ -325,7 +496,7 @@ pub struct Image {
 }
 
 /// Represents a glTF sampler
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct Sampler {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "magFilter")]
@@ -345,7 +516,7 @@ pub struct Sampler {
 }
 
 /// Represents a glTF animation
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct Animation {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
@@ -358,7 +529,7 @@ pub struct Animation {
 }
 
 /// Represents a glTF animation channel
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct AnimationChannel {
     pub sampler: usize,
     
@@ -366,7 +537,7 @@ pub struct AnimationChannel {
 }
 
 /// Represents a glTF animation channel target
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct AnimationChannelTarget {
     pub node: usize,
     
@@ -374,7 +545,7 @@ pub struct AnimationChannelTarget {
 }
 
 /// Represents a glTF animation sampler
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct AnimationSampler {
     pub input: usize,
     
@@ -385,7 +556,7 @@ pub struct AnimationSampler {
 }
 
 /// Represents material extensions for glTF
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct MaterialExtensions {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "KHR_materials_pbrSpecularGlossiness")]
@@ -393,7 +564,7 @@ pub struct MaterialExtensions {
 }
 
 /// Represents a glTF specular-glossiness PBR material extension
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct PbrSpecularGlossiness {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "diffuseFactor")]
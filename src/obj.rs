@@ -0,0 +1,389 @@
+//! # Wavefront OBJ/MTL Parsing
+//!
+//! This module parses Wavefront OBJ geometry and MTL material libraries into data that can be
+//! passed straight to `GltfBuilder::create_custom_mesh`, or imported as a whole with
+//! `GltfBuilder::import_obj`.
+//!
+//! OBJ files index positions, texture coordinates and normals separately. The parser re-indexes
+//! every distinct `v/vt/vn` combination into a single vertex, as glTF requires, triangulates
+//! polygons as triangle fans and splits each object (`o`) into one primitive per material
+//! (`usemtl`). Texture coordinates are flipped vertically to match the glTF convention.
+//!
+//! Statements that have no glTF counterpart (groups, smoothing groups, lines, points,
+//! free-form geometry) are ignored.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use mesh_tools::obj::parse_obj;
+//!
+//! let source = "
+//! v 0 0 0
+//! v 1 0 0
+//! v 1 1 0
+//! v 0 1 0
+//! vn 0 0 1
+//! f 1//1 2//1 3//1 4//1
+//! ";
+//!
+//! let model = parse_obj(source).unwrap();
+//! let primitive = &model.objects[0].primitives[0];
+//!
+//! // The quad is split into two triangles sharing four vertices
+//! assert_eq!(primitive.positions.len(), 4);
+//! assert_eq!(primitive.indices.len(), 2);
+//! assert!(primitive.normals.is_some());
+//! ```
+
+use std::collections::HashMap;
+
+use crate::builder_primitives::Triangle;
+use crate::compat::{Point3, Vector2, Vector3};
+use crate::error::{GltfError, Result};
+
+/// Triangulated geometry of an object that uses a single material
+#[derive(Debug, Clone, Default)]
+pub struct ObjPrimitive {
+    /// Name of the MTL material selected with `usemtl`, if any
+    pub material: Option<String>,
+    /// Vertex positions
+    pub positions: Vec<Point3<f32>>,
+    /// Triangles indexing into the vertex attributes
+    pub indices: Vec<Triangle>,
+    /// Vertex normals, present when every face of the primitive specifies them
+    pub normals: Option<Vec<Vector3<f32>>>,
+    /// Texture coordinates, present when every face of the primitive specifies them
+    pub texcoords: Option<Vec<Vector2<f32>>>,
+}
+
+/// An OBJ object (`o`) split into one primitive per material
+#[derive(Debug, Clone, Default)]
+pub struct ObjObject {
+    /// Object name
+    pub name: Option<String>,
+    /// Primitives in the order their materials were first used
+    pub primitives: Vec<ObjPrimitive>,
+}
+
+/// The contents of an OBJ file
+#[derive(Debug, Clone, Default)]
+pub struct ObjModel {
+    /// Material library files referenced with `mtllib`
+    pub material_libraries: Vec<String>,
+    /// Objects that contain at least one face
+    pub objects: Vec<ObjObject>,
+}
+
+/// A material from an MTL library
+#[derive(Debug, Clone)]
+pub struct MtlMaterial {
+    /// Material name given by `newmtl`
+    pub name: String,
+    /// Diffuse color (`Kd`)
+    pub diffuse: [f32; 3],
+    /// Opacity (`d`, or `1 - Tr`)
+    pub dissolve: f32,
+    /// Emissive color (`Ke`)
+    pub emissive: Option<[f32; 3]>,
+    /// Diffuse texture file (`map_Kd`)
+    pub diffuse_map: Option<String>,
+}
+
+impl MtlMaterial {
+    /// Create a white, opaque material with the given name
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            diffuse: [1.0, 1.0, 1.0],
+            dissolve: 1.0,
+            emissive: None,
+            diffuse_map: None,
+        }
+    }
+}
+
+/// Primitive being assembled while parsing
+#[derive(Default)]
+struct PrimitiveData {
+    material: Option<String>,
+    vertices: HashMap<(usize, Option<usize>, Option<usize>), u32>,
+    positions: Vec<Point3<f32>>,
+    normals: Vec<Option<Vector3<f32>>>,
+    texcoords: Vec<Option<Vector2<f32>>>,
+    indices: Vec<Triangle>,
+}
+
+impl PrimitiveData {
+    fn finish(self) -> ObjPrimitive {
+        ObjPrimitive {
+            material: self.material,
+            positions: self.positions,
+            indices: self.indices,
+            normals: self.normals.into_iter().collect(),
+            texcoords: self.texcoords.into_iter().collect(),
+        }
+    }
+}
+
+/// Parse the contents of an OBJ file
+///
+/// # Parameters
+/// * `source` - The OBJ file contents
+///
+/// # Returns
+/// The parsed model, or an error naming the offending line
+pub fn parse_obj(source: &str) -> Result<ObjModel> {
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut texcoords = Vec::new();
+    
+    let mut model = ObjModel::default();
+    let mut objects: Vec<(Option<String>, Vec<PrimitiveData>)> = vec![(None, Vec::new())];
+    let mut material: Option<String> = None;
+    
+    for (line_number, line) in logical_lines(source) {
+        let mut tokens = line.split_whitespace();
+        let keyword = match tokens.next() {
+            Some(keyword) => keyword,
+            None => continue,
+        };
+        
+        match keyword {
+            "v" => {
+                let [x, y, z] = parse_floats(tokens, line_number)?;
+                positions.push(crate::compat::point3::new(x, y, z));
+            }
+            "vn" => {
+                let [x, y, z] = parse_floats(tokens, line_number)?;
+                normals.push(crate::compat::normalize(crate::compat::vector3::new(x, y, z)));
+            }
+            "vt" => {
+                let u = parse_float(tokens.next(), line_number)?;
+                let v = match tokens.next() {
+                    Some(token) => parse_float(Some(token), line_number)?,
+                    None => 0.0,
+                };
+                // OBJ texture coordinates have their origin at the bottom left
+                texcoords.push(crate::compat::vector2::new(u, 1.0 - v));
+            }
+            "f" => {
+                let (_, primitives) = objects.last_mut().expect("there is always a current object");
+                let primitive = match primitives.iter().position(|primitive| primitive.material == material) {
+                    Some(index) => &mut primitives[index],
+                    None => {
+                        primitives.push(PrimitiveData {
+                            material: material.clone(),
+                            ..Default::default()
+                        });
+                        primitives.last_mut().unwrap()
+                    }
+                };
+                
+                let mut corners = Vec::new();
+                for token in tokens {
+                    let mut parts = token.split('/');
+                    let v = resolve_index(parts.next(), positions.len(), line_number)?
+                        .ok_or_else(|| invalid_line(line_number, "face vertex without a position"))?;
+                    let vt = resolve_index(parts.next(), texcoords.len(), line_number)?;
+                    let vn = resolve_index(parts.next(), normals.len(), line_number)?;
+                    
+                    let index = *primitive.vertices.entry((v, vt, vn)).or_insert_with(|| {
+                        primitive.positions.push(positions[v]);
+                        primitive.texcoords.push(vt.map(|vt| texcoords[vt]));
+                        primitive.normals.push(vn.map(|vn| normals[vn]));
+                        (primitive.positions.len() - 1) as u32
+                    });
+                    corners.push(index);
+                }
+                
+                if corners.len() < 3 {
+                    return Err(invalid_line(line_number, "face with fewer than 3 vertices"));
+                }
+                
+                // Fan triangulation, which is exact for convex polygons
+                for i in 1..corners.len() - 1 {
+                    primitive.indices.push(Triangle::new(corners[0], corners[i], corners[i + 1]));
+                }
+            }
+            "o" => {
+                let name = rest_of_line(&line, keyword);
+                match objects.last_mut() {
+                    // Name the current object if nothing has been added to it yet
+                    Some((current, primitives)) if primitives.is_empty() => *current = name,
+                    _ => objects.push((name, Vec::new())),
+                }
+            }
+            "usemtl" => material = rest_of_line(&line, keyword),
+            "mtllib" => model.material_libraries.extend(tokens.map(str::to_string)),
+            _ => {}
+        }
+    }
+    
+    model.objects = objects.into_iter()
+        .filter(|(_, primitives)| !primitives.is_empty())
+        .map(|(name, primitives)| ObjObject {
+            name,
+            primitives: primitives.into_iter().map(PrimitiveData::finish).collect(),
+        })
+        .collect();
+    
+    Ok(model)
+}
+
+/// Parse the contents of an MTL material library
+///
+/// Only the properties that map onto glTF materials are read: `Kd`, `d`/`Tr`, `Ke` and
+/// `map_Kd`. Texture options such as `-s` or `-clamp` are skipped.
+///
+/// # Parameters
+/// * `source` - The MTL file contents
+///
+/// # Returns
+/// The materials in the order they are defined
+pub fn parse_mtl(source: &str) -> Result<Vec<MtlMaterial>> {
+    let mut materials: Vec<MtlMaterial> = Vec::new();
+    
+    for (line_number, line) in logical_lines(source) {
+        let mut tokens = line.split_whitespace();
+        let keyword = match tokens.next() {
+            Some(keyword) => keyword,
+            None => continue,
+        };
+        
+        if keyword == "newmtl" {
+            let name = rest_of_line(&line, keyword)
+                .ok_or_else(|| invalid_line(line_number, "newmtl without a name"))?;
+            materials.push(MtlMaterial::new(name));
+            continue;
+        }
+        
+        let material = match materials.last_mut() {
+            Some(material) => material,
+            None => continue,
+        };
+        
+        match keyword {
+            "Kd" => material.diffuse = parse_floats(tokens, line_number)?,
+            "Ke" => material.emissive = Some(parse_floats(tokens, line_number)?),
+            "d" => material.dissolve = parse_float(tokens.next(), line_number)?,
+            "Tr" => material.dissolve = 1.0 - parse_float(tokens.next(), line_number)?,
+            "map_Kd" => material.diffuse_map = texture_file(tokens),
+            _ => {}
+        }
+    }
+    
+    Ok(materials)
+}
+
+/// Split a file into numbered lines, joining `\` continuations and removing comments
+fn logical_lines(source: &str) -> Vec<(usize, String)> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut start = 1;
+    
+    for (index, line) in source.lines().enumerate() {
+        if current.is_empty() {
+            start = index + 1;
+        }
+        
+        let line = line.split('#').next().unwrap_or("");
+        match line.trim_end().strip_suffix('\\') {
+            Some(continued) => {
+                current.push_str(continued);
+                current.push(' ');
+            }
+            None => {
+                current.push_str(line);
+                lines.push((start, std::mem::take(&mut current)));
+            }
+        }
+    }
+    
+    if !current.is_empty() {
+        lines.push((start, current));
+    }
+    
+    lines
+}
+
+/// Get everything after the keyword of a statement, for names that may contain spaces
+fn rest_of_line(line: &str, keyword: &str) -> Option<String> {
+    let rest = line.trim_start()[keyword.len()..].trim();
+    
+    if rest.is_empty() {
+        None
+    } else {
+        Some(rest.to_string())
+    }
+}
+
+/// Get the file name of a texture statement, skipping any leading options
+fn texture_file<'a>(tokens: impl Iterator<Item = &'a str>) -> Option<String> {
+    let mut tokens = tokens.peekable();
+    
+    while let Some(option) = tokens.next_if(|token| token.starts_with('-')) {
+        // Options take up to three numeric arguments, or a single word
+        if matches!(option, "-blendu" | "-blendv" | "-cc" | "-clamp" | "-imfchan" | "-type") {
+            tokens.next();
+        } else {
+            for _ in 0..3 {
+                if tokens.next_if(|token| token.parse::<f32>().is_ok()).is_none() {
+                    break;
+                }
+            }
+        }
+    }
+    
+    let file = tokens.collect::<Vec<_>>().join(" ");
+    
+    if file.is_empty() {
+        None
+    } else {
+        Some(file)
+    }
+}
+
+/// Resolve a 1-based (or negative, relative) OBJ index into a 0-based index
+fn resolve_index(token: Option<&str>, count: usize, line_number: usize) -> Result<Option<usize>> {
+    let token = match token {
+        Some(token) if !token.is_empty() => token,
+        _ => return Ok(None),
+    };
+    
+    let index: i64 = token.parse()
+        .map_err(|_| invalid_line(line_number, &format!("invalid index '{}'", token)))?;
+    
+    let resolved = if index > 0 {
+        index - 1
+    } else {
+        count as i64 + index
+    };
+    
+    if index == 0 || resolved < 0 || resolved >= count as i64 {
+        return Err(invalid_line(line_number, &format!("index {} is out of range", index)));
+    }
+    
+    Ok(Some(resolved as usize))
+}
+
+/// Parse a single number
+fn parse_float(token: Option<&str>, line_number: usize) -> Result<f32> {
+    let token = token.ok_or_else(|| invalid_line(line_number, "missing value"))?;
+    
+    token.parse()
+        .map_err(|_| invalid_line(line_number, &format!("invalid number '{}'", token)))
+}
+
+/// Parse the first three numbers of a statement, ignoring any extra values
+fn parse_floats<'a>(mut tokens: impl Iterator<Item = &'a str>, line_number: usize) -> Result<[f32; 3]> {
+    Ok([
+        parse_float(tokens.next(), line_number)?,
+        parse_float(tokens.next(), line_number)?,
+        parse_float(tokens.next(), line_number)?,
+    ])
+}
+
+/// Create an error for a malformed line
+fn invalid_line(line_number: usize, message: &str) -> GltfError {
+    GltfError::InvalidData(format!("Line {}: {}", line_number, message))
+}
@@ -0,0 +1,669 @@
+//! # PLY Reading and Writing
+//!
+//! This module reads and writes the Stanford PLY format produced by photogrammetry and
+//! point-cloud tools, in its ASCII, binary little-endian and binary big-endian encodings.
+//!
+//! A `PlyMesh` holds the vertex data that maps onto glTF attributes (positions, normals,
+//! texture coordinates and colors), any other per-vertex scalar properties, and the faces
+//! triangulated into `Triangle`s. Files without faces are point clouds. A mesh can be added
+//! to a `GltfBuilder` with `create_ply_mesh`, or its data passed to `create_custom_mesh`.
+//!
+//! Texture coordinates are flipped vertically on read and write, as PLY places the texture
+//! origin at the bottom left like OBJ.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use mesh_tools::ply::{read_ply, write_ply, PlyFormat};
+//!
+//! let source = b"ply
+//! format ascii 1.0
+//! element vertex 3
+//! property float x
+//! property float y
+//! property float z
+//! property uchar red
+//! property uchar green
+//! property uchar blue
+//! property float confidence
+//! element face 1
+//! property list uchar int vertex_indices
+//! end_header
+//! 0 0 0 255 0 0 0.5
+//! 1 0 0 0 255 0 0.75
+//! 0 1 0 0 0 255 1
+//! 3 0 1 2
+//! ";
+//!
+//! let mesh = read_ply(source).unwrap();
+//! assert_eq!(mesh.positions.len(), 3);
+//! assert_eq!(mesh.indices.len(), 1);
+//! assert_eq!(mesh.colors.as_ref().unwrap()[1], [0.0, 1.0, 0.0, 1.0]);
+//! assert_eq!(mesh.properties[0].name, "confidence");
+//!
+//! // Convert to binary
+//! let mut bytes = Vec::new();
+//! write_ply(&mut bytes, &mesh, PlyFormat::BinaryLittleEndian).unwrap();
+//! assert_eq!(read_ply(&bytes).unwrap().properties[0].values, vec![0.5, 0.75, 1.0]);
+//! ```
+
+use std::io::Write;
+use byteorder::{BigEndian, ByteOrder, LittleEndian, WriteBytesExt};
+
+use crate::builder_primitives::Triangle;
+use crate::compat::{Point3, Vector2, Vector3};
+use crate::error::{GltfError, Result};
+
+/// PLY encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlyFormat {
+    /// Human-readable ASCII encoding
+    Ascii,
+    /// Binary encoding with little-endian values
+    BinaryLittleEndian,
+    /// Binary encoding with big-endian values
+    BinaryBigEndian,
+}
+
+/// A per-vertex scalar property without a glTF counterpart, such as confidence or intensity
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlyProperty {
+    /// Property name from the PLY header
+    pub name: String,
+    /// One value per vertex
+    pub values: Vec<f32>,
+}
+
+/// Vertex and face data read from, or to be written to, a PLY file
+#[derive(Debug, Clone, Default)]
+pub struct PlyMesh {
+    /// Vertex positions (`x`, `y`, `z`)
+    pub positions: Vec<Point3<f32>>,
+    /// Vertex normals (`nx`, `ny`, `nz`)
+    pub normals: Option<Vec<Vector3<f32>>>,
+    /// Texture coordinates (`s`/`t`, `u`/`v` or `texture_u`/`texture_v`)
+    pub texcoords: Option<Vec<Vector2<f32>>>,
+    /// RGBA vertex colors in the range 0.0-1.0 (`red`, `green`, `blue`, `alpha`)
+    pub colors: Option<Vec<[f32; 4]>>,
+    /// Other per-vertex scalar properties
+    pub properties: Vec<PlyProperty>,
+    /// Faces triangulated as triangle fans; empty for point clouds
+    pub indices: Vec<Triangle>,
+}
+
+impl PlyMesh {
+    /// Check that every vertex attribute has one value per position
+    pub fn validate(&self) -> Result<()> {
+        let count = self.positions.len();
+        let mismatch = |name: &str, len: usize| -> Result<()> {
+            if len == count {
+                Ok(())
+            } else {
+                Err(GltfError::InvalidData(format!(
+                    "PLY {} has {} values but there are {} vertices", name, len, count
+                )))
+            }
+        };
+        
+        if let Some(normals) = &self.normals {
+            mismatch("normals", normals.len())?;
+        }
+        if let Some(texcoords) = &self.texcoords {
+            mismatch("texture coordinates", texcoords.len())?;
+        }
+        if let Some(colors) = &self.colors {
+            mismatch("colors", colors.len())?;
+        }
+        for property in &self.properties {
+            mismatch(&format!("property '{}'", property.name), property.values.len())?;
+        }
+        
+        if let Some(triangle) = self.indices.iter().find(|t| t.a.max(t.b).max(t.c) as usize >= count) {
+            return Err(GltfError::InvalidData(format!(
+                "PLY face {:?} references a vertex beyond {}", triangle, count
+            )));
+        }
+        
+        Ok(())
+    }
+}
+
+/// Scalar types allowed in PLY headers
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ScalarType {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl ScalarType {
+    fn parse(name: &str) -> Result<Self> {
+        match name {
+            "char" | "int8" => Ok(ScalarType::I8),
+            "uchar" | "uint8" => Ok(ScalarType::U8),
+            "short" | "int16" => Ok(ScalarType::I16),
+            "ushort" | "uint16" => Ok(ScalarType::U16),
+            "int" | "int32" => Ok(ScalarType::I32),
+            "uint" | "uint32" => Ok(ScalarType::U32),
+            "float" | "float32" => Ok(ScalarType::F32),
+            "double" | "float64" => Ok(ScalarType::F64),
+            _ => Err(GltfError::InvalidData(format!("Unknown PLY property type '{}'", name))),
+        }
+    }
+    
+    fn size(self) -> usize {
+        match self {
+            ScalarType::I8 | ScalarType::U8 => 1,
+            ScalarType::I16 | ScalarType::U16 => 2,
+            ScalarType::I32 | ScalarType::U32 | ScalarType::F32 => 4,
+            ScalarType::F64 => 8,
+        }
+    }
+    
+    /// Scale that maps the full range of an unsigned color channel to 0.0-1.0
+    fn color_scale(self) -> f64 {
+        match self {
+            ScalarType::U8 => 255.0,
+            ScalarType::U16 => 65535.0,
+            _ => 1.0,
+        }
+    }
+}
+
+/// A property declared in the PLY header
+#[derive(Debug)]
+struct PropertyDef {
+    name: String,
+    /// Type of the item count for list properties
+    count_type: Option<ScalarType>,
+    value_type: ScalarType,
+}
+
+/// An element declared in the PLY header
+#[derive(Debug)]
+struct ElementDef {
+    name: String,
+    count: usize,
+    properties: Vec<PropertyDef>,
+}
+
+/// Reader over the body of a PLY file
+enum Body<'a> {
+    Ascii(std::str::SplitAsciiWhitespace<'a>),
+    Binary { data: &'a [u8], offset: usize, big_endian: bool },
+}
+
+impl Body<'_> {
+    fn read(&mut self, value_type: ScalarType) -> Result<f64> {
+        match self {
+            Body::Ascii(tokens) => {
+                let token = tokens.next()
+                    .ok_or_else(|| GltfError::InvalidData("PLY data ends early".to_string()))?;
+                token.parse()
+                    .map_err(|_| GltfError::InvalidData(format!("Invalid PLY value '{}'", token)))
+            }
+            Body::Binary { data, offset, big_endian } => {
+                let size = value_type.size();
+                let bytes = data.get(*offset..*offset + size)
+                    .ok_or_else(|| GltfError::InvalidData("PLY data ends early".to_string()))?;
+                *offset += size;
+                
+                Ok(if *big_endian {
+                    read_binary::<BigEndian>(bytes, value_type)
+                } else {
+                    read_binary::<LittleEndian>(bytes, value_type)
+                })
+            }
+        }
+    }
+}
+
+/// Decode a single binary value
+fn read_binary<B: ByteOrder>(bytes: &[u8], value_type: ScalarType) -> f64 {
+    match value_type {
+        ScalarType::I8 => bytes[0] as i8 as f64,
+        ScalarType::U8 => bytes[0] as f64,
+        ScalarType::I16 => B::read_i16(bytes) as f64,
+        ScalarType::U16 => B::read_u16(bytes) as f64,
+        ScalarType::I32 => B::read_i32(bytes) as f64,
+        ScalarType::U32 => B::read_u32(bytes) as f64,
+        ScalarType::F32 => B::read_f32(bytes) as f64,
+        ScalarType::F64 => B::read_f64(bytes),
+    }
+}
+
+/// Property name pairs recognized as texture coordinates
+const TEXCOORD_NAMES: [(&str, &str); 4] = [
+    ("s", "t"),
+    ("u", "v"),
+    ("texture_u", "texture_v"),
+    ("texture_s", "texture_t"),
+];
+
+/// Read a PLY file in any of its encodings
+///
+/// Only the `vertex` and `face` elements are used; other elements are skipped. Faces are
+/// read from the `vertex_indices` (or `vertex_index`) list property.
+///
+/// # Parameters
+/// * `bytes` - The contents of the PLY file
+///
+/// # Returns
+/// The vertex and face data
+pub fn read_ply(bytes: &[u8]) -> Result<PlyMesh> {
+    let (format, elements, body_start) = read_header(bytes)?;
+    let data = &bytes[body_start..];
+    
+    let mut body = match format {
+        PlyFormat::Ascii => Body::Ascii(std::str::from_utf8(data)
+            .map_err(|_| GltfError::InvalidData("ASCII PLY data is not valid UTF-8".to_string()))?
+            .split_ascii_whitespace()),
+        PlyFormat::BinaryLittleEndian => Body::Binary { data, offset: 0, big_endian: false },
+        PlyFormat::BinaryBigEndian => Body::Binary { data, offset: 0, big_endian: true },
+    };
+    
+    let mut mesh = PlyMesh::default();
+    
+    for element in &elements {
+        match element.name.as_str() {
+            "vertex" => read_vertices(&mut body, element, &mut mesh)?,
+            "face" => read_faces(&mut body, element, &mut mesh)?,
+            _ => {
+                for _ in 0..element.count {
+                    read_record(&mut body, element)?;
+                }
+            }
+        }
+    }
+    
+    mesh.validate()?;
+    
+    Ok(mesh)
+}
+
+/// Parse the header, returning the encoding, the elements and the offset of the body
+fn read_header(bytes: &[u8]) -> Result<(PlyFormat, Vec<ElementDef>, usize)> {
+    let mut format = None;
+    let mut elements: Vec<ElementDef> = Vec::new();
+    let mut offset = 0;
+    let mut first = true;
+    
+    loop {
+        let end = bytes[offset..].iter().position(|&b| b == b'\n')
+            .map(|position| offset + position + 1)
+            .ok_or_else(|| GltfError::InvalidData("PLY header has no end_header".to_string()))?;
+        let line = String::from_utf8_lossy(&bytes[offset..end]);
+        offset = end;
+        
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        
+        if first {
+            if tokens != ["ply"] {
+                return Err(GltfError::InvalidData("Not a PLY file".to_string()));
+            }
+            first = false;
+            continue;
+        }
+        
+        match tokens.as_slice() {
+            ["format", name, _version] => {
+                format = Some(match *name {
+                    "ascii" => PlyFormat::Ascii,
+                    "binary_little_endian" => PlyFormat::BinaryLittleEndian,
+                    "binary_big_endian" => PlyFormat::BinaryBigEndian,
+                    _ => return Err(GltfError::InvalidData(format!("Unknown PLY format '{}'", name))),
+                });
+            }
+            ["element", name, count] => elements.push(ElementDef {
+                name: name.to_string(),
+                count: count.parse()
+                    .map_err(|_| GltfError::InvalidData(format!("Invalid PLY element count '{}'", count)))?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", count_type, value_type, name] => {
+                let element = elements.last_mut()
+                    .ok_or_else(|| GltfError::InvalidData("PLY property before any element".to_string()))?;
+                element.properties.push(PropertyDef {
+                    name: name.to_string(),
+                    count_type: Some(ScalarType::parse(count_type)?),
+                    value_type: ScalarType::parse(value_type)?,
+                });
+            }
+            ["property", value_type, name] => {
+                let element = elements.last_mut()
+                    .ok_or_else(|| GltfError::InvalidData("PLY property before any element".to_string()))?;
+                element.properties.push(PropertyDef {
+                    name: name.to_string(),
+                    count_type: None,
+                    value_type: ScalarType::parse(value_type)?,
+                });
+            }
+            ["end_header"] => break,
+            _ => {} // comment, obj_info and blank lines
+        }
+    }
+    
+    let format = format.ok_or_else(|| GltfError::InvalidData("PLY header has no format".to_string()))?;
+    
+    // Records without properties take no space, so their count could never be checked against the body
+    if let Some(element) = elements.iter().find(|element| element.count > 0 && element.properties.is_empty()) {
+        return Err(GltfError::InvalidData(format!("PLY element '{}' has records but no properties", element.name)));
+    }
+    
+    Ok((format, elements, offset))
+}
+
+/// Read one record of an element, returning its scalar values and its lists
+fn read_record(body: &mut Body, element: &ElementDef) -> Result<(Vec<f64>, Vec<Vec<f64>>)> {
+    let mut scalars = Vec::with_capacity(element.properties.len());
+    let mut lists = Vec::new();
+    
+    for property in &element.properties {
+        match property.count_type {
+            Some(count_type) => {
+                let count = body.read(count_type)? as usize;
+                let list = (0..count)
+                    .map(|_| body.read(property.value_type))
+                    .collect::<Result<Vec<_>>>()?;
+                lists.push(list);
+                scalars.push(0.0);
+            }
+            None => scalars.push(body.read(property.value_type)?),
+        }
+    }
+    
+    Ok((scalars, lists))
+}
+
+/// Read the vertex element into positions, normals, texture coordinates, colors and properties
+fn read_vertices(body: &mut Body, element: &ElementDef, mesh: &mut PlyMesh) -> Result<()> {
+    let find = |name: &str| element.properties.iter()
+        .position(|property| property.name == name && property.count_type.is_none());
+    let find_all = |names: &[&str]| names.iter().map(|name| find(name)).collect::<Option<Vec<_>>>();
+    
+    let position = find_all(&["x", "y", "z"])
+        .ok_or_else(|| GltfError::InvalidData("PLY vertices have no x, y and z properties".to_string()))?;
+    let normal = find_all(&["nx", "ny", "nz"]);
+    let texcoord = TEXCOORD_NAMES.iter().find_map(|(u, v)| find_all(&[u, v]));
+    let color = find_all(&["red", "green", "blue"]);
+    let alpha = find("alpha");
+    
+    // Every remaining scalar property is kept as a custom property
+    let used: Vec<usize> = position.iter()
+        .chain(normal.iter().flatten())
+        .chain(texcoord.iter().flatten())
+        .chain(color.iter().flatten())
+        .chain(alpha.iter())
+        .copied()
+        .collect();
+    let custom: Vec<usize> = (0..element.properties.len())
+        .filter(|index| !used.contains(index) && element.properties[*index].count_type.is_none())
+        .collect();
+    
+    let mut normals = Vec::new();
+    let mut texcoords = Vec::new();
+    let mut colors = Vec::new();
+    mesh.properties = custom.iter()
+        .map(|&index| PlyProperty {
+            name: element.properties[index].name.clone(),
+            // The element count comes from the header, so it is not trusted for reservations
+            values: Vec::new(),
+        })
+        .collect();
+    
+    for _ in 0..element.count {
+        let (values, _) = read_record(body, element)?;
+        let value = |index: usize| values[index] as f32;
+        
+        mesh.positions.push(crate::compat::point3::new(value(position[0]), value(position[1]), value(position[2])));
+        
+        if let Some(normal) = &normal {
+            normals.push(crate::compat::vector3::new(value(normal[0]), value(normal[1]), value(normal[2])));
+        }
+        
+        if let Some(texcoord) = &texcoord {
+            texcoords.push(crate::compat::vector2::new(value(texcoord[0]), 1.0 - value(texcoord[1])));
+        }
+        
+        if let Some(color) = &color {
+            let channel = |index: usize| (values[index] / element.properties[index].value_type.color_scale()) as f32;
+            colors.push([
+                channel(color[0]),
+                channel(color[1]),
+                channel(color[2]),
+                alpha.map_or(1.0, channel),
+            ]);
+        }
+        
+        for (property, &index) in mesh.properties.iter_mut().zip(&custom) {
+            property.values.push(value(index));
+        }
+    }
+    
+    mesh.normals = normal.map(|_| normals);
+    mesh.texcoords = texcoord.map(|_| texcoords);
+    mesh.colors = color.map(|_| colors);
+    
+    Ok(())
+}
+
+/// Read the face element, triangulating polygons as fans
+fn read_faces(body: &mut Body, element: &ElementDef, mesh: &mut PlyMesh) -> Result<()> {
+    let list_index = element.properties.iter()
+        .filter(|property| property.count_type.is_some())
+        .position(|property| property.name == "vertex_indices" || property.name == "vertex_index");
+    
+    for _ in 0..element.count {
+        let (_, lists) = read_record(body, element)?;
+        let face = match list_index {
+            Some(index) => &lists[index],
+            None => continue,
+        };
+        
+        let face = face.iter().map(|&index| vertex_index(index)).collect::<Result<Vec<_>>>()?;
+        for i in 1..face.len().saturating_sub(1) {
+            mesh.indices.push(Triangle::new(face[0], face[i], face[i + 1]));
+        }
+    }
+    
+    Ok(())
+}
+
+/// Convert a face list entry to a vertex index
+fn vertex_index(value: f64) -> Result<u32> {
+    if value < 0.0 || value > u32::MAX as f64 || value.fract() != 0.0 {
+        return Err(GltfError::InvalidData(format!("Invalid PLY vertex index {}", value)));
+    }
+    
+    Ok(value as u32)
+}
+
+/// Write a mesh as a PLY file
+///
+/// Positions, normals, texture coordinates and custom properties are written as `float`,
+/// colors as `uchar` and faces as a `uchar`/`uint` list. The face element is omitted for
+/// point clouds.
+///
+/// # Parameters
+/// * `writer` - Destination for the PLY data
+/// * `mesh` - The vertex and face data
+/// * `format` - ASCII or binary encoding
+pub fn write_ply<W: Write>(writer: &mut W, mesh: &PlyMesh, format: PlyFormat) -> Result<()> {
+    mesh.validate()?;
+    
+    let format_name = match format {
+        PlyFormat::Ascii => "ascii",
+        PlyFormat::BinaryLittleEndian => "binary_little_endian",
+        PlyFormat::BinaryBigEndian => "binary_big_endian",
+    };
+    
+    writeln!(writer, "ply")?;
+    writeln!(writer, "format {} 1.0", format_name)?;
+    writeln!(writer, "comment Exported by mesh-tools")?;
+    writeln!(writer, "element vertex {}", mesh.positions.len())?;
+    for name in ["x", "y", "z"] {
+        writeln!(writer, "property float {}", name)?;
+    }
+    if mesh.normals.is_some() {
+        for name in ["nx", "ny", "nz"] {
+            writeln!(writer, "property float {}", name)?;
+        }
+    }
+    if mesh.texcoords.is_some() {
+        for name in ["s", "t"] {
+            writeln!(writer, "property float {}", name)?;
+        }
+    }
+    if mesh.colors.is_some() {
+        for name in ["red", "green", "blue", "alpha"] {
+            writeln!(writer, "property uchar {}", name)?;
+        }
+    }
+    for property in &mesh.properties {
+        writeln!(writer, "property float {}", property.name)?;
+    }
+    if !mesh.indices.is_empty() {
+        writeln!(writer, "element face {}", mesh.indices.len())?;
+        writeln!(writer, "property list uchar uint vertex_indices")?;
+    }
+    writeln!(writer, "end_header")?;
+    
+    let mut record = RecordWriter { writer, format, first: true };
+    
+    for (index, p) in mesh.positions.iter().enumerate() {
+        for value in [p.x, p.y, p.z] {
+            record.write_f32(value)?;
+        }
+        if let Some(normals) = &mesh.normals {
+            let n = normals[index];
+            for value in [n.x, n.y, n.z] {
+                record.write_f32(value)?;
+            }
+        }
+        if let Some(texcoords) = &mesh.texcoords {
+            record.write_f32(texcoords[index].x)?;
+            record.write_f32(1.0 - texcoords[index].y)?;
+        }
+        if let Some(colors) = &mesh.colors {
+            for channel in colors[index] {
+                record.write_u8((channel.clamp(0.0, 1.0) * 255.0).round() as u8)?;
+            }
+        }
+        for property in &mesh.properties {
+            record.write_f32(property.values[index])?;
+        }
+        record.end()?;
+    }
+    
+    for triangle in &mesh.indices {
+        record.write_u8(3)?;
+        for index in [triangle.a, triangle.b, triangle.c] {
+            record.write_u32(index)?;
+        }
+        record.end()?;
+    }
+    
+    Ok(())
+}
+
+/// Writes the values of PLY records in the chosen encoding
+struct RecordWriter<'a, W: Write> {
+    writer: &'a mut W,
+    format: PlyFormat,
+    first: bool,
+}
+
+impl<W: Write> RecordWriter<'_, W> {
+    fn write_ascii(&mut self, value: impl std::fmt::Display) -> Result<()> {
+        if !self.first {
+            write!(self.writer, " ")?;
+        }
+        self.first = false;
+        write!(self.writer, "{}", value)?;
+        
+        Ok(())
+    }
+    
+    fn write_f32(&mut self, value: f32) -> Result<()> {
+        match self.format {
+            PlyFormat::Ascii => self.write_ascii(value),
+            PlyFormat::BinaryLittleEndian => Ok(self.writer.write_f32::<LittleEndian>(value)?),
+            PlyFormat::BinaryBigEndian => Ok(self.writer.write_f32::<BigEndian>(value)?),
+        }
+    }
+    
+    fn write_u32(&mut self, value: u32) -> Result<()> {
+        match self.format {
+            PlyFormat::Ascii => self.write_ascii(value),
+            PlyFormat::BinaryLittleEndian => Ok(self.writer.write_u32::<LittleEndian>(value)?),
+            PlyFormat::BinaryBigEndian => Ok(self.writer.write_u32::<BigEndian>(value)?),
+        }
+    }
+    
+    fn write_u8(&mut self, value: u8) -> Result<()> {
+        match self.format {
+            PlyFormat::Ascii => self.write_ascii(value),
+            _ => Ok(self.writer.write_u8(value)?),
+        }
+    }
+    
+    /// Finish a record, which ends the line in the ASCII encoding
+    fn end(&mut self) -> Result<()> {
+        if self.format == PlyFormat::Ascii {
+            writeln!(self.writer)?;
+        }
+        self.first = true;
+        
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const HEADER: &str = "ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\nproperty float y\nproperty float z\n";
+    
+    #[test]
+    fn huge_vertex_count_is_an_error() {
+        let source = "ply\nformat ascii 1.0\nelement vertex 1152921504606846976\nproperty float x\n\
+                      property float y\nproperty float z\nproperty float confidence\nend_header\n0 0 0 1\n";
+        
+        assert!(read_ply(source.as_bytes()).is_err());
+    }
+    
+    #[test]
+    fn huge_count_without_properties_is_an_error() {
+        let source = format!("{}element face 18446744073709551615\nend_header\n0 0 0\n1 0 0\n0 1 0\n", HEADER);
+        
+        assert!(read_ply(source.as_bytes()).is_err());
+    }
+    
+    #[test]
+    fn negative_face_index_is_an_error() {
+        let source = format!(
+            "{}element face 1\nproperty list uchar int vertex_indices\nend_header\n0 0 0\n1 0 0\n0 1 0\n3 0 1 -1\n",
+            HEADER
+        );
+        
+        assert!(read_ply(source.as_bytes()).is_err());
+    }
+    
+    #[test]
+    fn polygons_are_triangulated() {
+        let source = format!(
+            "{}element face 1\nproperty list uchar int vertex_indices\nend_header\n0 0 0\n1 0 0\n0 1 0\n3 0 1 2\n",
+            HEADER
+        );
+        
+        let mesh = read_ply(source.as_bytes()).unwrap();
+        
+        assert_eq!(mesh.indices.len(), 1);
+        assert_eq!((mesh.indices[0].a, mesh.indices[0].b, mesh.indices[0].c), (0, 1, 2));
+    }
+}
@@ -25,6 +25,9 @@ use std::f32::consts::PI;
 use crate::compat::{Point3, Vector2, Vector3};
 use crate::builder_primitives::Triangle;
 
+/// Geometry of a generated shape: positions, triangles, normals and texture coordinates
+pub type GeometryData = (Vec<Point3<f32>>, Vec<Triangle>, Vec<Vector3<f32>>, Vec<Vector2<f32>>);
+
 /// Generate a plane (flat surface) with subdivisions
 /// 
 /// # Parameters
@@ -40,7 +43,7 @@ pub fn generate_plane(
     depth: f32,
     width_segments: usize,
     depth_segments: usize,
-) -> (Vec<Point3<f32>>, Vec<Triangle>, Vec<Vector3<f32>>, Vec<Vector2<f32>>) {
+) -> GeometryData {
     let width_half = width / 2.0;
     let depth_half = depth / 2.0;
     
@@ -110,7 +113,7 @@ pub fn generate_sphere(
     radius: f32,
     width_segments: usize,
     height_segments: usize,
-) -> (Vec<Point3<f32>>, Vec<Triangle>, Vec<Vector3<f32>>, Vec<Vector2<f32>>) {
+) -> GeometryData {
     let width_segments = width_segments.max(3);
     let height_segments = height_segments.max(2);
     
@@ -189,7 +192,7 @@ pub fn generate_cylinder(
     radial_segments: usize,
     height_segments: usize,
     open_ended: bool,
-) -> (Vec<Point3<f32>>, Vec<Triangle>, Vec<Vector3<f32>>, Vec<Vector2<f32>>) {
+) -> GeometryData {
     let radial_segments = radial_segments.max(3);
     let height_segments = height_segments.max(1);
     
@@ -362,7 +365,7 @@ pub fn generate_cone(
     radial_segments: usize,
     height_segments: usize,
     open_ended: bool,
-) -> (Vec<Point3<f32>>, Vec<Triangle>, Vec<Vector3<f32>>, Vec<Vector2<f32>>) {
+) -> GeometryData {
     generate_cylinder(0.0, radius, height, radial_segments, height_segments, open_ended)
 }
 
@@ -381,7 +384,7 @@ pub fn generate_torus(
     tube: f32,
     radial_segments: usize,
     tubular_segments: usize,
-) -> (Vec<Point3<f32>>, Vec<Triangle>, Vec<Vector3<f32>>, Vec<Vector2<f32>>) {
+) -> GeometryData {
     let radial_segments = radial_segments.max(2);
     let tubular_segments = tubular_segments.max(3);
     
@@ -444,7 +447,7 @@ pub fn generate_torus(
 /// 
 /// # Returns
 /// Tuple of (positions, indices, normals, uvs)
-pub fn generate_icosahedron(radius: f32) -> (Vec<Point3<f32>>, Vec<Triangle>, Vec<Vector3<f32>>, Vec<Vector2<f32>>) {
+pub fn generate_icosahedron(radius: f32) -> GeometryData {
     // Constants for icosahedron construction
     let t = (1.0 + 5.0_f32.sqrt()) / 2.0;
     
@@ -0,0 +1,314 @@
+//! # STL Reading and Writing
+//!
+//! This module reads and writes the STL format used by 3D printing and CAD tools. STL stores
+//! a plain list of triangles ("facets"), each with its own normal and three corner positions,
+//! in either a binary or an ASCII encoding.
+//!
+//! `read_stl` detects the encoding and welds the corners of the facets into shared vertices,
+//! so the result can be passed straight to `GltfBuilder::create_custom_mesh`. Whole scenes are
+//! written with `GltfBuilder::export_stl`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use mesh_tools::GltfBuilder;
+//! use mesh_tools::stl::{read_stl, StlFormat};
+//!
+//! let mut builder = GltfBuilder::new();
+//! let box_mesh = builder.create_box(1.0);
+//! let node = builder.add_node(None, Some(box_mesh), None, None, None);
+//! builder.add_scene(None, Some(vec![node]));
+//!
+//! let mut bytes = Vec::new();
+//! builder.write_stl(&mut bytes, StlFormat::Binary).unwrap();
+//!
+//! // The 24 box vertices are welded back into the 8 corners
+//! let stl = read_stl(&bytes).unwrap();
+//! assert_eq!(stl.positions.len(), 8);
+//! assert_eq!(stl.indices.len(), 12);
+//!
+//! let mut imported = GltfBuilder::new();
+//! imported.create_custom_mesh(stl.name, &stl.positions, &stl.indices, None, None, None);
+//! ```
+
+use std::collections::HashMap;
+use std::io::Write;
+use byteorder::{ByteOrder, LittleEndian, WriteBytesExt};
+
+use crate::builder_primitives::Triangle;
+use crate::compat::Point3;
+use crate::error::{GltfError, Result};
+
+/// Size of the binary STL header in bytes
+const HEADER_SIZE: usize = 80;
+
+/// Size of a binary STL facet record in bytes
+const FACET_SIZE: usize = 50;
+
+/// STL encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StlFormat {
+    /// Compact binary encoding
+    Binary,
+    /// Human-readable ASCII encoding
+    Ascii,
+}
+
+/// A triangle mesh read from an STL file
+#[derive(Debug, Clone, Default)]
+pub struct StlMesh {
+    /// Solid name from an ASCII file, if any
+    pub name: Option<String>,
+    /// Welded vertex positions
+    pub positions: Vec<Point3<f32>>,
+    /// Triangles indexing into `positions`
+    pub indices: Vec<Triangle>,
+    /// Number of facets left out of `indices` because two of their corners are the same point
+    pub degenerate_facets: usize,
+}
+
+/// A single facet: three corners in counter-clockwise order
+pub type Facet = [[f32; 3]; 3];
+
+/// Read an STL file in either encoding
+///
+/// Corners with exactly the same coordinates are merged into one vertex. Facet normals are
+/// ignored, as they can be recomputed from the winding order. Facets with two identical
+/// corners have no area and are left out; their number is reported in `degenerate_facets`.
+///
+/// # Parameters
+/// * `bytes` - The contents of the STL file
+///
+/// # Returns
+/// The welded triangle mesh
+pub fn read_stl(bytes: &[u8]) -> Result<StlMesh> {
+    let (name, facets) = if is_ascii(bytes) {
+        read_ascii(&String::from_utf8_lossy(bytes))?
+    } else {
+        (None, read_binary(bytes)?)
+    };
+    
+    let mut mesh = StlMesh {
+        name,
+        ..Default::default()
+    };
+    let mut vertices: HashMap<[u32; 3], u32> = HashMap::new();
+    
+    for facet in &facets {
+        let [a, b, c] = facet.map(|corner| {
+            // Treat -0.0 and 0.0 as the same coordinate
+            let key = corner.map(|value| (value + 0.0).to_bits());
+            *vertices.entry(key).or_insert_with(|| {
+                mesh.positions.push(crate::compat::point3::new(corner[0], corner[1], corner[2]));
+                (mesh.positions.len() - 1) as u32
+            })
+        });
+        
+        // Facets that collapse after welding have no area
+        if a != b && b != c && a != c {
+            mesh.indices.push(Triangle::new(a, b, c));
+        } else {
+            mesh.degenerate_facets += 1;
+        }
+    }
+    
+    Ok(mesh)
+}
+
+/// Write facets in the given encoding, computing each facet normal from its corners
+///
+/// # Parameters
+/// * `writer` - Destination for the STL data
+/// * `name` - Solid name, used by the ASCII encoding
+/// * `facets` - Triangles to write
+/// * `format` - Binary or ASCII encoding
+pub fn write_stl<W: Write>(writer: &mut W, name: &str, facets: &[Facet], format: StlFormat) -> Result<()> {
+    match format {
+        StlFormat::Binary => {
+            let count = u32::try_from(facets.len())
+                .map_err(|_| GltfError::InvalidData("Too many facets for binary STL".to_string()))?;
+            
+            // The header must not start with "solid", or readers may mistake the file for ASCII
+            let mut header = [0u8; HEADER_SIZE];
+            let text = b"Binary STL exported by mesh-tools";
+            header[..text.len()].copy_from_slice(text);
+            writer.write_all(&header)?;
+            writer.write_u32::<LittleEndian>(count)?;
+            
+            for facet in facets {
+                for value in facet_normal(facet).iter().chain(facet.iter().flatten()) {
+                    writer.write_f32::<LittleEndian>(*value)?;
+                }
+                writer.write_u16::<LittleEndian>(0)?;
+            }
+        }
+        StlFormat::Ascii => {
+            let name = name.split_whitespace().collect::<Vec<_>>().join("_");
+            writeln!(writer, "solid {}", name)?;
+            
+            for facet in facets {
+                let [nx, ny, nz] = facet_normal(facet);
+                writeln!(writer, "  facet normal {:e} {:e} {:e}", nx, ny, nz)?;
+                writeln!(writer, "    outer loop")?;
+                for [x, y, z] in facet {
+                    writeln!(writer, "      vertex {:e} {:e} {:e}", x, y, z)?;
+                }
+                writeln!(writer, "    endloop")?;
+                writeln!(writer, "  endfacet")?;
+            }
+            
+            writeln!(writer, "endsolid {}", name)?;
+        }
+    }
+    
+    Ok(())
+}
+
+/// Unit normal of a facet from its counter-clockwise winding, or zero for degenerate facets
+fn facet_normal(facet: &Facet) -> [f32; 3] {
+    let [a, b, c] = facet.map(|[x, y, z]| crate::compat::vector3::new(x, y, z));
+    let ab = crate::compat::vector3::new(b.x - a.x, b.y - a.y, b.z - a.z);
+    let ac = crate::compat::vector3::new(c.x - a.x, c.y - a.y, c.z - a.z);
+    let normal = crate::compat::normalize(crate::compat::cross(ab, ac));
+    
+    [normal.x, normal.y, normal.z]
+}
+
+/// Check whether STL data uses the ASCII encoding
+///
+/// Some binary files also start with "solid", so the size implied by the facet count
+/// of the binary encoding is checked as well.
+fn is_ascii(bytes: &[u8]) -> bool {
+    if !bytes.trim_ascii_start().starts_with(b"solid") {
+        return false;
+    }
+    
+    if bytes.len() >= HEADER_SIZE + 4 {
+        let count = LittleEndian::read_u32(&bytes[HEADER_SIZE..HEADER_SIZE + 4]) as usize;
+        if binary_size(count) == Some(bytes.len()) {
+            return false;
+        }
+    }
+    
+    true
+}
+
+/// Size of a binary STL file with `count` facets, or `None` if it does not fit in `usize`
+fn binary_size(count: usize) -> Option<usize> {
+    count.checked_mul(FACET_SIZE)?.checked_add(HEADER_SIZE + 4)
+}
+
+/// Read the facets of a binary STL file
+fn read_binary(bytes: &[u8]) -> Result<Vec<Facet>> {
+    if bytes.len() < HEADER_SIZE + 4 {
+        return Err(GltfError::InvalidData("Binary STL is shorter than its header".to_string()));
+    }
+    
+    let count = LittleEndian::read_u32(&bytes[HEADER_SIZE..HEADER_SIZE + 4]) as usize;
+    let data = &bytes[HEADER_SIZE + 4..];
+    
+    // The facet count is a u32, so the size can overflow a 32-bit usize
+    if count.checked_mul(FACET_SIZE).is_none_or(|size| data.len() < size) {
+        return Err(GltfError::InvalidData(format!(
+            "Binary STL declares {} facets but contains only {}", count, data.len() / FACET_SIZE
+        )));
+    }
+    
+    Ok(data.chunks_exact(FACET_SIZE)
+        .take(count)
+        .map(|record| {
+            // Skip the stored normal (12 bytes) and read the three corners
+            let value = |index: usize| LittleEndian::read_f32(&record[12 + index * 4..]);
+            [
+                [value(0), value(1), value(2)],
+                [value(3), value(4), value(5)],
+                [value(6), value(7), value(8)],
+            ]
+        })
+        .collect())
+}
+
+/// Read the solid name and facets of an ASCII STL file
+fn read_ascii(source: &str) -> Result<(Option<String>, Vec<Facet>)> {
+    let mut name = None;
+    let mut facets = Vec::new();
+    let mut corners = Vec::with_capacity(3);
+    
+    for (index, line) in source.lines().enumerate() {
+        let mut tokens = line.split_whitespace();
+        
+        match tokens.next() {
+            Some("solid") if name.is_none() => {
+                let rest: Vec<_> = tokens.collect();
+                if !rest.is_empty() {
+                    name = Some(rest.join(" "));
+                }
+            }
+            Some("vertex") => {
+                let mut coordinate = || -> Result<f32> {
+                    tokens.next()
+                        .and_then(|token| token.parse().ok())
+                        .ok_or_else(|| GltfError::InvalidData(format!("Line {}: invalid vertex", index + 1)))
+                };
+                corners.push([coordinate()?, coordinate()?, coordinate()?]);
+            }
+            Some("endloop") => {
+                if corners.len() != 3 {
+                    return Err(GltfError::InvalidData(format!(
+                        "Line {}: facet has {} vertices instead of 3", index + 1, corners.len()
+                    )));
+                }
+                facets.push([corners[0], corners[1], corners[2]]);
+                corners.clear();
+            }
+            _ => {}
+        }
+    }
+    
+    Ok((name, facets))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// Binary STL data with the given facet count in its header, followed by the facets
+    fn binary(count: u32, facets: &[Facet]) -> Vec<u8> {
+        let mut bytes = vec![0; HEADER_SIZE];
+        bytes.extend_from_slice(&count.to_le_bytes());
+        for facet in facets {
+            bytes.extend_from_slice(&[0; 12]);
+            for value in facet.iter().flatten() {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+            bytes.extend_from_slice(&[0; 2]);
+        }
+        bytes
+    }
+    
+    #[test]
+    fn facet_count_beyond_data_is_an_error() {
+        let facet = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+        
+        assert!(read_stl(&binary(u32::MAX, &[facet])).is_err());
+    }
+    
+    #[test]
+    fn binary_size_overflow_is_detected() {
+        assert_eq!(binary_size(usize::MAX / FACET_SIZE + 1), None);
+        assert_eq!(binary_size(2), Some(HEADER_SIZE + 4 + 2 * FACET_SIZE));
+    }
+    
+    #[test]
+    fn degenerate_facets_are_counted() {
+        let facets = [
+            [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+            [[0.0, 0.0, 0.0], [0.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+        ];
+        
+        let mesh = read_stl(&binary(2, &facets)).unwrap();
+        
+        assert_eq!(mesh.indices.len(), 1);
+        assert_eq!(mesh.degenerate_facets, 1);
+    }
+}
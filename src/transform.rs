@@ -0,0 +1,230 @@
+//! # Node Transform Helpers
+//!
+//! This module contains the small amount of matrix math needed to flatten a glTF scene graph:
+//! building node matrices from translation/rotation/scale, composing them down the hierarchy,
+//! and transforming points and normals into world space. It also inverts world matrices
+//! to compute the inverse bind matrices of skins.
+//!
+//! Matrices are stored column-major as `[f32; 16]`, matching `Node.matrix` in glTF.
+
+use std::collections::HashSet;
+
+use crate::models::{Gltf, Node};
+
+/// A column-major 4x4 matrix
+pub(crate) type Mat4 = [f32; 16];
+
+/// The identity matrix
+pub(crate) const IDENTITY: Mat4 = [
+    1.0, 0.0, 0.0, 0.0,
+    0.0, 1.0, 0.0, 0.0,
+    0.0, 0.0, 1.0, 0.0,
+    0.0, 0.0, 0.0, 1.0,
+];
+
+/// Build a matrix from optional translation, rotation (quaternion `[x, y, z, w]`) and scale
+pub(crate) fn from_trs(translation: Option<[f32; 3]>, rotation: Option<[f32; 4]>, scale: Option<[f32; 3]>) -> Mat4 {
+    let [tx, ty, tz] = translation.unwrap_or([0.0, 0.0, 0.0]);
+    let [x, y, z, w] = rotation.unwrap_or([0.0, 0.0, 0.0, 1.0]);
+    let [sx, sy, sz] = scale.unwrap_or([1.0, 1.0, 1.0]);
+    
+    [
+        (1.0 - 2.0 * (y * y + z * z)) * sx,
+        (2.0 * (x * y + z * w)) * sx,
+        (2.0 * (x * z - y * w)) * sx,
+        0.0,
+        (2.0 * (x * y - z * w)) * sy,
+        (1.0 - 2.0 * (x * x + z * z)) * sy,
+        (2.0 * (y * z + x * w)) * sy,
+        0.0,
+        (2.0 * (x * z + y * w)) * sz,
+        (2.0 * (y * z - x * w)) * sz,
+        (1.0 - 2.0 * (x * x + y * y)) * sz,
+        0.0,
+        tx,
+        ty,
+        tz,
+        1.0,
+    ]
+}
+
+/// Get the local matrix of a node, preferring `matrix` over translation/rotation/scale
+pub(crate) fn node_matrix(node: &Node) -> Mat4 {
+    node.matrix.unwrap_or_else(|| from_trs(node.translation, node.rotation, node.scale))
+}
+
+/// Multiply two matrices (`a * b`)
+pub(crate) fn multiply(a: &Mat4, b: &Mat4) -> Mat4 {
+    let mut result = [0.0; 16];
+    
+    for column in 0..4 {
+        for row in 0..4 {
+            result[column * 4 + row] = (0..4)
+                .map(|k| a[k * 4 + row] * b[column * 4 + k])
+                .sum();
+        }
+    }
+    
+    result
+}
+
+/// Invert a matrix
+///
+/// Returns `None` if the matrix is singular.
+pub(crate) fn invert(m: &Mat4) -> Option<Mat4> {
+    // Cofactor expansion using the 2x2 minors of the first and last two rows
+    let s0 = m[0] * m[5] - m[1] * m[4];
+    let s1 = m[0] * m[9] - m[1] * m[8];
+    let s2 = m[0] * m[13] - m[1] * m[12];
+    let s3 = m[4] * m[9] - m[5] * m[8];
+    let s4 = m[4] * m[13] - m[5] * m[12];
+    let s5 = m[8] * m[13] - m[9] * m[12];
+    
+    let c5 = m[10] * m[15] - m[11] * m[14];
+    let c4 = m[6] * m[15] - m[7] * m[14];
+    let c3 = m[6] * m[11] - m[7] * m[10];
+    let c2 = m[2] * m[15] - m[3] * m[14];
+    let c1 = m[2] * m[11] - m[3] * m[10];
+    let c0 = m[2] * m[7] - m[3] * m[6];
+    
+    let det = s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0;
+    if det.abs() < f32::EPSILON * f32::EPSILON {
+        return None;
+    }
+    let inv = 1.0 / det;
+    
+    Some([
+        (m[5] * c5 - m[9] * c4 + m[13] * c3) * inv,
+        (-m[1] * c5 + m[9] * c2 - m[13] * c1) * inv,
+        (m[1] * c4 - m[5] * c2 + m[13] * c0) * inv,
+        (-m[1] * c3 + m[5] * c1 - m[9] * c0) * inv,
+        (-m[4] * c5 + m[8] * c4 - m[12] * c3) * inv,
+        (m[0] * c5 - m[8] * c2 + m[12] * c1) * inv,
+        (-m[0] * c4 + m[4] * c2 - m[12] * c0) * inv,
+        (m[0] * c3 - m[4] * c1 + m[8] * c0) * inv,
+        (m[7] * s5 - m[11] * s4 + m[15] * s3) * inv,
+        (-m[3] * s5 + m[11] * s2 - m[15] * s1) * inv,
+        (m[3] * s4 - m[7] * s2 + m[15] * s0) * inv,
+        (-m[3] * s3 + m[7] * s1 - m[11] * s0) * inv,
+        (-m[6] * s5 + m[10] * s4 - m[14] * s3) * inv,
+        (m[2] * s5 - m[10] * s2 + m[14] * s1) * inv,
+        (-m[2] * s4 + m[6] * s2 - m[14] * s0) * inv,
+        (m[2] * s3 - m[6] * s1 + m[10] * s0) * inv,
+    ])
+}
+
+/// Get the world matrix of a node by composing the matrices of its ancestors
+///
+/// Returns `None` if the node does not exist or its ancestors form a cycle.
+pub(crate) fn world_matrix(gltf: &Gltf, node_index: usize) -> Option<Mat4> {
+    let nodes = gltf.nodes.as_ref()?;
+    let mut world = node_matrix(nodes.get(node_index)?);
+    
+    let mut visited = HashSet::from([node_index]);
+    let mut current = node_index;
+    while let Some(parent) = nodes.iter()
+        .position(|node| node.children.as_ref().is_some_and(|children| children.contains(&current))) {
+        if !visited.insert(parent) {
+            return None;
+        }
+        world = multiply(&node_matrix(&nodes[parent]), &world);
+        current = parent;
+    }
+    
+    Some(world)
+}
+
+/// Transform a point by a matrix
+pub(crate) fn transform_point(m: &Mat4, p: [f32; 3]) -> [f32; 3] {
+    [
+        m[0] * p[0] + m[4] * p[1] + m[8] * p[2] + m[12],
+        m[1] * p[0] + m[5] * p[1] + m[9] * p[2] + m[13],
+        m[2] * p[0] + m[6] * p[1] + m[10] * p[2] + m[14],
+    ]
+}
+
+/// Determinant of the upper-left 3x3 part of a matrix
+///
+/// A negative determinant means the transform mirrors geometry, which flips triangle winding.
+pub(crate) fn determinant3(m: &Mat4) -> f32 {
+    m[0] * (m[5] * m[10] - m[9] * m[6])
+        - m[4] * (m[1] * m[10] - m[9] * m[2])
+        + m[8] * (m[1] * m[6] - m[5] * m[2])
+}
+
+/// Transform a normal by the inverse transpose of the upper-left 3x3 part of a matrix
+///
+/// The result is normalized.
+pub(crate) fn transform_normal(m: &Mat4, n: [f32; 3]) -> [f32; 3] {
+    // The inverse transpose is the cofactor matrix divided by the determinant; the scale
+    // is irrelevant after normalization, apart from its sign
+    let sign = if determinant3(m) < 0.0 { -1.0 } else { 1.0 };
+    
+    let c00 = m[5] * m[10] - m[9] * m[6];
+    let c01 = m[8] * m[6] - m[4] * m[10];
+    let c02 = m[4] * m[9] - m[8] * m[5];
+    let c10 = m[9] * m[2] - m[1] * m[10];
+    let c11 = m[0] * m[10] - m[8] * m[2];
+    let c12 = m[8] * m[1] - m[0] * m[9];
+    let c20 = m[1] * m[6] - m[5] * m[2];
+    let c21 = m[4] * m[2] - m[0] * m[6];
+    let c22 = m[0] * m[5] - m[4] * m[1];
+    
+    let v = crate::compat::vector3::new(
+        sign * (c00 * n[0] + c10 * n[1] + c20 * n[2]),
+        sign * (c01 * n[0] + c11 * n[1] + c21 * n[2]),
+        sign * (c02 * n[0] + c12 * n[1] + c22 * n[2]),
+    );
+    let v = crate::compat::normalize(v);
+    
+    [v.x, v.y, v.z]
+}
+
+/// Collect every node with a mesh that is reachable from the default scene, with its world matrix
+///
+/// Falls back to the first scene when no default scene is set, and to all root nodes
+/// when the document has no scenes. Cycles in the hierarchy are ignored.
+pub(crate) fn mesh_instances(gltf: &Gltf) -> Vec<(usize, Mat4)> {
+    let nodes = match &gltf.nodes {
+        Some(nodes) => nodes,
+        None => return Vec::new(),
+    };
+    
+    let scene_roots = gltf.scenes.as_ref().and_then(|scenes| {
+        scenes.get(gltf.scene.unwrap_or(0))
+            .map(|scene| scene.nodes.clone().unwrap_or_default())
+    });
+    
+    let roots = scene_roots.unwrap_or_else(|| {
+        let children: HashSet<usize> = nodes.iter()
+            .flat_map(|node| node.children.iter().flatten().copied())
+            .collect();
+        
+        (0..nodes.len()).filter(|index| !children.contains(index)).collect()
+    });
+    
+    let mut instances = Vec::new();
+    let mut visited = HashSet::new();
+    let mut stack: Vec<(usize, Mat4)> = roots.into_iter().rev().map(|root| (root, IDENTITY)).collect();
+    
+    while let Some((index, parent)) = stack.pop() {
+        let node = match nodes.get(index) {
+            Some(node) if visited.insert(index) => node,
+            _ => continue,
+        };
+        
+        let world = multiply(&parent, &node_matrix(node));
+        
+        if node.mesh.is_some() {
+            instances.push((index, world));
+        }
+        
+        if let Some(children) = &node.children {
+            for &child in children.iter().rev() {
+                stack.push((child, world));
+            }
+        }
+    }
+    
+    instances
+}
@@ -0,0 +1,1129 @@
+//! # Document Validation
+//!
+//! This module checks a glTF document against the requirements of the glTF 2.0 specification
+//! that the data model cannot enforce by itself: references between objects, the node
+//! hierarchy, accessor and buffer view layout, attribute and animation data counts, and
+//! extension declarations.
+//!
+//! All problems are collected in a `ValidationReport` instead of stopping at the first one.
+//! Errors are violations of the specification that viewers may reject or display incorrectly;
+//! warnings point at likely mistakes that still produce a valid file. Each issue is located
+//! with a JSON pointer into the document, such as `/meshes/0/primitives/1/attributes/NORMAL`.
+//!
+//! `Gltf::validate` checks the document on its own. `GltfBuilder::validate` also checks it
+//! against the binary buffer, and `GltfBuilder::set_validate_on_export` makes the exporters
+//! refuse to write documents with errors.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use mesh_tools::GltfBuilder;
+//!
+//! let mut builder = GltfBuilder::new();
+//! let cube = builder.create_box(1.0);
+//! let node = builder.add_node(None, Some(cube), None, None, None);
+//! builder.add_scene(None, Some(vec![node]));
+//!
+//! let report = builder.validate();
+//! assert!(report.is_valid(), "{}", report);
+//!
+//! // Break the document by referencing a node that does not exist
+//! builder.gltf.scenes.as_mut().unwrap()[0].nodes = Some(vec![7]);
+//!
+//! let report = builder.validate();
+//! let error = report.errors().next().unwrap();
+//! assert_eq!(error.pointer, "/scenes/0/nodes/0");
+//! ```
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+
+use crate::accessor::{component_size, type_components, AccessorReader};
+use crate::constants::{accessor_type, component_type, primitive_mode};
+use crate::models::*;
+
+/// How serious a validation issue is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The document violates the glTF specification
+    Error,
+    /// The document is valid, but probably not as intended
+    Warning,
+}
+
+/// A single problem found in a document
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationIssue {
+    /// Whether the issue is an error or a warning
+    pub severity: Severity,
+    /// JSON pointer to the offending property, e.g. `/nodes/3/children/0`
+    pub pointer: String,
+    /// Description of the problem
+    pub message: String,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{} at {}: {}", severity, self.pointer, self.message)
+    }
+}
+
+/// The result of validating a document
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    /// Every issue found, in document order
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Check whether the document has no errors; warnings are allowed
+    pub fn is_valid(&self) -> bool {
+        self.errors().next().is_none()
+    }
+    
+    /// Get the issues that violate the specification
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues.iter().filter(|issue| issue.severity == Severity::Error)
+    }
+    
+    /// Get the issues that are likely mistakes
+    pub fn warnings(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues.iter().filter(|issue| issue.severity == Severity::Warning)
+    }
+    
+    fn error(&mut self, pointer: impl Into<String>, message: impl Into<String>) {
+        self.push(Severity::Error, pointer.into(), message.into());
+    }
+    
+    fn warning(&mut self, pointer: impl Into<String>, message: impl Into<String>) {
+        self.push(Severity::Warning, pointer.into(), message.into());
+    }
+    
+    fn push(&mut self, severity: Severity, pointer: String, message: String) {
+        self.issues.push(ValidationIssue { severity, pointer, message });
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.issues.is_empty() {
+            return f.write_str("no issues");
+        }
+        
+        for (i, issue) in self.issues.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+impl Gltf {
+    /// Check the document against the glTF 2.0 specification
+    ///
+    /// Buffer contents are not available here, so buffer views are only checked against the
+    /// declared buffer lengths. Use `GltfBuilder::validate` to check the data as well.
+    pub fn validate(&self) -> ValidationReport {
+        validate(self, None)
+    }
+}
+
+/// Validate a document, checking buffer views and animation keyframes against the data of
+/// buffer 0 if it is given
+pub(crate) fn validate(gltf: &Gltf, buffer_data: Option<&[u8]>) -> ValidationReport {
+    let mut validator = Validator {
+        gltf,
+        buffer_data,
+        report: ValidationReport::default(),
+        invalid_buffer_views: HashSet::new(),
+        invalid_accessors: HashSet::new(),
+    };
+    
+    validator.check_asset();
+    validator.check_scenes();
+    validator.check_nodes();
+    validator.check_materials();
+    validator.check_textures();
+    validator.check_buffers();
+    validator.check_buffer_views();
+    validator.check_accessors();
+    // Meshes come after accessors so alignment checks can skip accessors that do not fit
+    validator.check_meshes();
+    validator.check_animations();
+    validator.check_skins();
+    validator.check_cameras();
+    validator.check_extensions();
+    
+    validator.report
+}
+
+/// Name of the lights extension, used by nodes and at the document root
+const LIGHTS_EXTENSION: &str = "KHR_lights_punctual";
+
+/// Name of the specular-glossiness material extension
+const SPECULAR_GLOSSINESS_EXTENSION: &str = "KHR_materials_pbrSpecularGlossiness";
+
+struct Validator<'a> {
+    gltf: &'a Gltf,
+    buffer_data: Option<&'a [u8]>,
+    report: ValidationReport,
+    /// Buffer views and accessors that do not fit their data, which must not be read
+    invalid_buffer_views: HashSet<usize>,
+    invalid_accessors: HashSet<usize>,
+}
+
+impl<'a> Validator<'a> {
+    fn check_asset(&mut self) {
+        if !self.gltf.asset.version.starts_with("2.") {
+            self.report.error("/asset/version", format!(
+                "Unsupported glTF version {}", self.gltf.asset.version
+            ));
+        }
+    }
+    
+    fn check_scenes(&mut self) {
+        let scene_count = count(&self.gltf.scenes);
+        let node_count = count(&self.gltf.nodes);
+        
+        if let Some(scene) = self.gltf.scene {
+            self.check_index("/scene", scene, scene_count, "Scene");
+        }
+        if scene_count == 0 && node_count > 0 {
+            self.report.warning("/scenes", "Document has nodes but no scene to display them");
+        }
+        
+        let parents = self.parents();
+        for (i, scene) in self.gltf.scenes.iter().flatten().enumerate() {
+            for (j, &node) in scene.nodes.iter().flatten().enumerate() {
+                let pointer = format!("/scenes/{}/nodes/{}", i, j);
+                if self.check_index(&pointer, node, node_count, "Node") && !parents[node].is_empty() {
+                    self.report.error(pointer, format!("Node {} is not a root node", node));
+                }
+            }
+        }
+    }
+    
+    fn check_nodes(&mut self) {
+        let gltf = self.gltf;
+        let node_count = count(&gltf.nodes);
+        let light_count = gltf.extensions.as_ref()
+            .and_then(|extensions| extensions.get(LIGHTS_EXTENSION))
+            .and_then(|lights_punctual| lights_punctual.get("lights"))
+            .and_then(|lights| lights.as_array())
+            .map_or(0, |lights| lights.len());
+        
+        let mut has_parent = vec![false; node_count];
+        for (i, node) in gltf.nodes.iter().flatten().enumerate() {
+            let pointer = format!("/nodes/{}", i);
+            
+            for (j, &child) in node.children.iter().flatten().enumerate() {
+                let child_pointer = format!("{}/children/{}", pointer, j);
+                if !self.check_index(&child_pointer, child, node_count, "Node") {
+                    continue;
+                }
+                if has_parent[child] {
+                    self.report.error(child_pointer, format!("Node {} has more than one parent", child));
+                }
+                has_parent[child] = true;
+            }
+            
+            if let Some(mesh) = node.mesh {
+                self.check_index(&format!("{}/mesh", pointer), mesh, count(&gltf.meshes), "Mesh");
+            }
+            if let Some(camera) = node.camera {
+                self.check_index(&format!("{}/camera", pointer), camera, count(&gltf.cameras), "Camera");
+            }
+            if let Some(light) = node.extensions.as_ref().and_then(|extensions| extensions.lights_punctual.as_ref()) {
+                let light_pointer = format!("{}/extensions/{}/light", pointer, LIGHTS_EXTENSION);
+                self.check_index(&light_pointer, light.light, light_count, "Light");
+            }
+            
+            if node.matrix.is_some() && (node.translation.is_some() || node.rotation.is_some() || node.scale.is_some()) {
+                self.report.error(format!("{}/matrix", pointer), "Node has both a matrix and translation, rotation or scale");
+            }
+            
+            if let Some(skin) = node.skin {
+                let skin_pointer = format!("{}/skin", pointer);
+                self.check_index(&skin_pointer, skin, count(&gltf.skins), "Skin");
+                
+                match node.mesh.and_then(|mesh| gltf.meshes.as_ref()?.get(mesh)) {
+                    None => self.report.error(skin_pointer, "Node has a skin but no mesh"),
+                    Some(mesh) => {
+                        let skinned = mesh.primitives.iter().all(|primitive| {
+                            primitive.attributes.contains_key("JOINTS_0") && primitive.attributes.contains_key("WEIGHTS_0")
+                        });
+                        if !skinned {
+                            self.report.error(skin_pointer, "Skinned mesh primitives need JOINTS_0 and WEIGHTS_0 attributes");
+                        }
+                    }
+                }
+            }
+        }
+        
+        // Follow the parent links from every node; with at most one parent each, a cycle is
+        // found by returning to a node already on the path
+        let parents = self.parents();
+        let mut on_reported_cycle = vec![false; node_count];
+        for start in 0..node_count {
+            let mut path = vec![start];
+            let mut current = start;
+            while let Some(&parent) = parents[current].first() {
+                if parent == start {
+                    if !on_reported_cycle[start] {
+                        for &node in &path {
+                            on_reported_cycle[node] = true;
+                        }
+                        // List the nodes from parent to child, starting at the first one found
+                        path[1..].reverse();
+                        let nodes: Vec<String> = path.iter().map(|node| node.to_string()).collect();
+                        self.report.error(format!("/nodes/{}/children", start), format!(
+                            "Node hierarchy has a cycle through nodes {}", nodes.join(", ")
+                        ));
+                    }
+                    break;
+                }
+                if path.contains(&parent) || path.len() > node_count {
+                    break;
+                }
+                path.push(parent);
+                current = parent;
+            }
+        }
+    }
+    
+    fn check_meshes(&mut self) {
+        let gltf = self.gltf;
+        
+        for (i, mesh) in gltf.meshes.iter().flatten().enumerate() {
+            let pointer = format!("/meshes/{}", i);
+            if mesh.primitives.is_empty() {
+                self.report.error(format!("{}/primitives", pointer), "Mesh has no primitives");
+            }
+            
+            let target_count = mesh.primitives.first()
+                .map_or(0, |primitive| primitive.targets.as_ref().map_or(0, |targets| targets.len()));
+            
+            for (j, primitive) in mesh.primitives.iter().enumerate() {
+                let primitive_pointer = format!("{}/primitives/{}", pointer, j);
+                self.check_primitive(&primitive_pointer, primitive);
+                
+                if primitive.targets.as_ref().map_or(0, |targets| targets.len()) != target_count {
+                    self.report.error(format!("{}/targets", primitive_pointer),
+                                      "All primitives of a mesh must have the same number of morph targets");
+                }
+            }
+            
+            if let Some(weights) = &mesh.weights {
+                if weights.len() != target_count {
+                    self.report.error(format!("{}/weights", pointer), format!(
+                        "Mesh has {} weights for {} morph targets", weights.len(), target_count
+                    ));
+                }
+            }
+        }
+    }
+    
+    fn check_primitive(&mut self, pointer: &str, primitive: &Primitive) {
+        let gltf = self.gltf;
+        let accessors = gltf.accessors.as_deref().unwrap_or_default();
+        
+        let mode = primitive.mode.unwrap_or(primitive_mode::TRIANGLES);
+        if mode > primitive_mode::TRIANGLE_FAN {
+            self.report.error(format!("{}/mode", pointer), format!("Invalid primitive mode {}", mode));
+        }
+        if let Some(material) = primitive.material {
+            self.check_index(&format!("{}/material", pointer), material, count(&gltf.materials), "Material");
+        }
+        
+        // Every attribute must have one element per vertex; POSITION defines the vertex count
+        let mut names: Vec<&String> = primitive.attributes.keys().collect();
+        names.sort_by_key(|name| (name.as_str() != "POSITION", name.as_str()));
+        if !primitive.attributes.contains_key("POSITION") {
+            self.report.warning(format!("{}/attributes", pointer), "Primitive has no POSITION attribute and will not be rendered");
+        }
+        
+        let mut vertex_count = None;
+        for name in names {
+            let attribute_pointer = format!("{}/attributes/{}", pointer, name);
+            let index = primitive.attributes[name];
+            if !self.check_index(&attribute_pointer, index, accessors.len(), "Accessor") {
+                continue;
+            }
+            
+            let accessor = &accessors[index];
+            match vertex_count {
+                None => vertex_count = Some(accessor.count),
+                Some(expected) if accessor.count != expected => {
+                    self.report.error(&attribute_pointer, format!(
+                        "Attribute has {} elements but the primitive has {} vertices", accessor.count, expected
+                    ));
+                }
+                _ => {}
+            }
+            
+            if name == "POSITION" {
+                self.check_position_accessor(&attribute_pointer, accessor);
+            }
+            self.check_vertex_alignment(&attribute_pointer, index);
+        }
+        
+        if let Some(indices) = primitive.indices {
+            let indices_pointer = format!("{}/indices", pointer);
+            if self.check_index(&indices_pointer, indices, accessors.len(), "Accessor") {
+                let accessor = &accessors[indices];
+                let unsigned = [component_type::UNSIGNED_BYTE, component_type::UNSIGNED_SHORT, component_type::UNSIGNED_INT];
+                if accessor.type_ != accessor_type::SCALAR || !unsigned.contains(&accessor.component_type) {
+                    self.report.error(&indices_pointer, "Indices must be unsigned byte, short or int scalars");
+                }
+                
+                let multiple = match mode {
+                    primitive_mode::TRIANGLES => 3,
+                    primitive_mode::LINES => 2,
+                    _ => 1,
+                };
+                if accessor.count % multiple != 0 {
+                    self.report.error(&indices_pointer, format!(
+                        "Index count {} is not a multiple of {} for the primitive mode", accessor.count, multiple
+                    ));
+                }
+            }
+        }
+        
+        for (t, target) in primitive.targets.iter().flatten().enumerate() {
+            let mut names: Vec<&String> = target.keys().collect();
+            names.sort();
+            
+            for name in names {
+                let target_pointer = format!("{}/targets/{}/{}", pointer, t, name);
+                let index = target[name];
+                if !self.check_index(&target_pointer, index, accessors.len(), "Accessor") {
+                    continue;
+                }
+                
+                let accessor = &accessors[index];
+                if let Some(expected) = vertex_count.filter(|&expected| expected != accessor.count) {
+                    self.report.error(&target_pointer, format!(
+                        "Morph target has {} elements but the primitive has {} vertices", accessor.count, expected
+                    ));
+                }
+                if name == "POSITION" {
+                    self.check_position_accessor(&target_pointer, accessor);
+                }
+                self.check_vertex_alignment(&target_pointer, index);
+            }
+        }
+    }
+    
+    /// POSITION accessors must be float VEC3 with bounds
+    fn check_position_accessor(&mut self, pointer: &str, accessor: &Accessor) {
+        if accessor.type_ != accessor_type::VEC3 || accessor.component_type != component_type::FLOAT {
+            self.report.error(pointer, "POSITION must be a float VEC3 accessor");
+        }
+        if accessor.min.is_none() || accessor.max.is_none() {
+            self.report.error(pointer, "POSITION accessors must define min and max");
+        }
+    }
+    
+    /// Every element of a vertex attribute must start on a 4-byte boundary
+    fn check_vertex_alignment(&mut self, pointer: &str, index: usize) {
+        if self.invalid_accessors.contains(&index) {
+            return;
+        }
+        let Some(accessor) = self.gltf.accessors.as_ref().and_then(|accessors| accessors.get(index)) else {
+            return;
+        };
+        let Some(view) = accessor.buffer_view
+            .filter(|view| !self.invalid_buffer_views.contains(view))
+            .and_then(|view| self.gltf.buffer_views.as_ref()?.get(view)) else {
+            return;
+        };
+        let Some(offset) = view.byte_offset.checked_add(accessor.byte_offset.unwrap_or(0)) else {
+            return;
+        };
+        
+        // Without a stride, elements are tightly packed and the element size is the stride
+        let stride = view.byte_stride.or_else(|| element_size(accessor)).unwrap_or(0);
+        if offset % 4 != 0 || (accessor.count > 1 && stride % 4 != 0) {
+            self.report.error(pointer, "Vertex attribute elements must be aligned to 4 bytes");
+        }
+    }
+    
+    fn check_materials(&mut self) {
+        let texture_count = count(&self.gltf.textures);
+        
+        for (i, material) in self.gltf.materials.iter().flatten().enumerate() {
+            let pointer = format!("/materials/{}", i);
+            let mut textures = Vec::new();
+            
+            if let Some(pbr) = &material.pbr_metallic_roughness {
+                let pbr_pointer = format!("{}/pbrMetallicRoughness", pointer);
+                if let Some(info) = &pbr.base_color_texture {
+                    textures.push((format!("{}/baseColorTexture", pbr_pointer), info.index));
+                }
+                if let Some(info) = &pbr.metallic_roughness_texture {
+                    textures.push((format!("{}/metallicRoughnessTexture", pbr_pointer), info.index));
+                }
+            }
+            if let Some(info) = &material.normal_texture {
+                textures.push((format!("{}/normalTexture", pointer), info.index));
+            }
+            if let Some(info) = &material.occlusion_texture {
+                textures.push((format!("{}/occlusionTexture", pointer), info.index));
+            }
+            if let Some(info) = &material.emissive_texture {
+                textures.push((format!("{}/emissiveTexture", pointer), info.index));
+            }
+            if let Some(specular) = material.extensions.as_ref().and_then(|extensions| extensions.pbr_specular_glossiness.as_ref()) {
+                let extension_pointer = format!("{}/extensions/{}", pointer, SPECULAR_GLOSSINESS_EXTENSION);
+                if let Some(info) = &specular.diffuse_texture {
+                    textures.push((format!("{}/diffuseTexture", extension_pointer), info.index));
+                }
+                if let Some(info) = &specular.specular_glossiness_texture {
+                    textures.push((format!("{}/specularGlossinessTexture", extension_pointer), info.index));
+                }
+            }
+            
+            for (texture_pointer, index) in textures {
+                self.check_index(&format!("{}/index", texture_pointer), index, texture_count, "Texture");
+            }
+        }
+    }
+    
+    fn check_textures(&mut self) {
+        let gltf = self.gltf;
+        
+        for (i, texture) in gltf.textures.iter().flatten().enumerate() {
+            let pointer = format!("/textures/{}", i);
+            self.check_index(&format!("{}/source", pointer), texture.source, count(&gltf.images), "Image");
+            if let Some(sampler) = texture.sampler {
+                self.check_index(&format!("{}/sampler", pointer), sampler, count(&gltf.samplers), "Sampler");
+            }
+        }
+        
+        for (i, image) in gltf.images.iter().flatten().enumerate() {
+            let pointer = format!("/images/{}", i);
+            match (&image.uri, image.buffer_view) {
+                (Some(_), Some(_)) => self.report.error(pointer, "Image has both a URI and a buffer view"),
+                (None, None) => self.report.error(pointer, "Image has neither a URI nor a buffer view"),
+                (None, Some(view)) => {
+                    self.check_index(&format!("{}/bufferView", pointer), view, count(&gltf.buffer_views), "Buffer view");
+                    if image.mime_type.is_none() {
+                        self.report.error(format!("{}/mimeType", pointer), "Images stored in buffer views need a MIME type");
+                    }
+                }
+                (Some(_), None) => {}
+            }
+        }
+    }
+    
+    fn check_buffers(&mut self) {
+        for (i, buffer) in self.gltf.buffers.iter().flatten().enumerate() {
+            let pointer = format!("/buffers/{}", i);
+            if buffer.byte_length == 0 {
+                self.report.error(format!("{}/byteLength", pointer), "Buffer is empty");
+            }
+            if buffer.uri.is_none() && i > 0 {
+                self.report.error(&pointer, "Only the first buffer can omit its URI, for the GLB binary chunk");
+            }
+            if let Some(data) = self.buffer_data.filter(|_| i == 0) {
+                if buffer.byte_length > data.len() {
+                    self.report.error(format!("{}/byteLength", pointer), format!(
+                        "Buffer length {} exceeds the {} bytes of buffer data", buffer.byte_length, data.len()
+                    ));
+                }
+            }
+        }
+    }
+    
+    fn check_buffer_views(&mut self) {
+        let buffers = self.gltf.buffers.as_deref().unwrap_or_default();
+        
+        for (i, view) in self.gltf.buffer_views.iter().flatten().enumerate() {
+            let pointer = format!("/bufferViews/{}", i);
+            if view.byte_length == 0 {
+                self.report.error(format!("{}/byteLength", pointer), "Buffer view is empty");
+            }
+            if let Some(stride) = view.byte_stride {
+                if !(4..=252).contains(&stride) || stride % 4 != 0 {
+                    self.report.error(format!("{}/byteStride", pointer), format!(
+                        "Byte stride {} must be a multiple of 4 between 4 and 252", stride
+                    ));
+                }
+            }
+            
+            if !self.check_index(&format!("{}/buffer", pointer), view.buffer, buffers.len(), "Buffer") {
+                self.invalid_buffer_views.insert(i);
+                continue;
+            }
+            
+            let Some(end) = view.byte_offset.checked_add(view.byte_length) else {
+                self.report.error(&pointer, "Buffer view offset and length overflow");
+                self.invalid_buffer_views.insert(i);
+                continue;
+            };
+            let available = match self.buffer_data.filter(|_| view.buffer == 0) {
+                Some(data) => buffers[0].byte_length.min(data.len()),
+                None => buffers[view.buffer].byte_length,
+            };
+            if end > available {
+                self.report.error(&pointer, format!(
+                    "Buffer view ends at byte {}, beyond the {} bytes of buffer {}", end, available, view.buffer
+                ));
+                self.invalid_buffer_views.insert(i);
+            }
+        }
+    }
+    
+    fn check_accessors(&mut self) {
+        let buffer_views = self.gltf.buffer_views.as_deref().unwrap_or_default();
+        
+        for (i, accessor) in self.gltf.accessors.iter().flatten().enumerate() {
+            let pointer = format!("/accessors/{}", i);
+            
+            let (Ok(components), Ok(value_size)) = (type_components(&accessor.type_), component_size(accessor.component_type)) else {
+                self.report.error(&pointer, format!(
+                    "Invalid accessor type {} or component type {}", accessor.type_, accessor.component_type
+                ));
+                self.invalid_accessors.insert(i);
+                continue;
+            };
+            
+            if accessor.count == 0 {
+                self.report.error(format!("{}/count", pointer), "Accessor has no elements");
+            }
+            for (name, bounds) in [("min", &accessor.min), ("max", &accessor.max)] {
+                if bounds.as_ref().is_some_and(|bounds| bounds.len() != components) {
+                    self.report.error(format!("{}/{}", pointer, name), format!(
+                        "Accessor {} must have {} values", name, components
+                    ));
+                }
+            }
+            if accessor.normalized == Some(true)
+                && [component_type::FLOAT, component_type::UNSIGNED_INT].contains(&accessor.component_type) {
+                self.report.error(format!("{}/normalized", pointer), "Float and unsigned int accessors cannot be normalized");
+            }
+            
+            if let Some(sparse) = &accessor.sparse {
+                let sparse_pointer = format!("{}/sparse", pointer);
+                let indices_valid = self.check_index(&format!("{}/indices/bufferView", sparse_pointer), sparse.indices.buffer_view, buffer_views.len(), "Buffer view");
+                let values_valid = self.check_index(&format!("{}/values/bufferView", sparse_pointer), sparse.values.buffer_view, buffer_views.len(), "Buffer view");
+                if !indices_valid || !values_valid
+                    || self.invalid_buffer_views.contains(&sparse.indices.buffer_view)
+                    || self.invalid_buffer_views.contains(&sparse.values.buffer_view) {
+                    self.invalid_accessors.insert(i);
+                }
+            }
+            
+            let Some(view_index) = accessor.buffer_view else {
+                if accessor.byte_offset.is_some() {
+                    self.report.error(format!("{}/byteOffset", pointer), "Accessor without a buffer view has a byte offset");
+                }
+                continue;
+            };
+            if !self.check_index(&format!("{}/bufferView", pointer), view_index, buffer_views.len(), "Buffer view") {
+                self.invalid_accessors.insert(i);
+                continue;
+            }
+            if self.invalid_buffer_views.contains(&view_index) {
+                self.invalid_accessors.insert(i);
+            }
+            
+            let view = &buffer_views[view_index];
+            let offset = accessor.byte_offset.unwrap_or(0);
+            if view.byte_offset.checked_add(offset).is_some_and(|start| start % value_size != 0) {
+                self.report.error(format!("{}/byteOffset", pointer), format!(
+                    "Accessor data must be aligned to its component size of {} bytes", value_size
+                ));
+            }
+            
+            let element_size = element_size(accessor).unwrap_or(components * value_size);
+            let stride = view.byte_stride.unwrap_or(element_size);
+            let end = stride.checked_mul(accessor.count.saturating_sub(1))
+                .and_then(|size| size.checked_add(offset))
+                .and_then(|size| size.checked_add(element_size));
+            match end {
+                None => {
+                    self.report.error(&pointer, "Accessor offset and size overflow");
+                    self.invalid_accessors.insert(i);
+                }
+                Some(end) if accessor.count > 0 && end > view.byte_length => {
+                    self.report.error(&pointer, format!(
+                        "Accessor data ends at byte {}, beyond the {} bytes of buffer view {}", end, view.byte_length, view_index
+                    ));
+                    self.invalid_accessors.insert(i);
+                }
+                Some(_) => {}
+            }
+        }
+    }
+    
+    fn check_animations(&mut self) {
+        let gltf = self.gltf;
+        let accessors = gltf.accessors.as_deref().unwrap_or_default();
+        let nodes = gltf.nodes.as_deref().unwrap_or_default();
+        
+        for (i, animation) in gltf.animations.iter().flatten().enumerate() {
+            let pointer = format!("/animations/{}", i);
+            let samplers = animation.samplers.as_deref().unwrap_or_default();
+            
+            // The path each sampler drives, which determines its output layout
+            let mut sampler_targets: HashMap<usize, (&str, usize)> = HashMap::new();
+            let mut targets = HashSet::new();
+            
+            for (j, channel) in animation.channels.iter().flatten().enumerate() {
+                let channel_pointer = format!("{}/channels/{}", pointer, j);
+                let path = channel.target.path.as_str();
+                
+                let sampler_valid = self.check_index(&format!("{}/sampler", channel_pointer), channel.sampler, samplers.len(), "Animation sampler");
+                if !self.check_index(&format!("{}/target/node", channel_pointer), channel.target.node, nodes.len(), "Node") {
+                    continue;
+                }
+                
+                if !targets.insert((channel.target.node, path)) {
+                    self.report.error(format!("{}/target", channel_pointer), format!(
+                        "Node {} has more than one channel animating {}", channel.target.node, path
+                    ));
+                }
+                
+                let target_count = nodes[channel.target.node].mesh
+                    .and_then(|mesh| gltf.meshes.as_ref()?.get(mesh))
+                    .and_then(|mesh| mesh.primitives.first())
+                    .map_or(0, |primitive| primitive.targets.as_ref().map_or(0, |targets| targets.len()));
+                
+                let values_per_key = match path {
+                    "translation" | "rotation" | "scale" => 1,
+                    "weights" => {
+                        if target_count == 0 {
+                            self.report.error(format!("{}/target/path", channel_pointer), "Animated weights need a node whose mesh has morph targets");
+                        }
+                        target_count
+                    }
+                    _ => {
+                        self.report.error(format!("{}/target/path", channel_pointer), format!("Invalid animation path {}", path));
+                        continue;
+                    }
+                };
+                if sampler_valid {
+                    sampler_targets.insert(channel.sampler, (path, values_per_key));
+                }
+            }
+            
+            for (j, sampler) in samplers.iter().enumerate() {
+                let sampler_pointer = format!("{}/samplers/{}", pointer, j);
+                let interpolation = sampler.interpolation.as_deref().unwrap_or("LINEAR");
+                if !["LINEAR", "STEP", "CUBICSPLINE"].contains(&interpolation) {
+                    self.report.error(format!("{}/interpolation", sampler_pointer), format!("Invalid interpolation {}", interpolation));
+                }
+                
+                let input_pointer = format!("{}/input", sampler_pointer);
+                let output_pointer = format!("{}/output", sampler_pointer);
+                let input_valid = self.check_index(&input_pointer, sampler.input, accessors.len(), "Accessor");
+                let output_valid = self.check_index(&output_pointer, sampler.output, accessors.len(), "Accessor");
+                if !input_valid || !output_valid {
+                    continue;
+                }
+                
+                let input = &accessors[sampler.input];
+                if input.type_ != accessor_type::SCALAR || input.component_type != component_type::FLOAT {
+                    self.report.error(&input_pointer, "Animation input must be float scalars");
+                }
+                if input.min.is_none() || input.max.is_none() {
+                    self.report.error(&input_pointer, "Animation input accessors must define min and max");
+                }
+                self.check_keyframe_times(&input_pointer, sampler.input);
+                
+                let Some(&(path, values_per_key)) = sampler_targets.get(&j) else {
+                    self.report.warning(&sampler_pointer, "Sampler is not used by any channel");
+                    continue;
+                };
+                
+                let cubic = interpolation == "CUBICSPLINE";
+                if cubic && input.count < 2 {
+                    self.report.error(&input_pointer, "Cubic spline animations need at least two keyframes");
+                }
+                
+                // Cubic splines store an in-tangent, value and out-tangent for every keyframe
+                let output = &accessors[sampler.output];
+                let expected = input.count
+                    .checked_mul(values_per_key)
+                    .and_then(|count| count.checked_mul(if cubic { 3 } else { 1 }));
+                let Some(expected) = expected else {
+                    self.report.error(&output_pointer, format!("{} keyframes need more output elements than can be counted", input.count));
+                    continue;
+                };
+                if output.count != expected {
+                    self.report.error(&output_pointer, format!(
+                        "Animation output has {} elements but {} keyframes need {}", output.count, input.count, expected
+                    ));
+                }
+                
+                let expected_type = match path {
+                    "translation" | "scale" => accessor_type::VEC3,
+                    "rotation" => accessor_type::VEC4,
+                    _ => accessor_type::SCALAR,
+                };
+                if output.type_ != expected_type {
+                    self.report.error(&output_pointer, format!(
+                        "Animation output for {} must be {}, found {}", path, expected_type, output.type_
+                    ));
+                }
+            }
+        }
+    }
+    
+    /// Keyframe times must be non-negative and strictly increasing, which can only be
+    /// checked with the buffer data
+    fn check_keyframe_times(&mut self, pointer: &str, accessor: usize) {
+        // Accessors that failed the bounds checks were already reported and cannot be read
+        let Some(data) = self.buffer_data.filter(|_| !self.invalid_accessors.contains(&accessor)) else {
+            return;
+        };
+        let Ok(times) = AccessorReader::new(self.gltf, data).read_scalars(accessor) else {
+            return;
+        };
+        
+        if times.first().is_some_and(|&time| time < 0.0) {
+            self.report.error(pointer, "Keyframe times must not be negative");
+        }
+        if let Some(key) = times.windows(2).position(|pair| pair[1] <= pair[0]) {
+            self.report.error(pointer, format!(
+                "Keyframe times must be strictly increasing, but keyframe {} is not after keyframe {}", key + 1, key
+            ));
+        }
+    }
+    
+    fn check_skins(&mut self) {
+        let gltf = self.gltf;
+        let node_count = count(&gltf.nodes);
+        let accessors = gltf.accessors.as_deref().unwrap_or_default();
+        
+        for (i, skin) in gltf.skins.iter().flatten().enumerate() {
+            let pointer = format!("/skins/{}", i);
+            if skin.joints.is_empty() {
+                self.report.error(format!("{}/joints", pointer), "Skin has no joints");
+            }
+            for (j, &joint) in skin.joints.iter().enumerate() {
+                self.check_index(&format!("{}/joints/{}", pointer, j), joint, node_count, "Node");
+            }
+            if let Some(skeleton) = skin.skeleton {
+                self.check_index(&format!("{}/skeleton", pointer), skeleton, node_count, "Node");
+            }
+            
+            if let Some(matrices) = skin.inverse_bind_matrices {
+                let matrices_pointer = format!("{}/inverseBindMatrices", pointer);
+                if self.check_index(&matrices_pointer, matrices, accessors.len(), "Accessor") {
+                    let accessor = &accessors[matrices];
+                    if accessor.type_ != accessor_type::MAT4 || accessor.component_type != component_type::FLOAT {
+                        self.report.error(&matrices_pointer, "Inverse bind matrices must be a float MAT4 accessor");
+                    }
+                    if accessor.count < skin.joints.len() {
+                        self.report.error(&matrices_pointer, format!(
+                            "Skin has {} joints but {} inverse bind matrices", skin.joints.len(), accessor.count
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    
+    fn check_cameras(&mut self) {
+        for (i, camera) in self.gltf.cameras.iter().flatten().enumerate() {
+            let consistent = match camera.type_.as_str() {
+                "perspective" => camera.perspective.is_some() && camera.orthographic.is_none(),
+                "orthographic" => camera.orthographic.is_some() && camera.perspective.is_none(),
+                _ => false,
+            };
+            if !consistent {
+                self.report.error(format!("/cameras/{}", i), format!(
+                    "Camera of type {} must have exactly the matching projection", camera.type_
+                ));
+            }
+        }
+    }
+    
+    fn check_extensions(&mut self) {
+        let gltf = self.gltf;
+        
+        let mut used = BTreeSet::new();
+        if let Some(extensions) = gltf.extensions.as_ref().and_then(|extensions| extensions.as_object()) {
+            used.extend(extensions.keys().cloned());
+        }
+        if gltf.nodes.iter().flatten().any(|node| node.extensions.as_ref().is_some_and(|extensions| extensions.lights_punctual.is_some())) {
+            used.insert(LIGHTS_EXTENSION.to_string());
+        }
+        if gltf.materials.iter().flatten().any(|material| material.extensions.as_ref().is_some_and(|extensions| extensions.pbr_specular_glossiness.is_some())) {
+            used.insert(SPECULAR_GLOSSINESS_EXTENSION.to_string());
+        }
+        
+        let declared = gltf.extensions_used.as_deref().unwrap_or_default();
+        for extension in &used {
+            if !declared.contains(extension) {
+                self.report.error("/extensionsUsed", format!("Extension {} is used but not declared", extension));
+            }
+        }
+        for (i, extension) in declared.iter().enumerate() {
+            if !used.contains(extension) {
+                self.report.warning(format!("/extensionsUsed/{}", i), format!("Extension {} is declared but not used", extension));
+            }
+        }
+        for (i, extension) in gltf.extensions_required.iter().flatten().enumerate() {
+            if !declared.contains(extension) {
+                self.report.error(format!("/extensionsRequired/{}", i), format!(
+                    "Required extension {} is not in extensionsUsed", extension
+                ));
+            }
+        }
+    }
+    
+    /// The parents of every node, ignoring out-of-range children
+    fn parents(&self) -> Vec<Vec<usize>> {
+        let mut parents = vec![Vec::new(); count(&self.gltf.nodes)];
+        for (i, node) in self.gltf.nodes.iter().flatten().enumerate() {
+            for &child in node.children.iter().flatten() {
+                if let Some(child_parents) = parents.get_mut(child) {
+                    child_parents.push(i);
+                }
+            }
+        }
+        parents
+    }
+    
+    /// Report an error unless an index refers to one of `len` objects
+    fn check_index(&mut self, pointer: &str, index: usize, len: usize, kind: &str) -> bool {
+        if index < len {
+            return true;
+        }
+        
+        self.report.error(pointer, format!("{} {} does not exist", kind, index));
+        false
+    }
+}
+
+/// Size in bytes of one accessor element, or `None` for an invalid type
+fn element_size(accessor: &Accessor) -> Option<usize> {
+    let components = type_components(&accessor.type_).ok()?;
+    let value_size = component_size(accessor.component_type).ok()?;
+    
+    // Matrix columns of 1- and 2-byte components are padded to 4 bytes
+    Some(match (accessor.type_.as_str(), value_size) {
+        (accessor_type::MAT2, 1) => 8,
+        (accessor_type::MAT3, 1) => 12,
+        (accessor_type::MAT3, 2) => 24,
+        _ => components * value_size,
+    })
+}
+
+/// Number of objects in an optional document array
+fn count<T>(items: &Option<Vec<T>>) -> usize {
+    items.as_ref().map_or(0, |items| items.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{GltfBuilder, GltfError, InterpolationType};
+    
+    /// A builder with one node animated by a two-keyframe translation
+    fn animated() -> GltfBuilder {
+        let mut builder = GltfBuilder::new();
+        let node = builder.add_node(None, None, None, None, None);
+        builder.add_scene(None, Some(vec![node]));
+        let animation = builder.add_animation(None);
+        builder.create_translation_animation(animation, node, vec![0.0, 1.0], vec![[0.0; 3], [1.0; 3]],
+                                             InterpolationType::Linear).unwrap();
+        builder
+    }
+    
+    #[test]
+    fn buffer_view_overflow_is_an_error() {
+        let mut builder = animated();
+        builder.gltf.buffer_views.as_mut().unwrap()[0].byte_offset = usize::MAX;
+        
+        let report = builder.validate();
+        
+        assert!(report.errors().any(|issue| issue.pointer == "/bufferViews/0"));
+    }
+    
+    #[test]
+    fn accessor_size_overflow_is_an_error() {
+        let mut builder = animated();
+        builder.gltf.accessors.as_mut().unwrap()[0].count = usize::MAX;
+        
+        let report = builder.validate();
+        
+        assert!(report.errors().any(|issue| issue.pointer == "/accessors/0" && issue.message.contains("overflow")));
+    }
+    
+    #[test]
+    fn out_of_bounds_keyframes_are_not_read() {
+        let mut builder = animated();
+        builder.gltf.accessors.as_mut().unwrap()[0].byte_offset = Some(4);
+        
+        let report = builder.validate();
+        
+        let pointers: Vec<_> = report.errors().map(|issue| issue.pointer.as_str()).collect();
+        assert_eq!(pointers, vec!["/accessors/0"]);
+    }
+    
+    #[test]
+    fn vertex_offset_overflow_does_not_panic() {
+        let mut builder = GltfBuilder::new();
+        builder.create_box(1.0);
+        let position = builder.gltf.meshes.as_ref().unwrap()[0].primitives[0].attributes["POSITION"];
+        let accessor = &mut builder.gltf.accessors.as_mut().unwrap()[position];
+        accessor.byte_offset = Some(8);
+        let view = accessor.buffer_view.unwrap();
+        builder.gltf.buffer_views.as_mut().unwrap()[view].byte_offset = usize::MAX - 2;
+        
+        let report = builder.validate();
+        
+        assert!(report.errors().any(|issue| issue.pointer == format!("/bufferViews/{}", view)));
+    }
+    
+    #[test]
+    fn keyframe_count_overflow_is_an_error() {
+        let mut builder = animated();
+        let sampler = &mut builder.gltf.animations.as_mut().unwrap()[0].samplers.as_mut().unwrap()[0];
+        sampler.interpolation = Some("CUBICSPLINE".to_string());
+        let input = sampler.input;
+        builder.gltf.accessors.as_mut().unwrap()[input].count = usize::MAX / 2;
+        
+        let report = builder.validate();
+        
+        assert!(report.errors().any(|issue| issue.pointer == "/animations/0/samplers/0/output"));
+    }
+    
+    /// A builder with one box in its scene, returning the index of the POSITION accessor
+    fn boxed() -> (GltfBuilder, usize) {
+        let mut builder = GltfBuilder::new();
+        let mesh = builder.create_box(1.0);
+        let node = builder.add_node(None, Some(mesh), None, None, None);
+        builder.add_scene(None, Some(vec![node]));
+        let position = builder.gltf.meshes.as_ref().unwrap()[0].primitives[0].attributes["POSITION"];
+        (builder, position)
+    }
+    
+    #[test]
+    fn valid_documents_have_no_errors() {
+        assert!(animated().validate().is_valid());
+        assert!(boxed().0.validate().is_valid());
+    }
+    
+    #[test]
+    fn children_cycle_is_an_error() {
+        let mut builder = GltfBuilder::new();
+        let child = builder.add_node(None, None, None, None, None);
+        let parent = builder.add_node_with_children(None, None, None, None, None, vec![child]);
+        builder.add_scene(None, Some(vec![parent]));
+        builder.add_child_to_node(child, parent).unwrap();
+        
+        let report = builder.validate();
+        
+        assert!(report.errors().any(|issue| issue.message.contains("cycle")));
+    }
+    
+    #[test]
+    fn node_with_two_parents_is_an_error() {
+        let mut builder = GltfBuilder::new();
+        let child = builder.add_node(None, None, None, None, None);
+        let first = builder.add_node_with_children(None, None, None, None, None, vec![child]);
+        let second = builder.add_node_with_children(None, None, None, None, None, vec![child]);
+        builder.add_scene(None, Some(vec![first, second]));
+        
+        let report = builder.validate();
+        
+        let pointer = format!("/nodes/{}/children/0", second.index());
+        assert!(report.errors().any(|issue| issue.pointer == pointer && issue.message.contains("more than one parent")));
+    }
+    
+    #[test]
+    fn position_without_bounds_is_an_error() {
+        let (mut builder, position) = boxed();
+        builder.gltf.accessors.as_mut().unwrap()[position].min = None;
+        
+        let report = builder.validate();
+        
+        let pointer = "/meshes/0/primitives/0/attributes/POSITION";
+        assert!(report.errors().any(|issue| issue.pointer == pointer && issue.message.contains("min and max")));
+    }
+    
+    #[test]
+    fn attribute_count_mismatch_is_an_error() {
+        let (mut builder, _) = boxed();
+        let normal = builder.gltf.meshes.as_ref().unwrap()[0].primitives[0].attributes["NORMAL"];
+        builder.gltf.accessors.as_mut().unwrap()[normal].count -= 1;
+        
+        let report = builder.validate();
+        
+        assert!(report.errors().any(|issue| issue.pointer.starts_with("/meshes/0/primitives/0/attributes/")
+            && issue.message.contains("but the primitive has")));
+    }
+    
+    #[test]
+    fn misaligned_vertex_attribute_is_an_error() {
+        let (mut builder, position) = boxed();
+        let accessor = &mut builder.gltf.accessors.as_mut().unwrap()[position];
+        // One element fewer keeps the shifted accessor inside its buffer view
+        accessor.count -= 1;
+        accessor.byte_offset = Some(2);
+        
+        let report = builder.validate();
+        
+        let pointer = "/meshes/0/primitives/0/attributes/POSITION";
+        assert!(report.errors().any(|issue| issue.pointer == pointer && issue.message.contains("aligned to 4 bytes")));
+    }
+    
+    #[test]
+    fn linear_output_count_must_match_keyframes() {
+        let mut builder = animated();
+        let output = builder.gltf.animations.as_ref().unwrap()[0].samplers.as_ref().unwrap()[0].output;
+        builder.gltf.accessors.as_mut().unwrap()[output].count = 1;
+        
+        let report = builder.validate();
+        
+        assert!(report.errors().any(|issue| issue.pointer == "/animations/0/samplers/0/output"
+            && issue.message.contains("2 keyframes need 2")));
+    }
+    
+    #[test]
+    fn cubic_spline_output_needs_three_values_per_keyframe() {
+        let mut builder = animated();
+        builder.gltf.animations.as_mut().unwrap()[0].samplers.as_mut().unwrap()[0].interpolation = Some("CUBICSPLINE".to_string());
+        
+        let report = builder.validate();
+        
+        assert!(report.errors().any(|issue| issue.pointer == "/animations/0/samplers/0/output"
+            && issue.message.contains("2 keyframes need 6")));
+    }
+    
+    #[test]
+    fn unregistered_declared_extension_is_a_warning() {
+        let mut builder = animated();
+        builder.gltf.extensions_used = Some(vec!["EXT_unknown".to_string()]);
+        
+        let report = builder.validate();
+        
+        assert!(report.is_valid());
+        assert!(report.warnings().any(|issue| issue.pointer == "/extensionsUsed/0" && issue.message.contains("EXT_unknown")));
+    }
+    
+    #[test]
+    fn validate_on_export_rejects_invalid_documents() {
+        let (mut builder, position) = boxed();
+        builder.gltf.accessors.as_mut().unwrap()[position].max = None;
+        assert!(builder.to_glb_bytes().is_ok());
+        
+        builder.set_validate_on_export(true);
+        
+        assert!(matches!(builder.to_glb_bytes(), Err(GltfError::Validation(_))));
+        let path = std::env::temp_dir().join("mesh_tools_validate_on_export.glb");
+        let result = builder.export_glb(path.to_str().unwrap());
+        let _ = std::fs::remove_file(&path);
+        assert!(matches!(result, Err(GltfError::Validation(_))));
+    }
+}